@@ -1,25 +1,47 @@
 use std::process::Stdio;
+use aws_sdk_s3::error::{DisplayErrorContext, ProvideErrorMetadata};
+use aws_sdk_s3::types::{
+    BucketLifecycleConfiguration, BucketLocationConstraint, BucketVersioningStatus, CreateBucketConfiguration, Delete,
+    ExpirationStatus, LifecycleRule, LifecycleRuleFilter, ObjectIdentifier, PublicAccessBlockConfiguration, ServerSideEncryption,
+    ServerSideEncryptionByDefault, ServerSideEncryptionConfiguration, ServerSideEncryptionRule, Transition,
+    TransitionStorageClass, VersioningConfiguration,
+};
+use serde_json::json;
 use tauri::command;
 use tokio::process::Command;
+use tracing::{debug, info, error};
 
 use crate::models::*;
+use crate::process::{run_with_timeout, QUICK_TIMEOUT};
+use crate::binary_resolver::get_aws_binary_path;
+use crate::network::apply_network_env;
 
-/// Get AWS binary path - uses awscli package from system PATH
-fn get_aws_command() -> Result<String, String> {
-    // Use aws from PATH (awscli package)
-    Ok("aws".to_string())
+/// AWS CLI to run: the aws_cli_path setting, or else the first one get_aws_binary_path finds
+async fn get_aws_command() -> Result<String, String> {
+    let settings = crate::config::load_config().await?.settings;
+    get_aws_binary_path(settings.aws_cli_path.as_deref())
+        .map(|path| path.to_string_lossy().to_string())
+}
+
+/// A Command for the AWS CLI at `aws_cmd`, going through the configured proxy
+fn aws_command(aws_cmd: &str) -> Command {
+    let mut cmd = Command::new(aws_cmd);
+    apply_network_env(&mut cmd);
+    cmd
 }
 
 #[command]
 pub async fn check_aws_credentials() -> Result<bool, String> {
-    let aws_cmd = get_aws_command()?;
-    let output = Command::new(aws_cmd)
-        .args(&["sts", "get-caller-identity"])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .await
-        .map_err(|e| format!("Failed to execute AWS CLI: {}", e))?;
+    let aws_cmd = get_aws_command().await?;
+    let output = run_with_timeout(
+        aws_command(&aws_cmd)
+            .args(["sts", "get-caller-identity"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped()),
+        QUICK_TIMEOUT,
+    )
+    .await
+    .map_err(|e| format!("Failed to execute AWS CLI: {}", e))?;
 
     Ok(output.status.success())
 }
@@ -29,34 +51,50 @@ pub async fn configure_aws_credentials(
     accessKeyId: String,
     secretAccessKey: String,
     region: String,
-    profileName: Option<String>
+    profileName: Option<String>,
+    session_token: Option<String>
 ) -> Result<String, String> {
     let profile = profileName.unwrap_or_else(|| "default".to_string());
-    
+    let session_token = session_token.filter(|t| !t.trim().is_empty());
+
     // Configure AWS CLI with access keys
-    let output_format = "json".to_string();
-    let commands = vec![
-        ("aws_access_key_id", &accessKeyId),
-        ("aws_secret_access_key", &secretAccessKey),
-        ("region", &region),
-        ("output", &output_format),
+    let mut values = vec![
+        ("aws_access_key_id", accessKeyId.as_str()),
+        ("aws_secret_access_key", secretAccessKey.as_str()),
+        ("region", region.as_str()),
+        ("output", "json"),
     ];
+    if let Some(token) = &session_token {
+        values.push(("aws_session_token", token.as_str()));
+    }
+    set_aws_cli_profile(&profile, &values).await?;
+
+    // Test the credentials
+    test_aws_cli_profile(&profile).await?;
+
+    let success_message = format!("AWS credentials configured and validated successfully! Profile: {}, Region: {}", profile, region);
+    Ok(success_message)
+}
 
-    for (key, value) in commands {
+/// Write `key = value` pairs into an AWS CLI profile with `aws configure set`
+async fn set_aws_cli_profile(profile: &str, values: &[(&str, &str)]) -> Result<(), String> {
+    for (key, value) in values {
         let cmd_args = vec![
             "configure".to_string(), 
             "set".to_string(),
             format!("profile.{}.{}", profile, key),
             value.to_string()
         ];
-        let aws_cmd = get_aws_command()?;
-        let output = Command::new(aws_cmd)
-            .args(&cmd_args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await
-            .map_err(|e| format!("Failed to configure AWS CLI: {}", e))?;
+        let aws_cmd = get_aws_command().await?;
+        let output = run_with_timeout(
+            aws_command(&aws_cmd)
+                .args(&cmd_args)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()),
+            QUICK_TIMEOUT,
+        )
+        .await
+        .map_err(|e| format!("Failed to configure AWS CLI: {}", e))?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -64,23 +102,164 @@ pub async fn configure_aws_credentials(
         }
     }
 
-    // Test the credentials
-    let aws_cmd = get_aws_command()?;
-    let test_output = Command::new(aws_cmd)
-        .args(&["sts", "get-caller-identity", "--profile", &profile])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .await
-        .map_err(|e| format!("Failed to test AWS credentials: {}", e))?;
+    Ok(())
+}
+
+async fn test_aws_cli_profile(profile: &str) -> Result<(), String> {
+    let aws_cmd = get_aws_command().await?;
+    let test_output = run_with_timeout(
+        aws_command(&aws_cmd)
+            .args(["sts", "get-caller-identity", "--profile", profile])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped()),
+        QUICK_TIMEOUT,
+    )
+    .await
+    .map_err(|e| format!("Failed to test AWS credentials: {}", e))?;
 
     if !test_output.status.success() {
         let stderr_str = String::from_utf8_lossy(&test_output.stderr);
         return Err(format!("AWS credentials test failed: {}", stderr_str));
     }
 
-    let success_message = format!("AWS credentials configured and validated successfully! Profile: {}, Region: {}", profile, region);
-    Ok(success_message)
+    Ok(())
+}
+
+/// Assume `role_arn` (optionally with an MFA device and code) using the credentials in
+/// `source_profile`, and store the temporary credentials in the AWS CLI profile `target_profile`
+/// (default "<source_profile>-assumed"). With `profile_id`, that app profile's credentials
+/// and rclone remote switch to the temporary ones too, until they expire.
+#[command]
+pub async fn assume_aws_role(
+    role_arn: String,
+    mfa_serial: Option<String>,
+    mfa_code: Option<String>,
+    source_profile: Option<String>,
+    target_profile: Option<String>,
+    duration_seconds: Option<u32>,
+    profile_id: Option<String>,
+) -> Result<TemporaryCredentials, String> {
+    let source_profile = source_profile.unwrap_or_else(|| "default".to_string());
+    let target_profile = target_profile.unwrap_or_else(|| format!("{}-assumed", source_profile));
+    if !role_arn.starts_with("arn:") {
+        return Err(format!("Invalid role ARN: {}", role_arn));
+    }
+
+    let mut args = vec![
+        "sts".to_string(),
+        "assume-role".to_string(),
+        "--role-arn".to_string(),
+        role_arn.clone(),
+        "--role-session-name".to_string(),
+        format!("cloud-backup-app-{}", chrono::Utc::now().timestamp()),
+        "--profile".to_string(),
+        source_profile.clone(),
+        "--output".to_string(),
+        "json".to_string(),
+    ];
+    if let Some(duration) = duration_seconds {
+        args.push("--duration-seconds".to_string());
+        args.push(duration.to_string());
+    }
+    match (mfa_serial.filter(|s| !s.is_empty()), mfa_code.filter(|c| !c.is_empty())) {
+        (Some(serial), Some(code)) => {
+            args.push("--serial-number".to_string());
+            args.push(serial);
+            args.push("--token-code".to_string());
+            args.push(code);
+        }
+        (None, None) => {}
+        _ => return Err("MFA requires both the device serial/ARN and a code".to_string()),
+    }
+
+    let aws_cmd = get_aws_command().await?;
+    let output = run_with_timeout(
+        aws_command(&aws_cmd)
+            .args(&args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped()),
+        QUICK_TIMEOUT,
+    )
+    .await
+    .map_err(|e| format!("Failed to assume role: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Assume role failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let response: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse assume-role response: {}", e))?;
+    let credentials = &response["Credentials"];
+    let field = |name: &str| -> Result<String, String> {
+        credentials[name].as_str()
+            .map(|v| v.to_string())
+            .ok_or_else(|| format!("assume-role response is missing Credentials.{}", name))
+    };
+
+    let temporary = TemporaryCredentials {
+        access_key_id: field("AccessKeyId")?,
+        secret_access_key: field("SecretAccessKey")?,
+        session_token: field("SessionToken")?,
+        expiration: field("Expiration")?,
+        profile_name: target_profile.clone(),
+    };
+
+    // Carry the region over so the assumed profile works on its own
+    let region = aws_cli_profile_region(&source_profile).await;
+    let mut values = vec![
+        ("aws_access_key_id", temporary.access_key_id.as_str()),
+        ("aws_secret_access_key", temporary.secret_access_key.as_str()),
+        ("aws_session_token", temporary.session_token.as_str()),
+        ("output", "json"),
+    ];
+    if let Some(region) = &region {
+        values.push(("region", region.as_str()));
+    }
+    set_aws_cli_profile(&target_profile, &values).await?;
+
+    if let Some(profile_id) = profile_id {
+        use_temporary_credentials(&profile_id, &temporary).await?;
+    }
+
+    debug!("Assumed role {} into profile {}, expires {}", role_arn, target_profile, temporary.expiration);
+    Ok(temporary)
+}
+
+/// Store temporary credentials on an app profile and rewrite its rclone remote to use them
+async fn use_temporary_credentials(profile_id: &str, temporary: &TemporaryCredentials) -> Result<(), String> {
+    let mut config = crate::config::load_config().await?;
+    let aws_config = config.profiles.iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?
+        .aws_config
+        .as_mut()
+        .ok_or("This profile has no AWS configuration to put the temporary credentials in")?;
+    aws_config.aws_access_key_id = temporary.access_key_id.clone();
+    aws_config.aws_secret_access_key = temporary.secret_access_key.clone();
+    aws_config.session_token = Some(temporary.session_token.clone());
+    config.updated_at = chrono::Utc::now();
+    crate::config::save_config(&config).await?;
+
+    crate::config::repair_rclone_config(profile_id.to_string())
+        .await
+        .map_err(|e| format!("Stored the temporary credentials, but updating rclone.conf failed: {}", e))?;
+    Ok(())
+}
+
+async fn aws_cli_profile_region(profile: &str) -> Option<String> {
+    let aws_cmd = get_aws_command().await.ok()?;
+    let output = run_with_timeout(
+        aws_command(&aws_cmd)
+            .args(["configure", "get", "region", "--profile", profile])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped()),
+        QUICK_TIMEOUT,
+    )
+    .await
+    .ok()?;
+
+    let region = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() && !region.is_empty() { Some(region) } else { None }
 }
 
 #[command]
@@ -88,14 +267,16 @@ pub async fn validate_aws_permissions(profile_name: Option<String>) -> Result<St
     let profile = profile_name.unwrap_or_else(|| "default".to_string());
     
     // Get caller identity to check if credentials work
-    let aws_cmd = get_aws_command()?;
-    let output = Command::new(aws_cmd)
-        .args(&["sts", "get-caller-identity", "--profile", &profile])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .await
-        .map_err(|e| format!("Failed to validate AWS permissions: {}", e))?;
+    let aws_cmd = get_aws_command().await?;
+    let output = run_with_timeout(
+        aws_command(&aws_cmd)
+            .args(["sts", "get-caller-identity", "--profile", &profile])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped()),
+        QUICK_TIMEOUT,
+    )
+    .await
+    .map_err(|e| format!("Failed to validate AWS permissions: {}", e))?;
 
     if !output.status.success() {
         return Err(format!("AWS permission validation failed: {}", String::from_utf8_lossy(&output.stderr)));
@@ -112,410 +293,1187 @@ pub async fn setup_aws_infrastructure(
     admin_username: String,
     lifecycle_config: LifecycleConfig,
     employees: Vec<String>,
-    profileName: Option<String>
-) -> Result<AwsConfig, String> {
+    profileName: Option<String>,
+    dry_run: Option<bool>,
+    replace_existing_keys: Option<bool>
+) -> Result<AwsSetupResult, String> {
     let profile = profileName.unwrap_or_else(|| "default".to_string());
-    // Create the setup script content based on the backup-test script
-    let script_content = generate_setup_script(
-        &bucket_name,
-        &region,
-        &admin_username,
-        &lifecycle_config,
-        &employees,
-        &profile
-    );
+    validate_region(&region)?;
+    validate_bucket_name(&bucket_name)?;
+    validate_setup_usernames(&admin_username, &employees)?;
+    validate_lifecycle_config(&lifecycle_config)?;
+    build_lifecycle_configuration(&lifecycle_config)?;
 
-    // Write the script to a temporary file
-    let script_path = "/tmp/setup-bucket.sh";
-    tokio::fs::write(script_path, script_content)
-        .await
-        .map_err(|e| format!("Failed to write setup script: {}", e))?;
+    if dry_run.unwrap_or(false) {
+        return Ok(AwsSetupResult::Planned(plan_aws_infrastructure(
+            &bucket_name, &region, &admin_username, &lifecycle_config, &employees,
+        )));
+    }
 
-    // Make the script executable
-    Command::new("chmod")
-        .args(&["+x", script_path])
-        .output()
-        .await
-        .map_err(|e| format!("Failed to make script executable: {}", e))?;
-
-    // Execute the setup script
-    let output = Command::new("bash")
-        .arg(script_path)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .await
-        .map_err(|e| format!("Failed to execute setup script: {}", e))?;
+    info!("Setting up shared bucket: {} with profile: {}", bucket_name, profile);
 
-    if !output.status.success() {
-        return Err(format!("Setup script failed: {}", String::from_utf8_lossy(&output.stderr)));
+    // 1. Create bucket if it doesn't exist, then talk to it in the region it actually lives in
+    let sdk_config = load_sdk_config(&profile, &region).await;
+
+    // Secrets of existing keys can't be read back, so refuse to pile up new keys unless
+    // the caller agreed to replace them. Checked before touching anything.
+    // The key setup is running with is never counted or deleted, or setup would lock
+    // itself out halfway through.
+    let replace_existing_keys = replace_existing_keys.unwrap_or(false);
+    let caller_key = caller_access_key_id(&sdk_config).await;
+    let iam = aws_sdk_iam::Client::new(&sdk_config);
+    let mut existing_keys = Vec::new();
+    for username in std::iter::once(&admin_username).chain(employees.iter()) {
+        let mut key_ids = list_access_key_ids(&iam, username).await?;
+        key_ids.retain(|id| Some(id) != caller_key.as_ref());
+        if !key_ids.is_empty() {
+            existing_keys.push(format!("{} ({})", username, key_ids.join(", ")));
+        }
+    }
+    if !existing_keys.is_empty() && !replace_existing_keys {
+        return Err(format!(
+            "These IAM users already have access keys: {}. AWS can't return the secret of an existing key, so setup would have to create another one. Re-run with replace_existing_keys to delete and recreate their keys, or remove them from the setup.",
+            existing_keys.join("; ")
+        ));
     }
 
-    // Parse the output to get credentials
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    parse_setup_output(&output_str, bucket_name, region, admin_username, lifecycle_config, employees)
+    let bucket_region = ensure_bucket(&aws_sdk_s3::Client::new(&sdk_config), &bucket_name, &region).await?;
+    let sdk_config = if bucket_region != region {
+        load_sdk_config(&profile, &bucket_region).await
+    } else {
+        sdk_config
+    };
+    let s3 = aws_sdk_s3::Client::new(&sdk_config);
+    let iam = aws_sdk_iam::Client::new(&sdk_config);
+
+    // 2-6. Harden the bucket
+    configure_bucket(&s3, &bucket_name, &lifecycle_config).await?;
+
+    // 7. Create IAM users and get credentials
+    ensure_iam_user(&iam, &admin_username).await?;
+    put_user_policy(&iam, &admin_username, "BackupAdminPolicy", &admin_policy(&bucket_name)).await?;
+    info!("Creating access key for {}...", admin_username);
+    let (admin_key, admin_secret) = recreate_access_key(&iam, &admin_username, caller_key.as_deref()).await?;
+
+    let mut employee_records = Vec::new();
+    for employee in &employees {
+        info!("Setting up user: {}", employee);
+        ensure_iam_user(&iam, employee).await?;
+        put_user_policy(&iam, employee, "BackupEmployeePolicy", &employee_policy(&bucket_name, employee)).await?;
+        let (key, secret) = recreate_access_key(&iam, employee, caller_key.as_deref()).await?;
+
+        employee_records.push(Employee {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: employee.clone(),
+            username: employee.clone(),
+            access_key_id: key,
+            secret_access_key: secret,
+            rclone_config_generated: false,
+            created_at: chrono::Utc::now(),
+            prefix: None,
+        });
+    }
+
+    info!("Setup completed successfully!");
+
+    Ok(AwsSetupResult::Applied(AwsConfig {
+        aws_access_key_id: admin_key,
+        aws_secret_access_key: admin_secret,
+        aws_region: bucket_region,
+        aws_sso_configured: false, // Using traditional credentials, not SSO
+        bucket_name,
+        lifecycle_config,
+        employees: employee_records,
+        provider: default_s3_provider(),
+        endpoint: None,
+        session_token: None,
+        needs_verification: false,
+    }))
 }
 
-fn generate_setup_script(
+/// Describe the changes setup_aws_infrastructure makes, without calling AWS
+fn plan_aws_infrastructure(
     bucket_name: &str,
     region: &str,
     admin_username: &str,
     lifecycle_config: &LifecycleConfig,
     employees: &[String],
-    profile: &str
-) -> String {
-    let employees_str = employees.join(" ");
-    
-    format!(r#"#!/bin/bash
-set -euo pipefail
-
-# Configuration
-BUCKET="{bucket_name}"
-REGION="{region}"
-ADMIN_USER="{admin_username}"
-EMPLOYEES="{employees_str}"
-PROFILE="{profile}"
-
-ENABLE_LIFECYCLE="{lifecycle_enabled}"
-DAYS_TO_IA="{days_to_ia}"
-DAYS_TO_GLACIER="{days_to_glacier}"
-
-# Create output directory
-mkdir -p /tmp/aws-output/creds
-
-echo "Setting up shared bucket: $BUCKET with profile: $PROFILE"
-
-# 1. Create bucket if it doesn't exist
-aws s3api head-bucket --bucket "$BUCKET" --region "$REGION" --profile "$PROFILE" 2>/dev/null || {{
-    aws s3 mb s3://"$BUCKET" --region "$REGION" --profile "$PROFILE"
-}}
-
-# 2. Enable Versioning
-echo "Enabling versioning..."
-aws s3api put-bucket-versioning \
-    --bucket "$BUCKET" \
-    --versioning-configuration Status=Enabled \
-    --profile "$PROFILE"
-
-# 3. Enable default encryption (SSE-S3)
-echo "Enabling SSE-S3 encryption..."
-aws s3api put-bucket-encryption \
-    --bucket "$BUCKET" \
-    --server-side-encryption-configuration '{{
-        "Rules": [
-            {{
-                "ApplyServerSideEncryptionByDefault": {{
-                    "SSEAlgorithm": "AES256"
-                }},
-                "BucketKeyEnabled": true
-            }}
-        ]
-    }}' \
-    --profile "$PROFILE"
-
-# 4. Block public access
-echo "Blocking public access..."
-aws s3api put-public-access-block \
-    --bucket "$BUCKET" \
-    --public-access-block-configuration \
-    BlockPublicAcls=true,IgnorePublicAcls=true,BlockPublicPolicy=true,RestrictPublicBuckets=true \
-    --profile "$PROFILE"
-
-# 5. Apply bucket policy to deny non-TLS
-echo "Applying TLS-only bucket policy..."
-cat > /tmp/bucket-policy.json << 'EOF'
-{{
-    "Version": "2012-10-17",
-    "Statement": [
-        {{
-            "Sid": "DenyInsecureConnections",
-            "Effect": "Deny",
-            "Principal": "*",
-            "Action": "s3:*",
-            "Resource": [
-                "arn:aws:s3:::{bucket_name}",
-                "arn:aws:s3:::{bucket_name}/*"
-            ],
-            "Condition": {{
-                "Bool": {{
-                    "aws:SecureTransport": "false"
-                }}
-            }}
-        }}
-    ]
-}}
-EOF
-
-aws s3api put-bucket-policy \
-    --bucket "$BUCKET" \
-    --policy file:///tmp/bucket-policy.json \
-    --profile "$PROFILE"
-
-rm /tmp/bucket-policy.json
-
-# 6. Optional Lifecycle (optimization without deletion)
-if [ "$ENABLE_LIFECYCLE" = "true" ]; then
-    echo "Setting up lifecycle policy..."
-    
-    # Check if Glacier transition should be included (999999 means never)
-    if [ "$DAYS_TO_GLACIER" -eq 999999 ]; then
-        # Only Standard-IA transition, no Glacier
-        cat > /tmp/lifecycle.json << EOF
-{{
-    "Rules": [
-        {{
-            "ID": "OptimizeStorage",
-            "Status": "Enabled",
-            "Filter": {{}},
-            "Transitions": [
-                {{
-                    "Days": $DAYS_TO_IA,
-                    "StorageClass": "STANDARD_IA"
-                }}
-            ]
-        }}
-    ]
-}}
-EOF
-    else
-        # Include both Standard-IA and Glacier transitions
-        cat > /tmp/lifecycle.json << EOF
-{{
-    "Rules": [
-        {{
-            "ID": "OptimizeStorage",
-            "Status": "Enabled",
-            "Filter": {{}},
-            "Transitions": [
-                {{
-                    "Days": $DAYS_TO_IA,
-                    "StorageClass": "STANDARD_IA"
-                }},
-                {{
-                    "Days": $DAYS_TO_GLACIER,
-                    "StorageClass": "GLACIER"
-                }}
-            ]
-        }}
-    ]
-}}
-EOF
-    fi
-
-    aws s3api put-bucket-lifecycle-configuration \
-        --bucket "$BUCKET" \
-        --lifecycle-configuration file:///tmp/lifecycle.json \
-        --profile "$PROFILE"
-
-    rm /tmp/lifecycle.json
-fi
-
-# 7. Create IAM users and get credentials
-echo "=== CREDENTIALS START ==="
-
-# Create admin user if not exists
-if ! aws iam get-user --user-name "$ADMIN_USER" --profile "$PROFILE" >/dev/null 2>&1; then
-    echo "Creating admin user: $ADMIN_USER"
-    aws iam create-user --user-name "$ADMIN_USER" --profile "$PROFILE"
-fi
-
-# Create admin policy
-cat > /tmp/admin-policy.json << 'EOF'
-{{
-    "Version": "2012-10-17",
-    "Statement": [
-        {{
-            "Effect": "Allow",
-            "Action": [
-                "s3:ListBucket",
-                "s3:ListBucketVersions",
-                "s3:GetBucketLocation"
-            ],
-            "Resource": "arn:aws:s3:::{bucket_name}"
-        }},
-        {{
-            "Effect": "Allow",
-            "Action": [
-                "s3:GetObject",
-                "s3:GetObjectVersion",
-                "s3:PutObject",
-                "s3:PutObjectAcl",
-                "s3:DeleteObject",
-                "s3:DeleteObjectVersion",
-                "s3:AbortMultipartUpload",
-                "s3:ListMultipartUploadParts"
-            ],
-            "Resource": "arn:aws:s3:::{bucket_name}/*"
-        }}
-    ]
-}}
-EOF
-
-# Attach admin policy
-aws iam put-user-policy \
-    --user-name "$ADMIN_USER" \
-    --policy-name "BackupAdminPolicy" \
-    --policy-document file:///tmp/admin-policy.json \
-    --profile "$PROFILE"
-
-# Create access key for admin
-echo "Creating access key for $ADMIN_USER..."
-ADMIN_CREDS=$(aws iam create-access-key --user-name "$ADMIN_USER" --output json --profile "$PROFILE")
-ADMIN_KEY=$(echo "$ADMIN_CREDS" | jq -r '.AccessKey.AccessKeyId')
-ADMIN_SECRET=$(echo "$ADMIN_CREDS" | jq -r '.AccessKey.SecretAccessKey')
-
-echo "ADMIN_CREDENTIALS:$ADMIN_KEY:$ADMIN_SECRET"
-
-# Create employee users
-for employee in $EMPLOYEES; do
-    echo "Setting up user: $employee"
-    
-    # Create employee user if not exists
-    if ! aws iam get-user --user-name "$employee" --profile "$PROFILE" >/dev/null 2>&1; then
-        aws iam create-user --user-name "$employee" --profile "$PROFILE"
-    fi
-
-    # Employee-specific policy
-    cat > /tmp/employee-policy.json << EOF
-{{
-    "Version": "2012-10-17",
-    "Statement": [
-        {{
-            "Effect": "Allow",
-            "Action": [
-                "s3:ListBucket"
-            ],
-            "Resource": "arn:aws:s3:::{bucket_name}",
-            "Condition": {{
-                "StringLike": {{
-                    "s3:prefix": [
-                        "$employee/*",
-                        "$employee"
-                    ]
-                }}
-            }}
-        }},
-        {{
-            "Effect": "Allow",
-            "Action": [
-                "s3:GetObject",
-                "s3:GetObjectVersion",
-                "s3:PutObject",
-                "s3:PutObjectAcl",
-                "s3:DeleteObject",
-                "s3:DeleteObjectVersion",
-                "s3:AbortMultipartUpload",
-                "s3:ListMultipartUploadParts"
-            ],
-            "Resource": [
-                "arn:aws:s3:::{bucket_name}/$employee/*",
-                "arn:aws:s3:::{bucket_name}/$employee"
-            ]
-        }}
-    ]
-}}
-EOF
-
-    # Attach employee policy
-    aws iam put-user-policy \
-        --user-name "$employee" \
-        --policy-name "BackupEmployeePolicy" \
-        --policy-document file:///tmp/employee-policy.json \
-        --profile "$PROFILE"
-
-    # Create access key for employee
-    EMPLOYEE_CREDS=$(aws iam create-access-key --user-name "$employee" --output json --profile "$PROFILE")
-    EMPLOYEE_KEY=$(echo "$EMPLOYEE_CREDS" | jq -r '.AccessKey.AccessKeyId')
-    EMPLOYEE_SECRET=$(echo "$EMPLOYEE_CREDS" | jq -r '.AccessKey.SecretAccessKey')
-
-    echo "EMPLOYEE_CREDENTIALS:$employee:$EMPLOYEE_KEY:$EMPLOYEE_SECRET"
-done
-
-echo "=== CREDENTIALS END ==="
-
-# Cleanup
-rm -f /tmp/admin-policy.json /tmp/employee-policy.json
-
-echo "Setup completed successfully!"
-"#,
-        bucket_name = bucket_name,
-        region = region,
-        admin_username = admin_username,
-        employees_str = employees_str,
-        profile = profile,
-        lifecycle_enabled = lifecycle_config.enabled,
-        days_to_ia = lifecycle_config.days_to_ia,
-        days_to_glacier = lifecycle_config.days_to_glacier
-    )
+) -> AwsSetupPlan {
+    let mut bucket_settings = vec![
+        format!("Create bucket {} in {} if it does not exist", bucket_name, region),
+        "Enable versioning".to_string(),
+        "Enable SSE-S3 (AES256) default encryption with bucket keys".to_string(),
+        "Block all public access".to_string(),
+        "Apply a bucket policy denying non-TLS requests".to_string(),
+    ];
+    if lifecycle_config.enabled {
+        let glacier = if lifecycle_config.days_to_glacier == 999999 {
+            "never move to Glacier".to_string()
+        } else {
+            format!("move to Glacier after {} days", lifecycle_config.days_to_glacier)
+        };
+        let deep_archive = if lifecycle_config.days_to_deep_archive == 999999 {
+            "never move to Deep Archive".to_string()
+        } else {
+            format!("move to Deep Archive after {} days", lifecycle_config.days_to_deep_archive)
+        };
+        bucket_settings.push(format!(
+            "Lifecycle: move to Standard-IA after {} days, {}, {}",
+            lifecycle_config.days_to_ia, glacier, deep_archive
+        ));
+    }
+
+    let mut users = vec![PlannedIamUser {
+        username: admin_username.to_string(),
+        is_admin: true,
+        policy_name: "BackupAdminPolicy".to_string(),
+        policy_document: admin_policy(bucket_name),
+    }];
+    users.extend(employees.iter().map(|employee| PlannedIamUser {
+        username: employee.clone(),
+        is_admin: false,
+        policy_name: "BackupEmployeePolicy".to_string(),
+        policy_document: employee_policy(bucket_name, employee),
+    }));
+
+    let warnings = vec![
+        format!(
+            "A new access key is created for each of the {} users. If any of them already has keys, setup stops unless you choose to replace them, which deletes the old keys.",
+            users.len()
+        ),
+        "If the bucket already exists in another region, it is configured where it is instead of in the region above.".to_string(),
+    ];
+
+    AwsSetupPlan {
+        bucket_name: bucket_name.to_string(),
+        region: region.to_string(),
+        bucket_settings,
+        bucket_policy: tls_only_policy(bucket_name),
+        users,
+        warnings,
+    }
 }
 
-fn parse_setup_output(
-    output: &str,
-    bucket_name: String,
-    region: String,
-    _admin_username: String,
+/// S3 naming rules: 3-63 lowercase letters, digits, dots and hyphens, alphanumeric at both ends
+fn validate_bucket_name(bucket_name: &str) -> Result<(), String> {
+    let valid_chars = bucket_name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '.' || c == '-');
+    let alnum_ends = bucket_name.chars().next().is_some_and(|c| c.is_ascii_alphanumeric())
+        && bucket_name.chars().last().is_some_and(|c| c.is_ascii_alphanumeric());
+
+    if !(3..=63).contains(&bucket_name.len()) || !valid_chars || !alnum_ends || bucket_name.contains("..") {
+        return Err(format!(
+            "Invalid bucket name '{}'. Use 3-63 lowercase letters, numbers, dots or hyphens, starting and ending with a letter or number.",
+            bucket_name
+        ));
+    }
+    Ok(())
+}
+
+/// IAM user names: 1-64 characters from letters, digits and `+=,.@_-`
+fn validate_iam_username(username: &str) -> Result<(), String> {
+    let valid = !username.is_empty()
+        && username.len() <= 64
+        && username.chars().all(|c| c.is_ascii_alphanumeric() || "+=,.@_-".contains(c));
+    if !valid {
+        return Err(format!(
+            "Invalid IAM user name '{}'. Use up to 64 letters, numbers or +=,.@_- characters.",
+            username
+        ));
+    }
+    Ok(())
+}
+
+/// Every setup user name must be a valid IAM name and unique within the setup
+fn validate_setup_usernames(admin_username: &str, employees: &[String]) -> Result<(), String> {
+    let mut seen = std::collections::HashSet::new();
+
+    for username in std::iter::once(admin_username).chain(employees.iter().map(|e| e.as_str())) {
+        validate_iam_username(username)?;
+        // IAM user names are case-insensitive
+        if !seen.insert(username.to_lowercase()) {
+            return Err(format!("User name '{}' is listed more than once", username));
+        }
+    }
+    Ok(())
+}
+
+/// Regions where S3 buckets can be created
+const AWS_REGIONS: &[&str] = &[
+    "us-east-1", "us-east-2", "us-west-1", "us-west-2",
+    "ca-central-1", "ca-west-1",
+    "sa-east-1", "mx-central-1",
+    "eu-west-1", "eu-west-2", "eu-west-3", "eu-central-1", "eu-central-2",
+    "eu-north-1", "eu-south-1", "eu-south-2",
+    "ap-east-1", "ap-south-1", "ap-south-2",
+    "ap-southeast-1", "ap-southeast-2", "ap-southeast-3", "ap-southeast-4", "ap-southeast-5", "ap-southeast-7",
+    "ap-northeast-1", "ap-northeast-2", "ap-northeast-3",
+    "me-south-1", "me-central-1", "il-central-1", "af-south-1",
+];
+
+fn validate_region(region: &str) -> Result<(), String> {
+    if AWS_REGIONS.contains(&region) {
+        Ok(())
+    } else {
+        Err(format!("Unsupported AWS region '{}'. Expected one of: {}", region, AWS_REGIONS.join(", ")))
+    }
+}
+
+/// Normalize a bucket LocationConstraint: us-east-1 reports none, and legacy eu-west-1 reports "EU"
+fn normalize_bucket_region(location: &str) -> String {
+    match location.trim() {
+        "" => "us-east-1".to_string(),
+        "EU" => "eu-west-1".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Load SDK config from the named AWS CLI profile (~/.aws/credentials), pinned to a region
+/// and going through the configured proxy
+async fn load_sdk_config(profile: &str, region: &str) -> aws_config::SdkConfig {
+    aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .profile_name(profile)
+        .region(aws_config::Region::new(region.to_string()))
+        .http_client(crate::network::sdk_http_client())
+        .load()
+        .await
+}
+
+/// Create the bucket if needed and return the region it lives in
+async fn ensure_bucket(s3: &aws_sdk_s3::Client, bucket_name: &str, region: &str) -> Result<String, String> {
+    if s3.head_bucket().bucket(bucket_name).send().await.is_err() {
+        let mut request = s3.create_bucket().bucket(bucket_name);
+
+        // us-east-1 rejects an explicit LocationConstraint; every other region requires one
+        if region != "us-east-1" {
+            request = request.create_bucket_configuration(
+                CreateBucketConfiguration::builder()
+                    .location_constraint(BucketLocationConstraint::from(region))
+                    .build(),
+            );
+        }
+
+        if let Err(e) = request.send().await {
+            match e.as_service_error() {
+                Some(service_error) if service_error.is_bucket_already_owned_by_you() => {
+                    info!("Bucket {} already owned by this account, continuing", bucket_name);
+                }
+                Some(service_error) if service_error.is_bucket_already_exists() => {
+                    return Err(format!(
+                        "Bucket name '{}' is already taken by another AWS account. S3 bucket names are globally unique - please choose a different name.",
+                        bucket_name
+                    ));
+                }
+                _ => return Err(format!("Failed to create bucket: {}", DisplayErrorContext(&e))),
+            }
+        }
+    }
+
+    // Report the bucket's actual region (an existing bucket may live elsewhere)
+    let location = s3.get_bucket_location()
+        .bucket(bucket_name)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get bucket location: {}", DisplayErrorContext(&e)))?;
+
+    Ok(normalize_bucket_region(
+        location.location_constraint().map(|c| c.as_str()).unwrap_or(""),
+    ))
+}
+
+async fn configure_bucket(s3: &aws_sdk_s3::Client, bucket_name: &str, lifecycle_config: &LifecycleConfig) -> Result<(), String> {
+    info!("Enabling versioning...");
+    s3.put_bucket_versioning()
+        .bucket(bucket_name)
+        .versioning_configuration(
+            VersioningConfiguration::builder()
+                .status(BucketVersioningStatus::Enabled)
+                .build(),
+        )
+        .send()
+        .await
+        .map_err(|e| format!("Failed to enable versioning: {}", DisplayErrorContext(&e)))?;
+
+    info!("Enabling SSE-S3 encryption...");
+    let encryption_rule = ServerSideEncryptionRule::builder()
+        .apply_server_side_encryption_by_default(
+            ServerSideEncryptionByDefault::builder()
+                .sse_algorithm(ServerSideEncryption::Aes256)
+                .build()
+                .map_err(|e| e.to_string())?,
+        )
+        .bucket_key_enabled(true)
+        .build();
+    s3.put_bucket_encryption()
+        .bucket(bucket_name)
+        .server_side_encryption_configuration(
+            ServerSideEncryptionConfiguration::builder()
+                .rules(encryption_rule)
+                .build()
+                .map_err(|e| e.to_string())?,
+        )
+        .send()
+        .await
+        .map_err(|e| format!("Failed to enable encryption: {}", DisplayErrorContext(&e)))?;
+
+    info!("Blocking public access...");
+    s3.put_public_access_block()
+        .bucket(bucket_name)
+        .public_access_block_configuration(
+            PublicAccessBlockConfiguration::builder()
+                .block_public_acls(true)
+                .ignore_public_acls(true)
+                .block_public_policy(true)
+                .restrict_public_buckets(true)
+                .build(),
+        )
+        .send()
+        .await
+        .map_err(|e| format!("Failed to block public access: {}", DisplayErrorContext(&e)))?;
+
+    info!("Applying TLS-only bucket policy...");
+    s3.put_bucket_policy()
+        .bucket(bucket_name)
+        .policy(tls_only_policy(bucket_name))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to apply bucket policy: {}", DisplayErrorContext(&e)))?;
+
+    // Optional Lifecycle (optimization without deletion)
+    if lifecycle_config.enabled {
+        info!("Setting up lifecycle policy...");
+        s3.put_bucket_lifecycle_configuration()
+            .bucket(bucket_name)
+            .lifecycle_configuration(build_lifecycle_configuration(lifecycle_config)?)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to set lifecycle policy: {}", DisplayErrorContext(&e)))?;
+    }
+
+    Ok(())
+}
+
+/// Read back the bucket settings setup_aws_infrastructure applies and report any drift.
+/// Uses the named AWS CLI profile, since the stored admin keys can't read bucket settings.
+#[command]
+pub async fn get_bucket_info(profile_id: String, aws_profile: Option<String>) -> Result<BucketInfo, String> {
+    use crate::config::load_config;
+
+    let aws_profile = aws_profile.unwrap_or_else(|| "default".to_string());
+    let config = load_config().await?;
+    let profile = config.profiles.iter()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    let aws_config = profile.aws_config.as_ref()
+        .ok_or("Profile does not have AWS configuration")?;
+    let bucket_name = aws_config.bucket_name.clone();
+
+    let sdk_config = load_sdk_config(&aws_profile, &aws_config.aws_region).await;
+    let s3 = aws_sdk_s3::Client::new(&sdk_config);
+    let mut drift = Vec::new();
+
+    let versioning = s3.get_bucket_versioning()
+        .bucket(&bucket_name)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to read versioning: {}", DisplayErrorContext(&e)))?
+        .status()
+        .map(|status| status.as_str().to_string());
+    if versioning.as_deref() != Some("Enabled") {
+        drift.push(format!("Versioning is {} (expected Enabled)", versioning.as_deref().unwrap_or("off")));
+    }
+
+    let (encryption, bucket_key_enabled) = match s3.get_bucket_encryption().bucket(&bucket_name).send().await {
+        Ok(output) => {
+            let rule = output.server_side_encryption_configuration()
+                .and_then(|config| config.rules().first());
+            (
+                rule.and_then(|r| r.apply_server_side_encryption_by_default())
+                    .map(|default| default.sse_algorithm().as_str().to_string()),
+                rule.and_then(|r| r.bucket_key_enabled()).unwrap_or(false),
+            )
+        }
+        Err(e) if s3_error_code(&e) == Some("ServerSideEncryptionConfigurationNotFoundError") => (None, false),
+        Err(e) => return Err(format!("Failed to read encryption: {}", DisplayErrorContext(&e))),
+    };
+    match encryption.as_deref() {
+        None => drift.push("Default encryption is not configured (expected AES256)".to_string()),
+        // KMS is at least as strong as the SSE-S3 setup applies, so it isn't drift
+        Some("AES256") | Some("aws:kms") | Some("aws:kms:dsse") => {}
+        Some(other) => drift.push(format!("Default encryption is {} (expected AES256)", other)),
+    }
+    if encryption.is_some() && !bucket_key_enabled {
+        drift.push("S3 bucket key is disabled".to_string());
+    }
+
+    let public_access_blocked = match s3.get_public_access_block().bucket(&bucket_name).send().await {
+        Ok(output) => output.public_access_block_configuration().is_some_and(|block| {
+            block.block_public_acls() == Some(true)
+                && block.ignore_public_acls() == Some(true)
+                && block.block_public_policy() == Some(true)
+                && block.restrict_public_buckets() == Some(true)
+        }),
+        Err(e) if s3_error_code(&e) == Some("NoSuchPublicAccessBlockConfiguration") => false,
+        Err(e) => return Err(format!("Failed to read public access block: {}", DisplayErrorContext(&e))),
+    };
+    if !public_access_blocked {
+        drift.push("Public access is not fully blocked".to_string());
+    }
+
+    let tls_only_policy = match s3.get_bucket_policy().bucket(&bucket_name).send().await {
+        Ok(output) => output.policy().is_some_and(|policy| policy.contains("aws:SecureTransport")),
+        Err(e) if s3_error_code(&e) == Some("NoSuchBucketPolicy") => false,
+        Err(e) => return Err(format!("Failed to read bucket policy: {}", DisplayErrorContext(&e))),
+    };
+    if !tls_only_policy {
+        drift.push("Bucket policy does not deny non-TLS requests".to_string());
+    }
+
+    let lifecycle_rules: Vec<String> = match s3.get_bucket_lifecycle_configuration().bucket(&bucket_name).send().await {
+        Ok(output) => output.rules().iter().map(|rule| {
+            let transitions: Vec<String> = rule.transitions().iter()
+                .map(|t| format!(
+                    "{} after {} days",
+                    t.storage_class().map(|c| c.as_str()).unwrap_or("?"),
+                    t.days().unwrap_or(0)
+                ))
+                .collect();
+            format!(
+                "{} ({}): {}",
+                rule.id().unwrap_or("unnamed"),
+                rule.status().as_str(),
+                if transitions.is_empty() { "no transitions".to_string() } else { transitions.join(", ") }
+            )
+        }).collect(),
+        Err(e) if s3_error_code(&e) == Some("NoSuchLifecycleConfiguration") => Vec::new(),
+        Err(e) => return Err(format!("Failed to read lifecycle rules: {}", DisplayErrorContext(&e))),
+    };
+    if aws_config.lifecycle_config.enabled
+        && !lifecycle_rules.iter().any(|rule| rule.starts_with("OptimizeStorage (Enabled)"))
+    {
+        drift.push("Lifecycle rule OptimizeStorage is missing or disabled".to_string());
+    }
+
+    Ok(BucketInfo {
+        bucket_name,
+        region: aws_config.aws_region.clone(),
+        versioning,
+        encryption,
+        bucket_key_enabled,
+        public_access_blocked,
+        tls_only_policy,
+        lifecycle_rules,
+        drift,
+    })
+}
+
+/// AWS error code of a failed S3 call, e.g. "NoSuchBucketPolicy"
+fn s3_error_code<E: ProvideErrorMetadata, R>(error: &aws_sdk_s3::error::SdkError<E, R>) -> Option<&str> {
+    error.as_service_error().and_then(|e| e.code())
+}
+
+/// Apply new lifecycle transitions to an existing bucket, or remove the lifecycle
+/// configuration when disabled, then store the new settings on the profile
+#[command]
+pub async fn update_lifecycle_policy(
+    profile_id: String,
     lifecycle_config: LifecycleConfig,
-    _employee_names: Vec<String>
+    aws_profile: Option<String>
 ) -> Result<AwsConfig, String> {
-    let mut admin_key = String::new();
-    let mut admin_secret = String::new();
-    let mut employees = Vec::new();
+    use crate::config::{load_config, save_config};
 
-    let lines: Vec<&str> = output.lines().collect();
-    let mut in_credentials = false;
+    validate_lifecycle_config(&lifecycle_config)?;
+    let aws_profile = aws_profile.unwrap_or_else(|| "default".to_string());
+    let mut config = load_config().await?;
 
-    for line in lines {
-        if line == "=== CREDENTIALS START ===" {
-            in_credentials = true;
-            continue;
+    let profile = config.profiles.iter()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    let aws_config = profile.aws_config.as_ref()
+        .ok_or("Profile does not have AWS configuration")?;
+    let bucket_name = aws_config.bucket_name.clone();
+
+    let sdk_config = load_sdk_config(&aws_profile, &aws_config.aws_region).await;
+    let s3 = aws_sdk_s3::Client::new(&sdk_config);
+
+    if lifecycle_config.enabled {
+        s3.put_bucket_lifecycle_configuration()
+            .bucket(&bucket_name)
+            .lifecycle_configuration(build_lifecycle_configuration(&lifecycle_config)?)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to set lifecycle policy: {}", DisplayErrorContext(&e)))?;
+    } else {
+        s3.delete_bucket_lifecycle()
+            .bucket(&bucket_name)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to remove lifecycle policy: {}", DisplayErrorContext(&e)))?;
+    }
+
+    let profile = config.profiles.iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    let aws_config = profile.aws_config.as_mut()
+        .ok_or("Profile does not have AWS configuration")?;
+    aws_config.lifecycle_config = lifecycle_config;
+    let updated = aws_config.clone();
+    profile.updated_at = chrono::Utc::now();
+
+    config.updated_at = chrono::Utc::now();
+    save_config(&config).await?;
+
+    info!("Updated lifecycle policy for bucket {}", bucket_name);
+    Ok(updated)
+}
+
+/// Transition days S3 will accept: Standard-IA no sooner than 30 days, Glacier (unless
+/// 999999, meaning never) after Standard-IA, and Deep Archive no sooner than Glacier
+fn validate_lifecycle_config(lifecycle_config: &LifecycleConfig) -> Result<(), String> {
+    if !lifecycle_config.enabled {
+        return Ok(());
+    }
+    if lifecycle_config.days_to_ia < 30 {
+        return Err(format!(
+            "Objects can move to Standard-IA after 30 days at the earliest (got {})",
+            lifecycle_config.days_to_ia
+        ));
+    }
+    if lifecycle_config.days_to_glacier != 999999 && lifecycle_config.days_to_ia >= lifecycle_config.days_to_glacier {
+        return Err(format!(
+            "Days to Glacier ({}) must be greater than days to Standard-IA ({})",
+            lifecycle_config.days_to_glacier, lifecycle_config.days_to_ia
+        ));
+    }
+    if lifecycle_config.days_to_deep_archive != 999999 {
+        if lifecycle_config.days_to_ia >= lifecycle_config.days_to_deep_archive {
+            return Err(format!(
+                "Days to Deep Archive ({}) must be greater than days to Standard-IA ({})",
+                lifecycle_config.days_to_deep_archive, lifecycle_config.days_to_ia
+            ));
         }
-        if line == "=== CREDENTIALS END ===" {
-            break;
+        if lifecycle_config.days_to_glacier != 999999 && lifecycle_config.days_to_deep_archive < lifecycle_config.days_to_glacier {
+            return Err(format!(
+                "Days to Deep Archive ({}) can't be less than days to Glacier ({})",
+                lifecycle_config.days_to_deep_archive, lifecycle_config.days_to_glacier
+            ));
         }
-        
-        if in_credentials {
-            if line.starts_with("ADMIN_CREDENTIALS:") {
-                let parts: Vec<&str> = line.split(':').collect();
-                if parts.len() >= 3 {
-                    admin_key = parts[1].to_string();
-                    admin_secret = parts[2].to_string();
+    }
+    Ok(())
+}
+
+fn build_lifecycle_configuration(lifecycle_config: &LifecycleConfig) -> Result<BucketLifecycleConfiguration, String> {
+    let mut rule = LifecycleRule::builder()
+        .id("OptimizeStorage")
+        .status(ExpirationStatus::Enabled)
+        .filter(LifecycleRuleFilter::builder().prefix("").build())
+        .transitions(
+            Transition::builder()
+                .days(lifecycle_config.days_to_ia as i32)
+                .storage_class(TransitionStorageClass::StandardIa)
+                .build(),
+        );
+
+    // 999999 means never transition to Glacier. When Deep Archive is due the same day,
+    // objects go straight there instead.
+    if lifecycle_config.days_to_glacier != 999999 && lifecycle_config.days_to_glacier != lifecycle_config.days_to_deep_archive {
+        rule = rule.transitions(
+            Transition::builder()
+                .days(lifecycle_config.days_to_glacier as i32)
+                .storage_class(TransitionStorageClass::Glacier)
+                .build(),
+        );
+    }
+
+    if lifecycle_config.days_to_deep_archive != 999999 {
+        rule = rule.transitions(
+            Transition::builder()
+                .days(lifecycle_config.days_to_deep_archive as i32)
+                .storage_class(TransitionStorageClass::DeepArchive)
+                .build(),
+        );
+    }
+
+    BucketLifecycleConfiguration::builder()
+        .rules(rule.build().map_err(|e| e.to_string())?)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+fn tls_only_policy(bucket_name: &str) -> String {
+    json!({
+        "Version": "2012-10-17",
+        "Statement": [
+            {
+                "Sid": "DenyInsecureConnections",
+                "Effect": "Deny",
+                "Principal": "*",
+                "Action": "s3:*",
+                "Resource": [
+                    format!("arn:aws:s3:::{}", bucket_name),
+                    format!("arn:aws:s3:::{}/*", bucket_name)
+                ],
+                "Condition": {
+                    "Bool": {
+                        "aws:SecureTransport": "false"
+                    }
                 }
-            } else if line.starts_with("EMPLOYEE_CREDENTIALS:") {
-                let parts: Vec<&str> = line.split(':').collect();
-                if parts.len() >= 4 {
-                    let name = parts[1].to_string();
-                    let key = parts[2].to_string();
-                    let secret = parts[3].to_string();
-                    
-                    employees.push(Employee {
-                        id: uuid::Uuid::new_v4().to_string(),
-                        name: name.clone(),
-                        username: name,
-                        access_key_id: key,
-                        secret_access_key: secret,
-                        rclone_config_generated: false,
-                        created_at: chrono::Utc::now(),
-                    });
+            }
+        ]
+    })
+    .to_string()
+}
+
+fn admin_policy(bucket_name: &str) -> String {
+    json!({
+        "Version": "2012-10-17",
+        "Statement": [
+            {
+                "Effect": "Allow",
+                "Action": [
+                    "s3:ListBucket",
+                    "s3:ListBucketVersions",
+                    "s3:GetBucketLocation"
+                ],
+                "Resource": format!("arn:aws:s3:::{}", bucket_name)
+            },
+            {
+                "Effect": "Allow",
+                "Action": [
+                    "s3:GetObject",
+                    "s3:GetObjectVersion",
+                    "s3:PutObject",
+                    "s3:PutObjectAcl",
+                    "s3:DeleteObject",
+                    "s3:DeleteObjectVersion",
+                    "s3:AbortMultipartUpload",
+                    "s3:ListMultipartUploadParts"
+                ],
+                "Resource": format!("arn:aws:s3:::{}/*", bucket_name)
+            }
+        ]
+    })
+    .to_string()
+}
+
+/// Access to `prefix`/ only; the prefix is the employee's username unless it was renamed
+fn employee_policy(bucket_name: &str, prefix: &str) -> String {
+    json!({
+        "Version": "2012-10-17",
+        "Statement": [
+            {
+                "Effect": "Allow",
+                "Action": [
+                    "s3:ListBucket"
+                ],
+                "Resource": format!("arn:aws:s3:::{}", bucket_name),
+                "Condition": {
+                    "StringLike": {
+                        "s3:prefix": [
+                            format!("{}/*", prefix),
+                            prefix
+                        ]
+                    }
                 }
+            },
+            {
+                "Effect": "Allow",
+                "Action": [
+                    "s3:GetObject",
+                    "s3:GetObjectVersion",
+                    "s3:PutObject",
+                    "s3:PutObjectAcl",
+                    "s3:DeleteObject",
+                    "s3:DeleteObjectVersion",
+                    "s3:AbortMultipartUpload",
+                    "s3:ListMultipartUploadParts"
+                ],
+                "Resource": [
+                    format!("arn:aws:s3:::{}/{}/*", bucket_name, prefix),
+                    format!("arn:aws:s3:::{}/{}", bucket_name, prefix)
+                ]
             }
+        ]
+    })
+    .to_string()
+}
+
+/// Create the IAM user unless it already exists
+async fn ensure_iam_user(iam: &aws_sdk_iam::Client, username: &str) -> Result<(), String> {
+    match iam.get_user().user_name(username).send().await {
+        Ok(_) => Ok(()),
+        Err(e) if e.as_service_error().is_some_and(|se| se.is_no_such_entity_exception()) => {
+            info!("Creating IAM user: {}", username);
+            iam.create_user()
+                .user_name(username)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to create IAM user {}: {}", username, DisplayErrorContext(&e)))?;
+            Ok(())
         }
+        Err(e) => Err(format!("Failed to look up IAM user {}: {}", username, DisplayErrorContext(&e))),
     }
+}
+
+/// Rewrite an employee's IAM policy so it allows `prefix` instead of their old prefix
+pub(crate) async fn update_employee_policy_prefix(
+    aws_profile: &str,
+    region: &str,
+    bucket_name: &str,
+    username: &str,
+    prefix: &str,
+) -> Result<(), String> {
+    let sdk_config = load_sdk_config(aws_profile, region).await;
+    let iam = aws_sdk_iam::Client::new(&sdk_config);
+    put_user_policy(&iam, username, "BackupEmployeePolicy", &employee_policy(bucket_name, prefix)).await
+}
+
+async fn put_user_policy(iam: &aws_sdk_iam::Client, username: &str, policy_name: &str, policy_document: &str) -> Result<(), String> {
+    iam.put_user_policy()
+        .user_name(username)
+        .policy_name(policy_name)
+        .policy_document(policy_document)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to attach {} to {}: {}", policy_name, username, DisplayErrorContext(&e)))?;
+    Ok(())
+}
+
+/// Create a new access key, returning (access_key_id, secret_access_key)
+async fn create_access_key(iam: &aws_sdk_iam::Client, username: &str) -> Result<(String, String), String> {
+    let output = iam.create_access_key()
+        .user_name(username)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to create access key for {}: {}", username, DisplayErrorContext(&e)))?;
+
+    let access_key = output.access_key()
+        .ok_or_else(|| format!("No access key returned for {}", username))?;
 
-    if admin_key.is_empty() || admin_secret.is_empty() {
-        return Err("Failed to parse admin credentials from setup output".to_string());
+    Ok((access_key.access_key_id().to_string(), access_key.secret_access_key().to_string()))
+}
+
+/// Access key ids for a user; empty if the user doesn't exist yet
+async fn list_access_key_ids(iam: &aws_sdk_iam::Client, username: &str) -> Result<Vec<String>, String> {
+    match iam.list_access_keys().user_name(username).send().await {
+        Ok(output) => Ok(output.access_key_metadata()
+            .iter()
+            .filter_map(|key| key.access_key_id().map(|id| id.to_string()))
+            .collect()),
+        Err(e) if e.as_service_error().is_some_and(|se| se.is_no_such_entity_exception()) => Ok(Vec::new()),
+        Err(e) => Err(format!("Failed to list access keys for {}: {}", username, DisplayErrorContext(&e))),
     }
+}
 
-    Ok(AwsConfig {
-        aws_access_key_id: admin_key,
-        aws_secret_access_key: admin_secret,
-        aws_region: region,
-        aws_sso_configured: false, // Using traditional credentials, not SSO
-        bucket_name,
-        lifecycle_config,
-        employees,
+/// Access key id of the credentials `sdk_config` signs with; None if they can't be loaded
+async fn caller_access_key_id(sdk_config: &aws_config::SdkConfig) -> Option<String> {
+    use aws_sdk_s3::config::ProvideCredentials;
+
+    let credentials = sdk_config.credentials_provider()?.provide_credentials().await.ok()?;
+    Some(credentials.access_key_id().to_string())
+}
+
+/// Delete the user's existing access keys, except `keep` (the caller's own), then create
+/// a fresh one
+async fn recreate_access_key(iam: &aws_sdk_iam::Client, username: &str, keep: Option<&str>) -> Result<(String, String), String> {
+    for access_key_id in list_access_key_ids(iam, username).await? {
+        if Some(access_key_id.as_str()) == keep {
+            info!("Keeping access key {} for {}, setup is signed with it", access_key_id, username);
+            continue;
+        }
+        info!("Deleting existing access key {} for {}", access_key_id, username);
+        iam.delete_access_key()
+            .user_name(username)
+            .access_key_id(&access_key_id)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to delete access key {} for {}: {}", access_key_id, username, DisplayErrorContext(&e)))?;
+    }
+    create_access_key(iam, username).await
+}
+
+/// Employees of an admin profile. Access keys are blanked unless include_secrets is set.
+#[command]
+pub async fn list_employees(profile_id: String, include_secrets: Option<bool>) -> Result<Vec<Employee>, String> {
+    use crate::config::load_config;
+
+    let config = load_config().await?;
+    let profile = config.profiles.into_iter()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+
+    let mut employees = profile.aws_config.map(|aws| aws.employees).unwrap_or_default();
+    if !include_secrets.unwrap_or(false) {
+        for employee in employees.iter_mut() {
+            employee.secret_access_key.clear();
+        }
+    }
+    Ok(employees)
+}
+
+/// Onboard one employee against the existing bucket: IAM user, prefix-scoped policy and
+/// an access key. IAM calls use the named AWS CLI profile, like remove_employee.
+#[command]
+pub async fn add_employee(
+    profile_id: String,
+    username: String,
+    name: Option<String>,
+    aws_profile: Option<String>
+) -> Result<Employee, String> {
+    use crate::config::{load_config, save_config};
+
+    let aws_profile = aws_profile.unwrap_or_else(|| "default".to_string());
+    let username = username.trim().to_string();
+    validate_iam_username(&username)?;
+
+    let mut config = load_config().await?;
+    let profile = config.profiles.iter()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    let aws_config = profile.aws_config.as_ref()
+        .ok_or("Profile does not have AWS configuration")?;
+    if aws_config.employees.iter().any(|e| e.username.eq_ignore_ascii_case(&username)) {
+        return Err(format!("Employee '{}' already exists", username));
+    }
+    let bucket_name = aws_config.bucket_name.clone();
+
+    let sdk_config = load_sdk_config(&aws_profile, &aws_config.aws_region).await;
+    let iam = aws_sdk_iam::Client::new(&sdk_config);
+
+    // An existing IAM user with keys belongs to someone else's setup; don't add a key to it
+    let existing_keys = list_access_key_ids(&iam, &username).await?;
+    if !existing_keys.is_empty() {
+        return Err(format!(
+            "IAM user '{}' already exists with access keys ({}). Choose another user name or remove that user first.",
+            username,
+            existing_keys.join(", ")
+        ));
+    }
+
+    ensure_iam_user(&iam, &username).await?;
+    put_user_policy(&iam, &username, "BackupEmployeePolicy", &employee_policy(&bucket_name, &username)).await?;
+    let (key, secret) = create_access_key(&iam, &username).await?;
+
+    let employee = Employee {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.filter(|n| !n.trim().is_empty()).unwrap_or_else(|| username.clone()),
+        username: username.clone(),
+        access_key_id: key,
+        secret_access_key: secret,
+        rclone_config_generated: false,
+        created_at: chrono::Utc::now(),
+        prefix: None,
+    };
+
+    let profile = config.profiles.iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    if let Some(aws_config) = profile.aws_config.as_mut() {
+        aws_config.employees.push(employee.clone());
+    }
+    profile.updated_at = chrono::Utc::now();
+
+    config.updated_at = chrono::Utc::now();
+    save_config(&config).await?;
+
+    info!("Added employee: {}", username);
+    Ok(employee)
+}
+
+/// Offboard an employee: delete their IAM access keys, inline policy and user, optionally
+/// purge their `{username}/` prefix, and drop them from the profile's employee list.
+/// IAM calls use the named AWS CLI profile since the stored admin keys only have S3 access.
+#[command]
+pub async fn remove_employee(
+    profile_id: String,
+    employee_id: String,
+    purge_data: Option<bool>,
+    aws_profile: Option<String>
+) -> Result<(), String> {
+    use crate::config::{load_config, save_config};
+
+    let aws_profile = aws_profile.unwrap_or_else(|| "default".to_string());
+    let mut config = load_config().await?;
+
+    let profile = config.profiles.iter()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    let aws_config = profile.aws_config.as_ref()
+        .ok_or("Profile does not have AWS configuration")?;
+    let employee = aws_config.employees.iter()
+        .find(|e| e.id == employee_id)
+        .ok_or("Employee not found")?
+        .clone();
+
+    let sdk_config = load_sdk_config(&aws_profile, &aws_config.aws_region).await;
+    let iam = aws_sdk_iam::Client::new(&sdk_config);
+
+    delete_iam_user(&iam, &employee.username).await?;
+
+    if purge_data.unwrap_or(false) {
+        let s3 = aws_sdk_s3::Client::new(&sdk_config);
+        let prefix = format!("{}/", employee.backup_prefix());
+        let deleted = purge_prefix(&s3, &aws_config.bucket_name, &prefix).await?;
+        info!("Purged {} object versions under s3://{}/{}", deleted, aws_config.bucket_name, prefix);
+    }
+
+    let profile = config.profiles.iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    if let Some(aws_config) = profile.aws_config.as_mut() {
+        aws_config.employees.retain(|e| e.id != employee_id);
+    }
+    profile.updated_at = chrono::Utc::now();
+
+    config.updated_at = chrono::Utc::now();
+    save_config(&config).await?;
+
+    info!("Removed employee: {}", employee.username);
+    Ok(())
+}
+
+/// Replace an employee's access key. The new key must pass sts get-caller-identity before
+/// the old one is deleted; if it doesn't, the new key is removed and the old one kept.
+/// Returns the rclone.conf for the new key, and rewrites the onboarding bundle at
+/// `bundle_path` if given.
+#[command]
+pub async fn rotate_employee_key(
+    profile_id: String,
+    employee_id: String,
+    aws_profile: Option<String>,
+    bundle_path: Option<String>
+) -> Result<KeyRotation, String> {
+    use crate::config::{load_config, save_config};
+
+    let aws_profile = aws_profile.unwrap_or_else(|| "default".to_string());
+    let mut config = load_config().await?;
+
+    let profile = config.profiles.iter()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    let aws_config = profile.aws_config.as_ref()
+        .ok_or("Profile does not have AWS configuration")?;
+    let employee = aws_config.employees.iter()
+        .find(|e| e.id == employee_id)
+        .ok_or("Employee not found")?
+        .clone();
+    let region = aws_config.aws_region.clone();
+    let bucket = aws_config.bucket_name.clone();
+
+    let sdk_config = load_sdk_config(&aws_profile, &region).await;
+    let iam = aws_sdk_iam::Client::new(&sdk_config);
+
+    let old_access_key_id = employee.access_key_id.clone();
+    let (new_key, new_secret) = create_access_key(&iam, &employee.username).await?;
+
+    if let Err(e) = validate_access_key(&new_key, &new_secret, &region).await {
+        // Roll back: keep the old key, discard the new one
+        if let Err(delete_err) = iam.delete_access_key()
+            .user_name(&employee.username)
+            .access_key_id(&new_key)
+            .send()
+            .await
+        {
+            error!("Failed to roll back new access key {}: {}", new_key, DisplayErrorContext(&delete_err));
+        }
+        return Err(format!("New access key failed validation, keeping the existing key: {}", e));
+    }
+
+    // Persist the new key before deleting the old one so config never points at a dead key
+    let profile = config.profiles.iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    let employee = profile.aws_config.as_mut()
+        .and_then(|aws_config| aws_config.employees.iter_mut().find(|e| e.id == employee_id))
+        .ok_or("Employee not found")?;
+    employee.access_key_id = new_key;
+    employee.secret_access_key = new_secret;
+    employee.rclone_config_generated = false; // UI prompts for a fresh rclone config download
+    let mut updated_employee = employee.clone();
+    profile.updated_at = chrono::Utc::now();
+
+    config.updated_at = chrono::Utc::now();
+    save_config(&config).await?;
+
+    // The new key is saved and working from here on, so later failures are only warnings
+    let mut warnings = Vec::new();
+    if let Err(e) = iam.delete_access_key()
+        .user_name(&updated_employee.username)
+        .access_key_id(&old_access_key_id)
+        .send()
+        .await
+    {
+        error!("Failed to delete old access key {}: {}", old_access_key_id, DisplayErrorContext(&e));
+        warnings.push(format!(
+            "Deleting the old access key {} failed, remove it in the IAM console: {}",
+            old_access_key_id,
+            DisplayErrorContext(&e)
+        ));
+    }
+
+    let rclone_config = generate_employee_rclone_config(updated_employee.clone(), bucket, region).await?;
+    let bundle_path = match bundle_path {
+        Some(path) => match create_employee_bundle(profile_id, employee_id, path).await {
+            Ok(path) => {
+                updated_employee.rclone_config_generated = true;
+                Some(path)
+            }
+            Err(e) => {
+                warnings.push(format!("Writing the onboarding bundle failed: {}", e));
+                None
+            }
+        },
+        None => None,
+    };
+
+    info!("Rotated access key for employee: {}", updated_employee.username);
+    Ok(KeyRotation {
+        employee: updated_employee,
+        rclone_config,
+        bundle_path,
+        warnings,
     })
 }
 
+/// Check a key pair with sts get-caller-identity. New IAM keys take a few seconds to
+/// propagate, so retry briefly before giving up.
+async fn validate_access_key(access_key_id: &str, secret_access_key: &str, region: &str) -> Result<(), String> {
+    let max_attempts = 5;
+    let mut last_error = String::new();
+
+    for attempt in 0..max_attempts {
+        if attempt > 0 {
+            tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+        }
+
+        let aws_cmd = get_aws_command().await?;
+        let output = run_with_timeout(
+            aws_command(&aws_cmd)
+                .args(["sts", "get-caller-identity"])
+                .env("AWS_ACCESS_KEY_ID", access_key_id)
+                .env("AWS_SECRET_ACCESS_KEY", secret_access_key)
+                .env("AWS_DEFAULT_REGION", region)
+                .env_remove("AWS_SESSION_TOKEN")
+                .env_remove("AWS_PROFILE")
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()),
+            QUICK_TIMEOUT,
+        )
+        .await
+        .map_err(|e| format!("Failed to execute AWS CLI: {}", e))?;
+
+        if output.status.success() {
+            return Ok(());
+        }
+        last_error = String::from_utf8_lossy(&output.stderr).to_string();
+    }
+
+    Err(last_error)
+}
+
+/// Delete an IAM user and everything attached to it. IAM refuses to delete a user that
+/// still has access keys or inline policies, so those go first. A user that was already
+/// removed in the console is treated as success.
+async fn delete_iam_user(iam: &aws_sdk_iam::Client, username: &str) -> Result<(), String> {
+    let keys = match iam.list_access_keys().user_name(username).send().await {
+        Ok(output) => output.access_key_metadata().to_vec(),
+        Err(e) if e.as_service_error().is_some_and(|se| se.is_no_such_entity_exception()) => {
+            info!("IAM user {} no longer exists, skipping IAM cleanup", username);
+            return Ok(());
+        }
+        Err(e) => return Err(format!("Failed to list access keys for {}: {}", username, DisplayErrorContext(&e))),
+    };
+
+    for key in keys {
+        if let Some(access_key_id) = key.access_key_id() {
+            match iam.delete_access_key().user_name(username).access_key_id(access_key_id).send().await {
+                Ok(_) => {}
+                Err(e) if e.as_service_error().is_some_and(|se| se.is_no_such_entity_exception()) => {}
+                Err(e) => return Err(format!("Failed to delete access key {}: {}", access_key_id, DisplayErrorContext(&e))),
+            }
+        }
+    }
+
+    match iam.delete_user_policy().user_name(username).policy_name("BackupEmployeePolicy").send().await {
+        Ok(_) => {}
+        Err(e) if e.as_service_error().is_some_and(|se| se.is_no_such_entity_exception()) => {}
+        Err(e) => return Err(format!("Failed to delete policy for {}: {}", username, DisplayErrorContext(&e))),
+    }
+
+    match iam.delete_user().user_name(username).send().await {
+        Ok(_) => Ok(()),
+        Err(e) if e.as_service_error().is_some_and(|se| se.is_no_such_entity_exception()) => Ok(()),
+        Err(e) => Err(format!("Failed to delete IAM user {}: {}", username, DisplayErrorContext(&e))),
+    }
+}
+
+/// Permanently delete every object version and delete marker under `prefix`.
+/// Returns the number of versions removed.
+async fn purge_prefix(s3: &aws_sdk_s3::Client, bucket_name: &str, prefix: &str) -> Result<u64, String> {
+    let mut deleted = 0u64;
+    let mut key_marker: Option<String> = None;
+    let mut version_id_marker: Option<String> = None;
+
+    loop {
+        let page = s3.list_object_versions()
+            .bucket(bucket_name)
+            .prefix(prefix)
+            .set_key_marker(key_marker.clone())
+            .set_version_id_marker(version_id_marker.clone())
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list object versions: {}", DisplayErrorContext(&e)))?;
+
+        let mut identifiers = Vec::new();
+        for version in page.versions() {
+            if let Some(key) = version.key() {
+                identifiers.push((key.to_string(), version.version_id().map(|v| v.to_string())));
+            }
+        }
+        for marker in page.delete_markers() {
+            if let Some(key) = marker.key() {
+                identifiers.push((key.to_string(), marker.version_id().map(|v| v.to_string())));
+            }
+        }
+
+        // DeleteObjects accepts at most 1000 keys per request
+        for chunk in identifiers.chunks(1000) {
+            let objects = chunk.iter()
+                .map(|(key, version_id)| {
+                    ObjectIdentifier::builder()
+                        .key(key)
+                        .set_version_id(version_id.clone())
+                        .build()
+                        .map_err(|e| e.to_string())
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+
+            s3.delete_objects()
+                .bucket(bucket_name)
+                .delete(
+                    Delete::builder()
+                        .set_objects(Some(objects))
+                        .quiet(true)
+                        .build()
+                        .map_err(|e| e.to_string())?,
+                )
+                .send()
+                .await
+                .map_err(|e| format!("Failed to delete objects: {}", DisplayErrorContext(&e)))?;
+
+            deleted += chunk.len() as u64;
+        }
+
+        if page.is_truncated() == Some(true) {
+            key_marker = page.next_key_marker().map(|s| s.to_string());
+            version_id_marker = page.next_version_id_marker().map(|s| s.to_string());
+        } else {
+            break;
+        }
+    }
+
+    Ok(deleted)
+}
+
 #[command]
 pub async fn generate_employee_rclone_config(
     employee: Employee,
@@ -540,6 +1498,99 @@ acl = private
     Ok(config)
 }
 
+/// S3 client that signs with an employee's own access key instead of an AWS CLI profile
+fn employee_s3_client(employee: &Employee, region: &str) -> aws_sdk_s3::Client {
+    let credentials = aws_sdk_s3::config::Credentials::new(
+        &employee.access_key_id,
+        &employee.secret_access_key,
+        None,
+        None,
+        "employee",
+    );
+    let config = aws_sdk_s3::Config::builder()
+        .behavior_version(aws_sdk_s3::config::BehaviorVersion::latest())
+        .region(aws_sdk_s3::config::Region::new(region.to_string()))
+        .credentials_provider(credentials)
+        .http_client(crate::network::sdk_http_client())
+        .build();
+    aws_sdk_s3::Client::from_conf(config)
+}
+
+/// Try to list `prefix` (the bucket root when empty) and compare the outcome with what
+/// the employee policy should allow
+async fn isolation_check(s3: &aws_sdk_s3::Client, bucket_name: &str, target: String, prefix: &str, expect_denied: bool) -> IsolationCheck {
+    let result = s3.list_objects_v2()
+        .bucket(bucket_name)
+        .set_prefix((!prefix.is_empty()).then(|| format!("{}/", prefix)))
+        .delimiter("/")
+        .max_keys(1)
+        .send()
+        .await;
+    let (denied, error) = match result {
+        Ok(_) => (false, None),
+        Err(e) if s3_error_code(&e) == Some("AccessDenied") => (true, None),
+        Err(e) => (false, Some(DisplayErrorContext(&e).to_string())),
+    };
+
+    IsolationCheck {
+        target,
+        expect_denied,
+        denied,
+        // Any other error means the attempt proved nothing either way
+        passed: error.is_none() && denied == expect_denied,
+        error,
+    }
+}
+
+/// Sign in as the employee and make sure their policy keeps them inside their own prefix:
+/// their prefix must be listable, while the bucket root, the admins/ folder and every
+/// other employee's prefix must answer AccessDenied
+#[command]
+pub async fn test_employee_isolation(profile_id: String, employee_id: String) -> Result<IsolationReport, String> {
+    use crate::config::load_config;
+
+    let config = load_config().await?;
+    let profile = config.profiles.iter()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    let aws_config = profile.aws_config.as_ref()
+        .ok_or("Profile does not have AWS configuration")?;
+    let employee = aws_config.employees.iter()
+        .find(|e| e.id == employee_id)
+        .ok_or("Employee not found")?;
+    if employee.access_key_id.is_empty() || employee.secret_access_key.is_empty() {
+        return Err(format!("No access key stored for {}", employee.username));
+    }
+
+    let s3 = employee_s3_client(employee, &aws_config.aws_region);
+    let bucket_name = &aws_config.bucket_name;
+    let own_prefix = employee.backup_prefix();
+
+    let mut checks = vec![
+        isolation_check(&s3, bucket_name, format!("{}/ (own prefix)", own_prefix), own_prefix, false).await,
+        isolation_check(&s3, bucket_name, "bucket root".to_string(), "", true).await,
+        isolation_check(&s3, bucket_name, "admins/".to_string(), "admins", true).await,
+    ];
+    for other in aws_config.employees.iter().filter(|e| e.id != employee.id) {
+        let prefix = other.backup_prefix();
+        checks.push(isolation_check(&s3, bucket_name, format!("{}/ ({})", prefix, other.name), prefix, true).await);
+    }
+
+    let isolated = checks.iter().all(|check| check.passed);
+    if isolated {
+        info!("Isolation confirmed for employee {}", employee.username);
+    } else {
+        error!("Isolation check failed for employee {}: {:?}", employee.username, checks.iter().filter(|c| !c.passed).collect::<Vec<_>>());
+    }
+
+    Ok(IsolationReport {
+        employee_id: employee.id.clone(),
+        username: employee.username.clone(),
+        checks,
+        isolated,
+    })
+}
+
 #[command]
 pub async fn get_employee_credentials(profile_id: String, employee_id: String) -> Result<Employee, String> {
     use crate::config::load_config;
@@ -555,4 +1606,104 @@ pub async fn get_employee_credentials(profile_id: String, employee_id: String) -
     }
     
     Err("Employee not found".to_string())
+}
+
+/// Write a zip with everything a new hire needs: their rclone.conf, a README and a
+/// profile they can import. Returns the path of the written zip.
+#[command]
+pub async fn create_employee_bundle(profile_id: String, employee_id: String, output_path: String) -> Result<String, String> {
+    use std::io::Write;
+    use chrono::Utc;
+    use crate::config::{load_config, save_config};
+
+    let mut config = load_config().await?;
+    let admin_profile = config.profiles.iter()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    let aws_config = admin_profile.aws_config.as_ref()
+        .ok_or("Profile has no AWS configuration")?;
+    let employee = aws_config.employees.iter()
+        .find(|e| e.id == employee_id)
+        .ok_or("Employee not found")?
+        .clone();
+    let bucket = aws_config.bucket_name.clone();
+    let region = aws_config.aws_region.clone();
+
+    if employee.access_key_id.is_empty() || employee.secret_access_key.is_empty() {
+        return Err(format!("No access key stored for {}; rotate their key first", employee.username));
+    }
+
+    let rclone_conf = generate_employee_rclone_config(employee.clone(), bucket.clone(), region.clone()).await?;
+
+    // Backups land under {username}/ (or the renamed prefix), the only prefix their IAM policy allows
+    let mut employee_profile = Profile::new(employee.name.clone(), ProfileType::User);
+    config.settings.apply_profile_defaults(&mut employee_profile);
+    employee_profile.bucket = bucket.clone();
+    employee_profile.prefix = employee.backup_prefix().to_string();
+    let profile_json = serde_json::to_string_pretty(&ProfileExport {
+        format_version: PROFILE_EXPORT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: Utc::now(),
+        secrets_included: false,
+        profile: employee_profile,
+        crypt_secrets: None,
+        rclone_config_password: None,
+    })
+    .map_err(|e| format!("Failed to serialize profile: {}", e))?;
+
+    let readme = format!(
+        "Cloud backup setup for {name}\n\
+         \n\
+         Bucket: {bucket}\n\
+         Region: {region}\n\
+         Your folder: {prefix}/\n\
+         \n\
+         This bundle contains:\n\
+         - rclone.conf: your personal storage credentials. Keep it private.\n\
+         - profile.json: backup settings to import into the app.\n\
+         \n\
+         To set up:\n\
+         1. Copy rclone.conf somewhere private, e.g. your home folder.\n\
+         2. In the app, import profile.json.\n\
+         3. In the imported profile's settings, set the rclone config path to the copied rclone.conf\n\
+            and choose the folders to back up.\n\
+         \n\
+         Your credentials can only read and write inside {prefix}/. Contact your administrator\n\
+         if you lose this bundle so your access key can be rotated.\n",
+        name = employee.name,
+        bucket = bucket,
+        region = region,
+        prefix = employee.backup_prefix(),
+    );
+
+    let file = std::fs::File::create(&output_path)
+        .map_err(|e| format!("Failed to create {}: {}", output_path, e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    for (name, contents, mode) in [
+        ("rclone.conf", &rclone_conf, 0o600),
+        ("profile.json", &profile_json, 0o644),
+        ("README.txt", &readme, 0o644),
+    ] {
+        zip.start_file(name, options.unix_permissions(mode))
+            .map_err(|e| format!("Failed to add {} to bundle: {}", name, e))?;
+        zip.write_all(contents.as_bytes())
+            .map_err(|e| format!("Failed to add {} to bundle: {}", name, e))?;
+    }
+    zip.finish().map_err(|e| format!("Failed to write bundle: {}", e))?;
+
+    if let Some(stored) = config.profiles.iter_mut()
+        .find(|p| p.id == profile_id)
+        .and_then(|p| p.aws_config.as_mut())
+        .and_then(|aws| aws.employees.iter_mut().find(|e| e.id == employee_id))
+    {
+        stored.rclone_config_generated = true;
+    }
+    config.updated_at = Utc::now();
+    save_config(&config).await?;
+
+    debug!("Wrote onboarding bundle for {} to {}", employee.username, output_path);
+    Ok(output_path)
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use std::path::PathBuf;
 use tauri::command;
+use tracing::debug;
 
 /// Get the path to the rclone binary (bundled with app, brew, or system)
 pub fn get_rclone_binary_path() -> Result<PathBuf, String> {
@@ -11,7 +12,7 @@ pub fn get_rclone_binary_path() -> Result<PathBuf, String> {
             // Try ../binaries/rclone-{arch}-{platform} pattern
             let arch = std::env::consts::ARCH;
             let os = std::env::consts::OS;
-            println!("[DEBUG] Detected OS: {}, ARCH: {}", os, arch);
+            debug!("Detected OS: {}, ARCH: {}", os, arch);
 
             #[cfg(target_os = "macos")]
             let dev_binary_name = format!("rclone-{}-apple-darwin", arch);
@@ -22,7 +23,7 @@ pub fn get_rclone_binary_path() -> Result<PathBuf, String> {
             #[cfg(target_os = "linux")]
             let dev_binary_name = format!("rclone-{}-unknown-linux-gnu", arch);
 
-            println!("[DEBUG] Looking for binary named: {}", dev_binary_name);
+            debug!("Looking for binary named: {}", dev_binary_name);
 
             // Look for binaries in project structure
             let possible_paths = vec![
@@ -33,26 +34,26 @@ pub fn get_rclone_binary_path() -> Result<PathBuf, String> {
             ];
 
             for path in possible_paths {
-                println!("[DEBUG] Checking path: {:?}", path);
+                debug!("Checking path: {:?}", path);
 
                 // First check if the path exists
                 if path.exists() {
-                    println!("[DEBUG] Path exists!");
+                    debug!("Path exists!");
 
                     // Try to canonicalize for a clean absolute path
                     match path.canonicalize() {
                         Ok(canonical) => {
-                            println!("[DEBUG] Canonicalized to: {:?}", canonical);
+                            debug!("Canonicalized to: {:?}", canonical);
                             return Ok(canonical);
                         }
                         Err(e) => {
                             // If canonicalize fails but file exists, use the path as-is
-                            println!("[DEBUG] Failed to canonicalize ({}), using path as-is: {:?}", e, path);
+                            debug!("Failed to canonicalize ({}), using path as-is: {:?}", e, path);
                             return Ok(path);
                         }
                     }
                 } else {
-                    println!("[DEBUG] Path does not exist: {:?}", path);
+                    debug!("Path does not exist: {:?}", path);
                 }
             }
         }
@@ -68,9 +69,9 @@ pub fn get_rclone_binary_path() -> Result<PathBuf, String> {
             #[cfg(not(target_os = "windows"))]
             let bundled_path = bin_dir.join("rclone");
 
-            println!("[DEBUG] Checking for bundled rclone at: {:?}", bundled_path);
+            debug!("Checking for bundled rclone at: {:?}", bundled_path);
             if bundled_path.exists() {
-                println!("[DEBUG] Found bundled rclone at: {:?}", bundled_path);
+                debug!("Found bundled rclone at: {:?}", bundled_path);
                 return Ok(bundled_path);
             }
         }
@@ -144,4 +145,71 @@ pub fn get_rclone_binary_path() -> Result<PathBuf, String> {
 #[command]
 pub async fn get_rclone_path() -> Result<String, String> {
     get_rclone_binary_path().map(|p| p.to_string_lossy().to_string())
+}
+
+/// Get the path to the AWS CLI: `preferred` (the aws_cli_path setting) when set, then the
+/// installer and brew locations, then PATH. Apps started from Finder don't get the shell's
+/// PATH, so the known locations are tried first.
+pub fn get_aws_binary_path(preferred: Option<&str>) -> Result<PathBuf, String> {
+    if let Some(preferred) = preferred {
+        let path = PathBuf::from(preferred);
+        if path.exists() {
+            return Ok(path);
+        }
+        return Err(format!("AWS CLI not found at {} (set in app settings)", preferred));
+    }
+
+    #[cfg(target_os = "macos")]
+    let known_paths = vec![
+        "/usr/local/bin/aws",          // Official pkg installer symlink, Intel brew
+        "/opt/homebrew/bin/aws",       // Apple Silicon brew
+        "/usr/local/aws-cli/aws",      // Official pkg installer
+    ];
+
+    #[cfg(target_os = "windows")]
+    let known_paths = vec![
+        r"C:\Program Files\Amazon\AWSCLIV2\aws.exe",
+    ];
+
+    #[cfg(target_os = "linux")]
+    let known_paths = vec![
+        "/usr/local/bin/aws",          // Official installer symlink
+        "/usr/bin/aws",
+    ];
+
+    for path in known_paths {
+        let path_buf = PathBuf::from(path);
+        if path_buf.exists() {
+            debug!("Found AWS CLI at: {:?}", path_buf);
+            return Ok(path_buf);
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    let lookup = "which";
+    #[cfg(target_os = "windows")]
+    let lookup = "where";
+
+    if let Ok(output) = std::process::Command::new(lookup).arg("aws").output() {
+        if output.status.success() {
+            let path_str = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .next()
+                .unwrap_or("")
+                .trim()
+                .to_string();
+            if !path_str.is_empty() {
+                return Ok(PathBuf::from(path_str));
+            }
+        }
+    }
+
+    Err("AWS CLI not found. Install it from aws.amazon.com/cli or Homebrew, or set its path in app settings.".to_string())
+}
+
+/// Command to get the AWS CLI path, honouring the aws_cli_path setting
+#[command]
+pub async fn get_aws_path() -> Result<String, String> {
+    let settings = crate::config::load_config().await?.settings;
+    get_aws_binary_path(settings.aws_cli_path.as_deref()).map(|p| p.to_string_lossy().to_string())
 }
\ No newline at end of file
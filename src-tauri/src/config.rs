@@ -1,13 +1,18 @@
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use chrono::Utc;
 use tauri::command;
 use std::process::Stdio;
 use tokio::process::Command;
 use fs2::FileExt;
+use tracing::{debug, info, warn, error};
 
 use crate::models::*;
+use crate::size::parse_byte_size;
+use crate::process::{run_with_timeout, QUICK_TIMEOUT};
 
 #[tauri::command]
 pub async fn initialize_config() -> Result<(), String> {
@@ -25,7 +30,46 @@ pub async fn initialize_config() -> Result<(), String> {
     Ok(())
 }
 
+/// Mark operations left Running by a previous session as Cancelled. Call once at startup,
+/// before anything new can start, since nothing from the last session is still running.
+pub async fn recover_interrupted_operations() -> Result<usize, String> {
+    let mut config = load_config().await?;
+    let mut recovered = 0;
+
+    for operation in config.backup_operations.iter_mut() {
+        if matches!(operation.status, OperationStatus::Running) {
+            operation.status = OperationStatus::Cancelled;
+            operation.error_message = Some("Interrupted: the app was closed or crashed before this operation finished".to_string());
+            recovered += 1;
+        }
+    }
+
+    if recovered > 0 {
+        config.updated_at = Utc::now();
+        save_config(&config).await?;
+        debug!("Marked {} interrupted operations as cancelled", recovered);
+    }
+
+    Ok(recovered)
+}
+
+/// Environment variable that moves the whole config directory, e.g. for portable
+/// installs, config on an external drive, or tests that must not touch the real config
+pub const CONFIG_DIR_ENV: &str = "LAVOZ_CONFIG_DIR";
+
+/// The LAVOZ_CONFIG_DIR override made absolute, if it is set to something
+fn config_dir_override() -> Option<PathBuf> {
+    let dir = std::env::var_os(CONFIG_DIR_ENV).filter(|dir| !dir.is_empty())?;
+    // Runner scripts embed these paths, so they must not depend on the working directory
+    Some(std::path::absolute(&dir).unwrap_or_else(|_| PathBuf::from(dir)))
+}
+
+/// Root of everything the app stores: LAVOZ_CONFIG_DIR when set, otherwise
+/// <system config dir>/cloud-backup-app
 pub fn get_config_dir() -> Result<PathBuf, String> {
+    if let Some(dir) = config_dir_override() {
+        return Ok(dir);
+    }
     dirs::config_dir()
         .map(|dir| dir.join("cloud-backup-app"))
         .ok_or_else(|| "Could not determine config directory".to_string())
@@ -35,6 +79,44 @@ pub fn get_config_file() -> Result<PathBuf, String> {
     Ok(get_config_dir()?.join("config.json"))
 }
 
+/// Directory holding per-operation log files (logs/{operation_id}.log)
+pub fn get_operation_logs_dir() -> Result<PathBuf, String> {
+    Ok(get_config_dir()?.join("logs"))
+}
+
+pub fn get_operation_log_file(operation_id: &str) -> Result<PathBuf, String> {
+    Ok(get_operation_logs_dir()?.join(format!("{}.log", operation_id)))
+}
+
+fn remove_operation_log_file(operation_id: &str) {
+    if let Ok(log_file) = get_operation_log_file(operation_id) {
+        if log_file.exists() {
+            if let Err(e) = fs::remove_file(&log_file) {
+                error!("Failed to remove operation log {:?}: {}", log_file, e);
+            }
+        }
+    }
+}
+
+/// Write a file only the current user can read, for anything holding credentials such as
+/// rclone.conf. An existing file is narrowed to 0600 too.
+pub fn write_private_file(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(contents.as_ref())
+}
+
 pub async fn load_config() -> Result<AppConfig, String> {
     let config_file = get_config_file()?;
 
@@ -44,36 +126,293 @@ pub async fn load_config() -> Result<AppConfig, String> {
 
     let content = fs::read_to_string(&config_file).map_err(|e| e.to_string())?;
 
-    // Try to parse the config
-    match serde_json::from_str(&content) {
+    let parsed = serde_json::from_str::<AppConfig>(&content);
+    let needs_migration = match &parsed {
+        Ok(config) => config.schema_version < CURRENT_SCHEMA_VERSION,
+        Err(e) => {
+            warn!("Config failed to deserialize ({}), attempting migration", e);
+            true
+        }
+    };
+
+    // Older schema or a deserialize error: upgrade step by step before treating it as corrupted
+    if needs_migration {
+        match parse_and_migrate_config(&content) {
+            Ok(config) => {
+                info!("Migrated config to schema version {}", config.schema_version);
+                if let Err(save_err) = save_config(&config).await {
+                    error!("Failed to save migrated config: {}", save_err);
+                }
+                return Ok(config);
+            }
+            Err(migrate_err) => warn!("Migration failed: {}", migrate_err),
+        }
+    }
+
+    match parsed {
         Ok(config) => Ok(config),
         Err(e) => {
-            eprintln!("[ERROR] Config file is corrupted: {}", e);
-            eprintln!("[ERROR] Attempting to recover...");
+            error!("Config file is corrupted: {}", e);
+            error!("Attempting to recover...");
 
             // Try to fix common corruption issues
             let fixed_content = content.trim_end_matches("\"}").to_string() + "\n}";
 
             match serde_json::from_str(&fixed_content) {
                 Ok(config) => {
-                    eprintln!("[RECOVERY] Successfully recovered config");
+                    info!("Successfully recovered config");
                     // Save the fixed version
                     let config_ref: AppConfig = config;
                     if let Err(save_err) = save_config(&config_ref).await {
-                        eprintln!("[ERROR] Failed to save recovered config: {}", save_err);
+                        error!("Failed to save recovered config: {}", save_err);
                     } else {
-                        eprintln!("[RECOVERY] Saved fixed config file");
+                        info!("Saved fixed config file");
                     }
                     Ok(config_ref)
                 }
                 Err(_) => {
-                    // Backup the corrupted file and return error
-                    let backup_path = config_file.with_extension("json.corrupted");
-                    let _ = fs::copy(&config_file, &backup_path);
-                    Err(format!("Config file is corrupted and cannot be recovered. Original saved to: {:?}. Error: {}", backup_path, e))
+                    // Keep a copy of the corrupted file for inspection
+                    let corrupted_path = config_file.with_extension("json.corrupted");
+                    let _ = fs::copy(&config_file, &corrupted_path);
+
+                    // Fall back to the last good version written by save_config
+                    let backup_file = config_file.with_extension("json.bak");
+                    let backup_config = fs::read_to_string(&backup_file)
+                        .ok()
+                        .and_then(|backup| parse_and_migrate_config(&backup).ok());
+
+                    if let Some(config) = backup_config {
+                        warn!("Restored config from backup: {:?}", backup_file);
+                        if let Err(save_err) = save_config(&config).await {
+                            error!("Failed to save restored config: {}", save_err);
+                        }
+                        return Ok(config);
+                    }
+
+                    Err(format!("Config file is corrupted and cannot be recovered. Original saved to: {:?}. Error: {}", corrupted_path, e))
+                }
+            }
+        }
+    }
+}
+
+/// Parse raw config JSON, applying any pending schema migrations first
+fn parse_and_migrate_config(content: &str) -> Result<AppConfig, String> {
+    let value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| format!("Config is not valid JSON: {}", e))?;
+    let migrated = migrate_config(value)?;
+    serde_json::from_value(migrated)
+        .map_err(|e| format!("Migrated config is still invalid: {}", e))
+}
+
+/// Upgrade a config document one schema version at a time until it reaches
+/// CURRENT_SCHEMA_VERSION. Each step only fills in what that version introduced.
+fn migrate_config(mut value: serde_json::Value) -> Result<serde_json::Value, String> {
+    let mut version = value.get("schema_version")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as u32;
+
+    if version > CURRENT_SCHEMA_VERSION {
+        // Written by a newer app; leave it alone and let unknown fields be ignored
+        return Ok(value);
+    }
+
+    let root = value.as_object_mut().ok_or("Config root is not a JSON object")?;
+
+    while version < CURRENT_SCHEMA_VERSION {
+        match version {
+            0 => migrate_v0_to_v1(root),
+            1 => migrate_v1_to_v2(root),
+            2 => migrate_v2_to_v3(root),
+            3 => migrate_v3_to_v4(root),
+            4 => migrate_v4_to_v5(root),
+            _ => return Err(format!("No migration defined from schema version {}", version)),
+        }
+        version += 1;
+        info!("Applied config migration to schema version {}", version);
+    }
+
+    root.insert("schema_version".to_string(), serde_json::json!(version));
+    Ok(value)
+}
+
+/// v0 -> v1: fill in profile fields added before the config was versioned
+fn migrate_v0_to_v1(root: &mut serde_json::Map<String, serde_json::Value>) {
+    use serde_json::json;
+
+    root.entry("backup_operations").or_insert_with(|| json!([]));
+    root.entry("active_profile_id").or_insert(serde_json::Value::Null);
+    root.entry("app_version").or_insert_with(|| json!(env!("CARGO_PKG_VERSION")));
+    let now = json!(Utc::now());
+    root.entry("created_at").or_insert_with(|| now.clone());
+    root.entry("updated_at").or_insert_with(|| now.clone());
+
+    if let Some(profiles) = root.get_mut("profiles").and_then(|p| p.as_array_mut()) {
+        for profile in profiles.iter_mut().filter_map(|p| p.as_object_mut()) {
+            profile.entry("user_id").or_insert(serde_json::Value::Null);
+            profile.entry("prefix").or_insert_with(|| json!(""));
+            profile.entry("sources").or_insert_with(|| json!([]));
+            profile.entry("mode").or_insert_with(|| json!("Copy"));
+            profile.entry("schedule").or_insert(serde_json::Value::Null);
+            profile.entry("rclone_flags").or_insert_with(|| json!([]));
+            profile.entry("include_patterns").or_insert_with(|| json!([]));
+            profile.entry("exclude_patterns").or_insert_with(|| json!([]));
+            profile.entry("max_parallel_sources").or_insert_with(|| json!(2));
+            profile.entry("transfer_timeout_secs").or_insert_with(|| json!(0));
+            profile.entry("aws_config").or_insert(serde_json::Value::Null);
+            profile.entry("created_at").or_insert_with(|| now.clone());
+            profile.entry("updated_at").or_insert_with(|| now.clone());
+        }
+    }
+}
+
+/// v1 -> v2: move --transfers/--checkers out of rclone_flags into typed fields
+fn migrate_v1_to_v2(root: &mut serde_json::Map<String, serde_json::Value>) {
+    use serde_json::json;
+
+    let Some(profiles) = root.get_mut("profiles").and_then(|p| p.as_array_mut()) else {
+        return;
+    };
+
+    for profile in profiles.iter_mut().filter_map(|p| p.as_object_mut()) {
+        let flags: Vec<String> = profile.get("rclone_flags")
+            .and_then(|f| serde_json::from_value(f.clone()).ok())
+            .unwrap_or_default();
+
+        let mut transfers = default_transfers();
+        let mut checkers = default_checkers();
+        let mut remaining = Vec::new();
+        let mut iter = flags.into_iter();
+        while let Some(flag) = iter.next() {
+            let (name, value) = match flag.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (flag.clone(), None),
+            };
+            if name != "--transfers" && name != "--checkers" {
+                remaining.push(flag);
+                continue;
+            }
+            let value = value.or_else(|| iter.next());
+            if let Some(parsed) = value.and_then(|v| v.trim().parse::<u32>().ok()) {
+                if name == "--transfers" { transfers = parsed } else { checkers = parsed }
+            }
+        }
+
+        profile.insert("rclone_flags".to_string(), json!(remaining));
+        profile.entry("transfers").or_insert_with(|| json!(transfers));
+        profile.entry("checkers").or_insert_with(|| json!(checkers));
+    }
+}
+
+/// v2 -> v3: derive hash_check from --checksum/--size-only in rclone_flags
+fn migrate_v2_to_v3(root: &mut serde_json::Map<String, serde_json::Value>) {
+    use serde_json::json;
+
+    let Some(profiles) = root.get_mut("profiles").and_then(|p| p.as_array_mut()) else {
+        return;
+    };
+
+    for profile in profiles.iter_mut().filter_map(|p| p.as_object_mut()) {
+        let flags: Vec<String> = profile.get("rclone_flags")
+            .and_then(|f| serde_json::from_value(f.clone()).ok())
+            .unwrap_or_default();
+
+        let hash_check = if flags.iter().any(|f| f == "--size-only") {
+            HashMode::SizeOnly
+        } else if flags.iter().any(|f| f == "--checksum") {
+            HashMode::Checksum
+        } else {
+            HashMode::SizeModTime
+        };
+
+        let remaining: Vec<String> = flags.into_iter()
+            .filter(|f| f != "--checksum" && f != "--size-only")
+            .collect();
+
+        profile.insert("rclone_flags".to_string(), json!(remaining));
+        profile.entry("hash_check").or_insert_with(|| json!(hash_check));
+    }
+}
+
+/// v3 -> v4: move --retries/--low-level-retries/--retries-sleep out of rclone_flags into
+/// typed fields. Values that don't parse fall back to rclone's defaults.
+fn migrate_v3_to_v4(root: &mut serde_json::Map<String, serde_json::Value>) {
+    use serde_json::json;
+
+    let Some(profiles) = root.get_mut("profiles").and_then(|p| p.as_array_mut()) else {
+        return;
+    };
+
+    for profile in profiles.iter_mut().filter_map(|p| p.as_object_mut()) {
+        let flags: Vec<String> = profile.get("rclone_flags")
+            .and_then(|f| serde_json::from_value(f.clone()).ok())
+            .unwrap_or_default();
+
+        let mut retries = default_retries();
+        let mut low_level_retries = default_low_level_retries();
+        let mut retries_sleep_secs = 0;
+        let mut remaining = Vec::new();
+        let mut iter = flags.into_iter();
+        while let Some(flag) = iter.next() {
+            let (name, value) = match flag.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (flag.clone(), None),
+            };
+            if !matches!(name.as_str(), "--retries" | "--low-level-retries" | "--retries-sleep") {
+                remaining.push(flag);
+                continue;
+            }
+            let Some(value) = value.or_else(|| iter.next()) else {
+                continue;
+            };
+            let value = value.trim();
+            match name.as_str() {
+                "--retries" => retries = value.parse().unwrap_or(retries),
+                "--low-level-retries" => low_level_retries = value.parse().unwrap_or(low_level_retries),
+                // A bare number is seconds to rclone, same as "10s"
+                _ => {
+                    let seconds = value.parse::<f64>().ok()
+                        .or_else(|| crate::models::parse_rclone_age(value).ok().flatten());
+                    retries_sleep_secs = seconds.map_or(0, |s| s.round() as u64);
                 }
             }
         }
+
+        profile.insert("rclone_flags".to_string(), json!(remaining));
+        profile.entry("retries").or_insert_with(|| json!(retries.max(1)));
+        profile.entry("low_level_retries").or_insert_with(|| json!(low_level_retries));
+        profile.entry("retries_sleep_secs").or_insert_with(|| json!(retries_sleep_secs));
+    }
+}
+
+/// v4 -> v5: derive symlink_mode from --links/--copy-links in rclone_flags
+fn migrate_v4_to_v5(root: &mut serde_json::Map<String, serde_json::Value>) {
+    use serde_json::json;
+
+    let Some(profiles) = root.get_mut("profiles").and_then(|p| p.as_array_mut()) else {
+        return;
+    };
+
+    for profile in profiles.iter_mut().filter_map(|p| p.as_object_mut()) {
+        let flags: Vec<String> = profile.get("rclone_flags")
+            .and_then(|f| serde_json::from_value(f.clone()).ok())
+            .unwrap_or_default();
+
+        // Following wins when both are set, since it's the one that backs up the link targets
+        let symlink_mode = if flags.iter().any(|f| f == "--copy-links" || f == "-L") {
+            SymlinkMode::Follow
+        } else if flags.iter().any(|f| f == "--links" || f == "-l") {
+            SymlinkMode::Copy
+        } else {
+            SymlinkMode::Skip
+        };
+
+        let remaining: Vec<String> = flags.into_iter()
+            .filter(|f| !matches!(f.as_str(), "--links" | "-l" | "--copy-links" | "-L"))
+            .collect();
+
+        profile.insert("rclone_flags".to_string(), json!(remaining));
+        profile.entry("symlink_mode").or_insert_with(|| json!(symlink_mode));
     }
 }
 
@@ -90,9 +429,27 @@ pub async fn save_config(config: &AppConfig) -> Result<(), String> {
     let _: serde_json::Value = serde_json::from_str(&content)
         .map_err(|e| format!("Generated invalid JSON: {}", e))?;
 
-    // Write atomically using a temp file + rename to prevent corruption
+    // Keep the previous good version around so load_config can fall back to it
+    if config_file.exists() {
+        let previous_is_valid = fs::read_to_string(&config_file)
+            .ok()
+            .is_some_and(|previous| serde_json::from_str::<serde_json::Value>(&previous).is_ok());
+        if previous_is_valid {
+            let backup_file = config_file.with_extension("json.bak");
+            if let Err(e) = fs::copy(&config_file, &backup_file) {
+                warn!("Failed to back up previous config: {}", e);
+            }
+        }
+    }
+
+    // Write atomically using a temp file + rename to prevent corruption.
+    // Flush to disk before the rename so a crash can't leave a truncated file in place.
     let temp_file = config_file.with_extension("json.tmp");
-    fs::write(&temp_file, &content).map_err(|e| e.to_string())?;
+    {
+        let mut file = fs::File::create(&temp_file).map_err(|e| e.to_string())?;
+        file.write_all(content.as_bytes()).map_err(|e| e.to_string())?;
+        file.sync_all().map_err(|e| e.to_string())?;
+    }
     fs::rename(&temp_file, &config_file).map_err(|e| e.to_string())?;
 
     // Release lock (by dropping the file handle)
@@ -116,13 +473,13 @@ fn acquire_config_lock(lock_file: &PathBuf) -> Result<std::fs::File, String> {
         match lock_handle.try_lock_exclusive() {
             Ok(()) => {
                 if attempt > 0 {
-                    eprintln!("[CONFIG] Acquired lock after {} attempts", attempt + 1);
+                    debug!("Acquired config lock after {} attempts", attempt + 1);
                 }
                 return Ok(lock_handle);
             }
             Err(_) => {
                 if attempt == 0 {
-                    eprintln!("[CONFIG] Config is locked, waiting...");
+                    debug!("Config is locked, waiting...");
                 }
                 std::thread::sleep(retry_delay);
             }
@@ -132,12 +489,139 @@ fn acquire_config_lock(lock_file: &PathBuf) -> Result<std::fs::File, String> {
     Err("Failed to acquire config lock after 10 seconds - another process may have it locked".to_string())
 }
 
+#[command]
+pub async fn get_settings() -> Result<AppSettings, String> {
+    let config = load_config().await?;
+    Ok(config.settings)
+}
+
+#[command]
+pub async fn update_settings(mut settings: AppSettings) -> Result<AppSettings, String> {
+    settings.default_region = settings.default_region.trim().to_string();
+    if settings.default_region.is_empty() {
+        return Err("Default region cannot be empty".to_string());
+    }
+    if settings.default_transfers == 0 || settings.default_checkers == 0 {
+        return Err("Default transfers and checkers must be at least 1".to_string());
+    }
+    if settings.log_retention_count == 0 {
+        return Err("Log retention must keep at least one operation".to_string());
+    }
+    let trimmed = |value: Option<String>| value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    settings.preferred_rclone_bin = trimmed(settings.preferred_rclone_bin);
+    settings.aws_cli_path = trimmed(settings.aws_cli_path);
+    settings.http_proxy = trimmed(settings.http_proxy);
+    settings.https_proxy = trimmed(settings.https_proxy);
+    settings.no_proxy = trimmed(settings.no_proxy);
+    settings.bind_address = trimmed(settings.bind_address);
+    for (name, value) in [("HTTP proxy", &settings.http_proxy), ("HTTPS proxy", &settings.https_proxy), ("Bind address", &settings.bind_address)] {
+        if value.as_ref().is_some_and(|value| value.contains(char::is_whitespace)) {
+            return Err(format!("{} cannot contain spaces", name));
+        }
+    }
+
+    let mut config = load_config().await?;
+    config.settings = settings.clone();
+    config.updated_at = Utc::now();
+    save_config(&config).await?;
+    crate::network::set_network_settings(&settings);
+
+    debug!("Updated app settings: {:?}", settings);
+    Ok(settings)
+}
+
 #[command]
 pub async fn get_profiles() -> Result<Vec<Profile>, String> {
     let config = load_config().await?;
     Ok(config.profiles)
 }
 
+/// One line per profile for list views: whether it is scheduled and how its last backup went,
+/// from a single config read instead of a schedule and operations call per profile
+#[command]
+pub async fn get_profiles_summary() -> Result<Vec<ProfileSummary>, String> {
+    let config = load_config().await?;
+
+    Ok(config.profiles.iter().map(|profile| {
+        let last_backup = config.backup_operations.iter()
+            .filter(|op| op.profile_id == profile.id && matches!(op.operation_type, OperationType::Backup))
+            .max_by_key(|op| op.started_at);
+        let schedule = profile.schedule.as_ref().filter(|s| s.enabled);
+
+        ProfileSummary {
+            id: profile.id.clone(),
+            name: profile.name.clone(),
+            is_scheduled: schedule.is_some(),
+            next_run: schedule.and_then(crate::schedule::calculate_next_run),
+            last_operation_status: last_backup.map(|op| op.status.clone()),
+            last_run_at: last_backup.map(|op| op.completed_at.unwrap_or(op.started_at)),
+        }
+    }).collect())
+}
+
+/// Profiles carrying any of `tags`; an empty list returns every profile
+#[command]
+pub async fn get_profiles_filtered(tags: Vec<String>) -> Result<Vec<Profile>, String> {
+    let config = load_config().await?;
+    let wanted: Vec<String> = tags.iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    if wanted.is_empty() {
+        return Ok(config.profiles);
+    }
+
+    Ok(config.profiles.into_iter()
+        .filter(|p| p.tags.iter().any(|t| wanted.contains(&t.to_lowercase())))
+        .collect())
+}
+
+#[command]
+pub async fn add_profile_tag(profile_id: String, tag: String) -> Result<Profile, String> {
+    let tag = tag.trim().to_string();
+    if tag.is_empty() {
+        return Err("Tag cannot be empty".to_string());
+    }
+
+    let mut config = load_config().await?;
+    let profile = config.profiles.iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+
+    if !profile.tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+        profile.tags.push(tag);
+        profile.updated_at = Utc::now();
+    }
+    let updated = profile.clone();
+
+    config.updated_at = Utc::now();
+    save_config(&config).await?;
+    Ok(updated)
+}
+
+#[command]
+pub async fn remove_profile_tag(profile_id: String, tag: String) -> Result<Profile, String> {
+    let tag = tag.trim();
+    let mut config = load_config().await?;
+    let profile = config.profiles.iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+
+    let initial_len = profile.tags.len();
+    profile.tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+    if profile.tags.len() != initial_len {
+        profile.updated_at = Utc::now();
+    }
+    let updated = profile.clone();
+
+    config.updated_at = Utc::now();
+    save_config(&config).await?;
+    Ok(updated)
+}
+
 #[command]
 pub async fn get_or_create_user_profile(
     user_id: String,
@@ -208,6 +692,7 @@ pub async fn get_or_create_user_profile(
     };
 
     let mut profile = Profile::new(email.clone(), profile_type);
+    config.settings.apply_profile_defaults(&mut profile);
     profile.user_id = Some(user_id.clone());
     profile.bucket = bucket;
 
@@ -281,69 +766,588 @@ acl = private
         region
     );
 
-    println!("Writing rclone config to: {}", rclone_conf_path.display());
+    info!("Writing rclone config to: {}", rclone_conf_path.display());
     fs::write(&rclone_conf_path, &rclone_config)
         .map_err(|e| format!("Failed to write rclone config: {}", e))?;
 
-    println!("Rclone config written successfully");
-    Ok(())
-}
+    info!("Rclone config written successfully");
+    Ok(())
+}
+
+#[command]
+pub async fn create_profile(name: String, profile_type: ProfileType) -> Result<Profile, String> {
+    let mut config = load_config().await?;
+    let mut profile = Profile::new(name, profile_type);
+    config.settings.apply_profile_defaults(&mut profile);
+
+    config.profiles.push(profile.clone());
+    config.updated_at = Utc::now();
+    
+    save_config(&config).await?;
+    Ok(profile)
+}
+
+/// Where the external or network drive holding `path` is mounted: /Volumes/<name> on
+/// macOS, /media/<user>/<name>, /run/media/<user>/<name> or /mnt/<name> on Linux, and
+/// the drive root on Windows. None for paths on the system volume.
+#[cfg(not(windows))]
+fn drive_mount_point(path: &Path) -> Option<PathBuf> {
+    let names: Vec<&str> = path.components()
+        .filter_map(|component| match component {
+            std::path::Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+    let depth = match names[..] {
+        ["Volumes", _, ..] | ["mnt", _, ..] => 2,
+        ["media", _, _, ..] => 3,
+        ["run", "media", _, _, ..] => 4,
+        _ => return None,
+    };
+
+    let mut mount_point = PathBuf::from("/");
+    mount_point.extend(&names[..depth]);
+    Some(mount_point)
+}
+
+#[cfg(windows)]
+fn drive_mount_point(path: &Path) -> Option<PathBuf> {
+    let mut components = path.components();
+    match (components.next(), components.next()) {
+        (Some(prefix @ std::path::Component::Prefix(_)), Some(root @ std::path::Component::RootDir)) => {
+            Some([prefix, root].iter().collect())
+        }
+        _ => None,
+    }
+}
+
+/// Whether a drive is mounted at `mount_point`. An empty mount directory left behind
+/// (e.g. /mnt/usb) is on the same device as its parent, so it doesn't count.
+#[cfg(unix)]
+fn is_mounted(mount_point: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    let Some(parent) = mount_point.parent() else {
+        return true;
+    };
+    match (fs::metadata(mount_point), fs::metadata(parent)) {
+        (Ok(mount), Ok(parent)) => mount.dev() != parent.dev(),
+        _ => false,
+    }
+}
+
+/// Whether the drive root `mount_point` is ready; a card reader with no card or a
+/// disconnected network drive keeps its letter but can't be read.
+#[cfg(windows)]
+fn is_mounted(mount_point: &Path) -> bool {
+    fs::read_dir(mount_point).is_ok()
+}
+
+/// Whether each of the profile's sources is there, and how full the volume holding it is.
+/// A source on an external drive that isn't plugged in shows as not mounted.
+#[command]
+pub async fn get_source_info(profile_id: String) -> Result<Vec<SourceInfo>, String> {
+    let config = load_config().await?;
+    let profile = config.profiles.iter()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+
+    Ok(profile.sources.iter().map(|source| {
+        let path = Path::new(source);
+        let mount_point = drive_mount_point(path);
+        let mounted = mount_point.as_deref().is_none_or(is_mounted);
+
+        // Measure the volume at the deepest folder that exists, unless that would be the
+        // system volume standing in for a missing drive
+        let volume_dir = path.ancestors().find(|dir| dir.exists()).filter(|_| mounted);
+        let total = volume_dir.and_then(|dir| fs2::total_space(dir).ok());
+        let free = volume_dir.and_then(|dir| fs2::available_space(dir).ok());
+        let used = volume_dir.and_then(|dir| {
+            Some(fs2::total_space(dir).ok()?.saturating_sub(fs2::free_space(dir).ok()?))
+        });
+
+        SourceInfo {
+            path: source.clone(),
+            exists: path.exists(),
+            mounted,
+            mount_point: mount_point.map(|dir| dir.to_string_lossy().to_string()),
+            total_bytes: total,
+            used_bytes: used,
+            free_bytes: free,
+        }
+    }).collect())
+}
+
+/// Check that every backup source exists and can be read; one message per problem
+pub fn source_path_problems(sources: &[String]) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for source in sources {
+        if source.trim().is_empty() {
+            problems.push("Source path is empty".to_string());
+            continue;
+        }
+
+        let path = Path::new(source);
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                match drive_mount_point(path).filter(|mount_point| !is_mounted(mount_point)) {
+                    Some(mount_point) => problems.push(format!("Drive not connected: {} (needed for {})", mount_point.display(), source)),
+                    None => problems.push(format!("Source not found (is the drive connected?): {}", source)),
+                }
+                continue;
+            }
+            Err(e) => {
+                problems.push(format!("Cannot access source {}: {}", source, e));
+                continue;
+            }
+        };
+
+        let readable = if metadata.is_dir() {
+            fs::read_dir(path).map(|_| ())
+        } else if metadata.is_file() {
+            fs::File::open(path).map(|_| ())
+        } else {
+            problems.push(format!("Source is not a file or directory: {}", source));
+            continue;
+        };
+
+        if let Err(e) = readable {
+            problems.push(format!("Source is not readable: {} ({})", source, e));
+        }
+    }
+
+    problems
+}
+
+/// Report problems with a profile before it is saved or scheduled; empty means OK
+#[command]
+pub async fn validate_profile(profile: Profile) -> Result<Vec<String>, String> {
+    let mut problems = source_path_problems(&profile.sources);
+    problems.extend(secondary_destination_problems(&profile));
+    Ok(problems)
+}
+
+/// Check secondary destinations: not blank, not repeated, and local ones on a connected drive
+fn secondary_destination_problems(profile: &Profile) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut seen = vec![profile.destination()];
+
+    for destination in &profile.secondary_destinations {
+        let destination = destination.trim();
+        if destination.is_empty() {
+            problems.push("Secondary destination is empty".to_string());
+            continue;
+        }
+        if seen.iter().any(|d| d.trim_end_matches('/') == destination.trim_end_matches('/')) {
+            problems.push(format!("Destination is listed more than once: {}", destination));
+            continue;
+        }
+        seen.push(destination.to_string());
+
+        // "remote:path" goes through rclone; anything else (including C:\...) is a local folder
+        let is_remote = destination.find(':').is_some_and(|i| i > 1);
+        if !is_remote {
+            let parent = Path::new(destination).parent().unwrap_or(Path::new(destination));
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                problems.push(format!("Secondary destination not reachable (is the drive connected?): {}", destination));
+            }
+        }
+    }
+
+    problems
+}
+
+#[command]
+pub async fn update_profile(profile: Profile) -> Result<Profile, String> {
+    // Reject size filters that would fail every backup
+    profile.backup_filter_flags()?;
+
+    let mut config = load_config().await?;
+
+    debug!("Attempting to update profile with ID: {}", profile.id);
+    debug!("Existing profile IDs in config: {:?}", config.profiles.iter().map(|p| &p.id).collect::<Vec<_>>());
+
+    if let Some(existing) = config.profiles.iter_mut().find(|p| p.id == profile.id) {
+        let mut updated_profile = profile;
+        updated_profile.updated_at = Utc::now();
+        *existing = updated_profile.clone();
+
+        config.updated_at = Utc::now();
+        save_config(&config).await?;
+        info!("Profile updated successfully");
+        Ok(updated_profile)
+    } else {
+        warn!("Profile not found! Looking for ID: {}", profile.id);
+        Err(format!("Profile not found. Looking for ID: {}, Available IDs: {:?}",
+            profile.id,
+            config.profiles.iter().map(|p| &p.id).collect::<Vec<_>>()))
+    }
+}
+
+#[command]
+pub async fn delete_profile(profile_id: String) -> Result<(), String> {
+    let mut config = load_config().await?;
+    
+    let initial_len = config.profiles.len();
+    config.profiles.retain(|p| p.id != profile_id);
+    
+    if config.profiles.len() == initial_len {
+        return Err("Profile not found".to_string());
+    }
+
+    // If we deleted the active profile, clear it
+    if config.active_profile_id.as_ref() == Some(&profile_id) {
+        config.active_profile_id = None;
+    }
+
+    config.updated_at = Utc::now();
+    save_config(&config).await?;
+    Ok(())
+}
+
+#[command]
+pub async fn duplicate_profile(profile_id: String, new_name: String) -> Result<Profile, String> {
+    let new_name = new_name.trim().to_string();
+    if new_name.is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+
+    let mut config = load_config().await?;
+    let mut profile = config.profiles.iter()
+        .find(|p| p.id == profile_id)
+        .cloned()
+        .ok_or("Profile not found")?;
+
+    let now = Utc::now();
+    profile.id = uuid::Uuid::new_v4().to_string();
+    profile.name = new_name;
+    profile.created_at = now;
+    profile.updated_at = now;
+
+    // The OS job id is derived from the profile, so the copy starts unscheduled
+    profile.schedule = None;
+
+    if let Some(aws_config) = profile.aws_config.as_mut() {
+        aws_config.needs_verification = true;
+    }
+
+    // Keychain entries and the crypt remote are keyed by profile id, so the copy needs its own
+    crate::crypt::copy_profile_secrets(&profile_id, &profile.id)?;
+    if profile.encryption_enabled {
+        crate::crypt::write_crypt_remote(&profile, Path::new(&profile.rclone_conf)).await?;
+        let scheduled_conf = get_config_dir()?.join("rclone-scheduled.conf");
+        if scheduled_conf.exists() {
+            crate::crypt::write_crypt_remote(&profile, &scheduled_conf).await?;
+        }
+    }
+
+    config.profiles.push(profile.clone());
+    config.updated_at = now;
+    save_config(&config).await?;
+
+    debug!("Duplicated profile {} as '{}' ({})", profile_id, profile.name, profile.id);
+    Ok(profile)
+}
+
+#[command]
+pub async fn export_profile(profile_id: String, include_secrets: Option<bool>) -> Result<String, String> {
+    let config = load_config().await?;
+    let mut profile = config.profiles.into_iter()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+
+    let include_secrets = include_secrets.unwrap_or(false);
+    let (crypt_secrets, rclone_config_password) = if include_secrets {
+        (crate::crypt::load_crypt_secrets(&profile.id)?, crate::crypt::load_config_password(&profile.id)?)
+    } else {
+        strip_profile_secrets(&mut profile);
+        (None, None)
+    };
+
+    let export = ProfileExport {
+        format_version: PROFILE_EXPORT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: Utc::now(),
+        secrets_included: include_secrets,
+        profile,
+        crypt_secrets,
+        rclone_config_password,
+    };
+
+    debug!("Exporting profile {} (secrets included: {})", profile_id, include_secrets);
+    serde_json::to_string_pretty(&export)
+        .map_err(|e| format!("Failed to serialize profile: {}", e))
+}
+
+/// Blank out AWS and employee keys so an export can be shared safely
+fn strip_profile_secrets(profile: &mut Profile) {
+    profile.notifications.webhook_url = None;
+    if let Some(aws_config) = profile.aws_config.as_mut() {
+        aws_config.aws_access_key_id.clear();
+        aws_config.aws_secret_access_key.clear();
+        aws_config.session_token = None;
+        for employee in aws_config.employees.iter_mut() {
+            employee.access_key_id.clear();
+            employee.secret_access_key.clear();
+        }
+    }
+}
+
+#[command]
+pub async fn import_profile(json: String) -> Result<Profile, String> {
+    let mut profile = parse_profile_import(&json).map_err(|errors| {
+        let problems: Vec<String> = errors.iter().map(ProfileFieldError::to_string).collect();
+        format!("Import file does not contain a valid profile: {}", problems.join("; "))
+    })?;
+
+    let now = Utc::now();
+    profile.id = uuid::Uuid::new_v4().to_string();
+    profile.created_at = now;
+    profile.updated_at = now;
+
+    // OS schedules are per-machine, so the imported schedule must be re-enabled here
+    if let Some(schedule) = profile.schedule.as_mut() {
+        schedule.enabled = false;
+    }
+
+    // Passwords kept in the keychain only come along in an export with secrets. Without
+    // them the flags are cleared, and encryption is turned back on by entering the passwords.
+    let export: Option<ProfileExport> = serde_json::from_str(&json).ok();
+    match export.as_ref().and_then(|export| export.crypt_secrets.as_ref()) {
+        Some(secrets) => crate::crypt::store_crypt_secrets(&profile.id, secrets)?,
+        None if profile.encryption_enabled => {
+            warn!("Import of '{}' has no encryption passwords; encryption is off until they are entered", profile.name);
+            profile.encryption_enabled = false;
+        }
+        None => {}
+    }
+    match export.as_ref().and_then(|export| export.rclone_config_password.as_deref()) {
+        Some(password) => crate::crypt::store_config_password(&profile.id, password)?,
+        None if profile.rclone_config_encrypted => {
+            warn!("Import of '{}' has no rclone config password; it has to be entered again", profile.name);
+            profile.rclone_config_encrypted = false;
+        }
+        None => {}
+    }
+    if profile.encryption_enabled && Path::new(&profile.rclone_conf).exists() {
+        crate::crypt::write_crypt_remote(&profile, Path::new(&profile.rclone_conf)).await?;
+    }
+
+    let mut config = load_config().await?;
+    config.profiles.push(profile.clone());
+    config.updated_at = now;
+    save_config(&config).await?;
+
+    debug!("Imported profile '{}' as {}", profile.name, profile.id);
+    Ok(profile)
+}
+
+/// Check an import file the way import_profile will, reporting every problem by field
+/// instead of stopping at the first. Empty means it can be imported.
+#[command]
+pub async fn validate_profile_json(json: String) -> Result<Vec<ProfileFieldError>, String> {
+    Ok(parse_profile_import(&json).err().unwrap_or_default())
+}
+
+fn field_error(field: impl Into<String>, message: impl Into<String>) -> ProfileFieldError {
+    ProfileFieldError {
+        field: field.into(),
+        message: message.into(),
+    }
+}
+
+/// The profile in an import file (an export_profile wrapper or a bare profile object),
+/// or everything wrong with it
+fn parse_profile_import(json: &str) -> Result<Profile, Vec<ProfileFieldError>> {
+    use serde_json::json;
+
+    let value: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| vec![field_error("", format!("Not valid JSON: {}", e))])?;
+
+    let mut profile_value = match value.get("profile") {
+        Some(profile) => {
+            let format_version = value.get("format_version")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(1);
+            if format_version > PROFILE_EXPORT_VERSION as u64 {
+                return Err(vec![field_error("format_version", format!(
+                    "Exported by a newer version of the app (format {}, supported up to {}). Please update before importing.",
+                    format_version, PROFILE_EXPORT_VERSION
+                ))]);
+            }
+            profile.clone()
+        }
+        None => value,
+    };
+
+    let Some(object) = profile_value.as_object_mut() else {
+        return Err(vec![field_error("", "Expected a profile object")]);
+    };
+    // import_profile replaces these, so a file trimmed by hand may leave them out
+    let now = json!(Utc::now());
+    object.entry("id").or_insert_with(|| json!(""));
+    object.entry("created_at").or_insert_with(|| now.clone());
+    object.entry("updated_at").or_insert_with(|| now.clone());
+
+    let errors = profile_structure_errors(object);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // Anything the structure checks don't cover is still reported, if less precisely
+    let profile: Profile = serde_json::from_value(profile_value)
+        .map_err(|e| vec![field_error("", e.to_string())])?;
+
+    let errors = profile_value_errors(&profile);
+    if errors.is_empty() {
+        Ok(profile)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Missing fields and fields of the wrong type, which serde would report one at a time
+fn profile_structure_errors(object: &serde_json::Map<String, serde_json::Value>) -> Vec<ProfileFieldError> {
+    use serde_json::Value;
+
+    let mut errors = Vec::new();
+
+    for field in ["name", "rclone_bin", "rclone_conf", "remote", "bucket", "prefix"] {
+        match object.get(field) {
+            None | Some(Value::Null) => errors.push(field_error(field, "Required")),
+            Some(Value::String(_)) => {}
+            Some(_) => errors.push(field_error(field, "Must be text")),
+        }
+    }
+
+    let one_of = |field: &str, allowed: &[&str], required: bool, errors: &mut Vec<ProfileFieldError>| {
+        match object.get(field) {
+            None | Some(Value::Null) if !required => {}
+            None | Some(Value::Null) => errors.push(field_error(field, format!("Required, one of {}", allowed.join(", ")))),
+            Some(Value::String(s)) if allowed.contains(&s.as_str()) => {}
+            Some(other) => errors.push(field_error(field, format!("Must be one of {}, not {}", allowed.join(", "), other))),
+        }
+    };
+    one_of("profile_type", &["Admin", "User"], true, &mut errors);
+    one_of("mode", &["Copy", "Sync"], true, &mut errors);
+    one_of("hash_check", &["Checksum", "SizeModTime", "SizeOnly"], false, &mut errors);
+    one_of("log_level", &["Quiet", "Normal", "Verbose", "Debug"], false, &mut errors);
+    one_of("symlink_mode", &["Skip", "Copy", "Follow"], false, &mut errors);
+
+    let required_lists = ["sources", "rclone_flags"];
+    for field in ["sources", "rclone_flags", "include_patterns", "exclude_patterns", "tags", "secondary_destinations"] {
+        match object.get(field) {
+            None | Some(Value::Null) if required_lists.contains(&field) => errors.push(field_error(field, "Required, a list of text")),
+            None | Some(Value::Null) => {}
+            Some(Value::Array(items)) => {
+                for (index, item) in items.iter().enumerate() {
+                    if !item.is_string() {
+                        errors.push(field_error(format!("{}[{}]", field, index), "Must be text"));
+                    }
+                }
+            }
+            Some(_) => errors.push(field_error(field, "Must be a list of text")),
+        }
+    }
+
+    for field in ["max_parallel_sources", "transfers", "checkers", "retries", "low_level_retries", "sync_delete_threshold", "transfer_timeout_secs", "retries_sleep_secs", "stats_interval_secs"] {
+        if let Some(value) = object.get(field).filter(|v| !v.is_null()) {
+            let max = if field.ends_with("_secs") { u64::MAX } else { u32::MAX as u64 };
+            if value.as_u64().is_none_or(|n| n > max) {
+                errors.push(field_error(field, "Must be a whole number of 0 or more"));
+            }
+        }
+    }
+
+    for field in ["encryption_enabled", "rclone_config_encrypted", "confirm_sync_deletions", "fail_on_secondary_error", "use_backup_dir", "check_write_access", "detailed_stats"] {
+        if object.get(field).is_some_and(|v| !v.is_null() && !v.is_boolean()) {
+            errors.push(field_error(field, "Must be true or false"));
+        }
+    }
+
+    match object.get("schedule") {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(schedule)) => {
+            if !schedule.get("enabled").is_some_and(Value::is_boolean) {
+                errors.push(field_error("schedule.enabled", "Required, true or false"));
+            }
+            if !schedule.get("time").is_some_and(Value::is_string) {
+                errors.push(field_error("schedule.time", "Required, a time like 02:30"));
+            }
+            let frequency_ok = match schedule.get("frequency") {
+                Some(Value::String(s)) => s == "Daily",
+                Some(Value::Object(tagged)) => tagged.len() == 1
+                    && tagged.iter().all(|(kind, day)| {
+                        (kind == "Weekly" || kind == "Monthly") && day.as_u64().is_some_and(|d| d <= u8::MAX as u64)
+                    }),
+                _ => false,
+            };
+            if !frequency_ok {
+                errors.push(field_error("schedule.frequency", r#"Must be "Daily", {"Weekly": day} or {"Monthly": day}"#));
+            }
+        }
+        Some(_) => errors.push(field_error("schedule", "Must be a schedule object or null")),
+    }
 
-#[command]
-pub async fn create_profile(name: String, profile_type: ProfileType) -> Result<Profile, String> {
-    let mut config = load_config().await?;
-    let profile = Profile::new(name, profile_type);
-    
-    config.profiles.push(profile.clone());
-    config.updated_at = Utc::now();
-    
-    save_config(&config).await?;
-    Ok(profile)
+    errors
 }
 
-#[command]
-pub async fn update_profile(profile: Profile) -> Result<Profile, String> {
-    let mut config = load_config().await?;
+/// Values that parse but can't work: blank remote or bucket, relative sources, bad times
+fn profile_value_errors(profile: &Profile) -> Vec<ProfileFieldError> {
+    let mut errors = Vec::new();
 
-    println!("Attempting to update profile with ID: {}", profile.id);
-    println!("Existing profile IDs in config: {:?}", config.profiles.iter().map(|p| &p.id).collect::<Vec<_>>());
+    if profile.name.trim().is_empty() {
+        errors.push(field_error("name", "Cannot be empty"));
+    }
+    if profile.remote.trim().is_empty() {
+        errors.push(field_error("remote", "Cannot be empty"));
+    }
+    if profile.bucket.trim().is_empty() {
+        errors.push(field_error("bucket", "Cannot be empty"));
+    }
 
-    if let Some(existing) = config.profiles.iter_mut().find(|p| p.id == profile.id) {
-        let mut updated_profile = profile;
-        updated_profile.updated_at = Utc::now();
-        *existing = updated_profile.clone();
+    for (index, source) in profile.sources.iter().enumerate() {
+        let field = format!("sources[{}]", index);
+        if source.trim().is_empty() {
+            errors.push(field_error(field, "Cannot be empty"));
+        } else if !Path::new(source).is_absolute() {
+            errors.push(field_error(field, format!("Must be an absolute path on this computer, not {}", source)));
+        }
+    }
 
-        config.updated_at = Utc::now();
-        save_config(&config).await?;
-        println!("Profile updated successfully");
-        Ok(updated_profile)
-    } else {
-        println!("Profile not found! Looking for ID: {}", profile.id);
-        Err(format!("Profile not found. Looking for ID: {}, Available IDs: {:?}",
-            profile.id,
-            config.profiles.iter().map(|p| &p.id).collect::<Vec<_>>()))
+    if let Some(schedule) = &profile.schedule {
+        if chrono::NaiveTime::parse_from_str(&schedule.time, "%H:%M").is_err() {
+            errors.push(field_error("schedule.time", format!("Must be a 24-hour time like 02:30, not {}", schedule.time)));
+        }
+        match schedule.frequency {
+            ScheduleFrequency::Weekly(day) if day > 6 => {
+                errors.push(field_error("schedule.frequency", format!("Weekly day must be 0 (Sunday) to 6, not {}", day)));
+            }
+            ScheduleFrequency::Monthly(day) if !(1..=31).contains(&day) => {
+                errors.push(field_error("schedule.frequency", format!("Monthly day must be 1 to 31, not {}", day)));
+            }
+            _ => {}
+        }
     }
-}
 
-#[command]
-pub async fn delete_profile(profile_id: String) -> Result<(), String> {
-    let mut config = load_config().await?;
-    
-    let initial_len = config.profiles.len();
-    config.profiles.retain(|p| p.id != profile_id);
-    
-    if config.profiles.len() == initial_len {
-        return Err("Profile not found".to_string());
+    if profile.retries == 0 {
+        errors.push(field_error("retries", "Must be at least 1; rclone makes no attempt at all with 0"));
+    }
+    if profile.stats_interval_secs == 0 {
+        errors.push(field_error("stats_interval_secs", "Must be at least 1 second"));
     }
 
-    // If we deleted the active profile, clear it
-    if config.active_profile_id.as_ref() == Some(&profile_id) {
-        config.active_profile_id = None;
+    // The filter checks name the bound they reject, e.g. "Maximum file size: ..."
+    if let Err(e) = profile.size_flags() {
+        errors.push(field_error(if e.starts_with("Maximum") { "max_size" } else { "min_size" }, e));
+    }
+    if let Err(e) = profile.age_flags() {
+        errors.push(field_error(if e.starts_with("Maximum") { "max_age" } else { "min_age" }, e));
     }
 
-    config.updated_at = Utc::now();
-    save_config(&config).await?;
-    Ok(())
+    errors
 }
 
 #[command]
@@ -380,13 +1384,17 @@ pub async fn auto_configure_rclone(profile_id: String) -> Result<Profile, String
     let config_dir = get_config_dir()?;
     let rclone_conf = config_dir.join("rclone.conf").to_string_lossy().to_string();
     
-    // Update profile with rclone config path (rclone_bin is already set to "bundled")
+    // Update profile with rclone config path; rclone_bin stays "bundled" unless settings prefer another
     let mut config = load_config().await?;
+    let preferred_bin = config.settings.preferred_rclone_bin.clone();
     let profile = config.profiles.iter_mut()
         .find(|p| p.id == profile_id)
         .ok_or("Profile not found")?;
         
     profile.rclone_conf = rclone_conf;
+    if let Some(bin) = preferred_bin {
+        profile.rclone_bin = bin;
+    }
     profile.updated_at = Utc::now();
     
     let updated_profile = profile.clone();
@@ -397,84 +1405,467 @@ pub async fn auto_configure_rclone(profile_id: String) -> Result<Profile, String
 }
 
 #[command]
-pub async fn generate_rclone_config(_profile_id: String, remote_name: String, access_key: String, secret_key: String, region: String) -> Result<String, String> {
+#[allow(clippy::too_many_arguments)]
+pub async fn generate_rclone_config(
+    _profile_id: String,
+    remote_name: String,
+    access_key: String,
+    secret_key: String,
+    region: String,
+    provider: Option<String>,
+    endpoint: Option<String>,
+    session_token: Option<String>,
+) -> Result<String, String> {
     let config_dir = get_config_dir()?;
     let rclone_conf_path = config_dir.join("rclone.conf");
-    
-    let rclone_config = format!(
-        "[{}]
-type = s3
-provider = AWS
-access_key_id = {}
-secret_access_key = {}
-region = {}
-location_constraint = {}
 
-",
-        remote_name,
-        access_key,
-        secret_key,
-        region,
-        region
-    );
-    
+    let mut params = HashMap::new();
+    params.insert("access_key_id".to_string(), access_key);
+    params.insert("secret_access_key".to_string(), secret_key);
+    params.insert("region".to_string(), region);
+    if let Some(provider) = provider {
+        params.insert("provider".to_string(), provider);
+    }
+    if let Some(endpoint) = endpoint {
+        params.insert("endpoint".to_string(), endpoint);
+    }
+    if let Some(session_token) = session_token {
+        params.insert("session_token".to_string(), session_token);
+    }
+
+    let rclone_config = render_remote_config(&remote_name, &RemoteKind::S3, &params)?;
+
     fs::write(&rclone_conf_path, rclone_config).map_err(|e| e.to_string())?;
-    
+
+    Ok(rclone_conf_path.to_string_lossy().to_string())
+}
+
+/// Write (or replace) a single remote section in rclone.conf, leaving other remotes untouched
+#[command]
+pub async fn generate_remote_config(remote_name: String, kind: RemoteKind, params: HashMap<String, String>) -> Result<String, String> {
+    let config_dir = get_config_dir()?;
+    if !config_dir.exists() {
+        fs::create_dir_all(&config_dir).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+
+    let rclone_conf_path = config_dir.join("rclone.conf");
+    let section = render_remote_config(&remote_name, &kind, &params)?;
+
+    let existing = if rclone_conf_path.exists() {
+        fs::read_to_string(&rclone_conf_path).map_err(|e| format!("Failed to read rclone config: {}", e))?
+    } else {
+        String::new()
+    };
+
+    let mut rclone_config = remove_remote_section(&existing, &remote_name);
+    if !rclone_config.is_empty() && !rclone_config.ends_with("\n\n") {
+        rclone_config.push('\n');
+    }
+    rclone_config.push_str(&section);
+
+    fs::write(&rclone_conf_path, rclone_config)
+        .map_err(|e| format!("Failed to write rclone config: {}", e))?;
+
+    info!("Wrote {:?} remote '{}' to: {}", kind, remote_name, rclone_conf_path.display());
     Ok(rclone_conf_path.to_string_lossy().to_string())
 }
 
+fn render_remote_config(remote_name: &str, kind: &RemoteKind, params: &HashMap<String, String>) -> Result<String, String> {
+    let required = |key: &str| -> Result<&String, String> {
+        params.get(key)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| format!("Missing required parameter '{}' for {:?} remote", key, kind))
+    };
+
+    let mut lines = vec![
+        format!("[{}]", remote_name),
+        format!("type = {}", kind.rclone_type()),
+    ];
+
+    match kind {
+        RemoteKind::S3 => {
+            let region = required("region")?;
+            let provider = params.get("provider")
+                .filter(|v| !v.is_empty())
+                .cloned()
+                .unwrap_or_else(default_s3_provider);
+            let endpoint = params.get("endpoint").filter(|v| !v.is_empty());
+
+            lines.push(format!("provider = {}", provider));
+            lines.push(format!("access_key_id = {}", required("access_key_id")?));
+            lines.push(format!("secret_access_key = {}", required("secret_access_key")?));
+            if let Some(session_token) = params.get("session_token").filter(|v| !v.is_empty()) {
+                lines.push(format!("session_token = {}", session_token));
+            }
+            lines.push(format!("region = {}", region));
+            match endpoint {
+                Some(endpoint) => {
+                    validate_endpoint(endpoint)?;
+                    lines.push(format!("endpoint = {}", endpoint));
+                }
+                None if provider != "AWS" => {
+                    return Err(format!("Provider '{}' requires a custom endpoint URL", provider));
+                }
+                // location_constraint is AWS-specific; other providers reject or ignore it
+                None => lines.push(format!("location_constraint = {}", region)),
+            }
+        }
+        RemoteKind::GoogleDrive | RemoteKind::Dropbox => {
+            // OAuth token JSON comes from `rclone authorize "drive"` / `rclone authorize "dropbox"`
+            let token = params.get("token").filter(|v| !v.is_empty()).ok_or_else(|| {
+                format!(
+                    "Missing required parameter 'token' for {:?} remote. Run `rclone authorize \"{}\"` to obtain one.",
+                    kind,
+                    kind.rclone_type()
+                )
+            })?;
+
+            if let Some(client_id) = params.get("client_id").filter(|v| !v.is_empty()) {
+                lines.push(format!("client_id = {}", client_id));
+            }
+            if let Some(client_secret) = params.get("client_secret").filter(|v| !v.is_empty()) {
+                lines.push(format!("client_secret = {}", client_secret));
+            }
+            if *kind == RemoteKind::GoogleDrive {
+                let scope = params.get("scope").filter(|v| !v.is_empty()).map(|s| s.as_str()).unwrap_or("drive");
+                lines.push(format!("scope = {}", scope));
+                if let Some(root_folder_id) = params.get("root_folder_id").filter(|v| !v.is_empty()) {
+                    lines.push(format!("root_folder_id = {}", root_folder_id));
+                }
+            }
+            lines.push(format!("token = {}", token));
+        }
+        RemoteKind::Backblaze => {
+            lines.push(format!("account = {}", required("account")?));
+            lines.push(format!("key = {}", required("key")?));
+        }
+    }
+
+    Ok(format!("{}\n\n", lines.join("\n")))
+}
+
+/// Check that an S3 endpoint looks like http(s)://host[:port][/path]
+fn validate_endpoint(endpoint: &str) -> Result<(), String> {
+    let invalid = || format!("Invalid endpoint URL '{}': expected http(s)://host[:port]", endpoint);
+
+    let rest = endpoint.strip_prefix("https://")
+        .or_else(|| endpoint.strip_prefix("http://"))
+        .ok_or_else(invalid)?;
+    let authority = rest.split('/').next().unwrap_or("");
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (authority, None),
+    };
+
+    if host.is_empty() || endpoint.chars().any(char::is_whitespace) {
+        return Err(invalid());
+    }
+    if !host.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-') {
+        return Err(invalid());
+    }
+    if let Some(port) = port {
+        port.parse::<u16>().map_err(|_| invalid())?;
+    }
+
+    Ok(())
+}
+
+/// Strip the `[remote_name]` section (header through the line before the next header)
+fn remove_remote_section(content: &str, remote_name: &str) -> String {
+    let header = format!("[{}]", remote_name);
+    let mut skipping = false;
+    let mut kept = Vec::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            skipping = trimmed == header;
+        }
+        if !skipping {
+            kept.push(line);
+        }
+    }
+
+    let mut result = kept.join("\n");
+    if !result.is_empty() {
+        result.push('\n');
+    }
+    result
+}
+
 #[command]
-pub async fn save_backup_operation(operation: crate::models::BackupOperation) -> Result<(), String> {
+pub async fn save_backup_operation(mut operation: crate::models::BackupOperation) -> Result<(), String> {
     let mut config = load_config().await?;
 
-    // Add the new operation to the beginning of the list (most recent first)
-    config.backup_operations.insert(0, operation);
+    // Store the full log text in its own file so config.json only holds metadata
+    if !operation.log_output.is_empty() {
+        let logs_dir = get_operation_logs_dir()?;
+        fs::create_dir_all(&logs_dir).map_err(|e| format!("Failed to create logs dir: {}", e))?;
+        let log_file = get_operation_log_file(&operation.id)?;
+        fs::write(&log_file, &operation.log_output)
+            .map_err(|e| format!("Failed to write operation log: {}", e))?;
+        operation.log_output = String::new();
+    }
 
-    // Keep only the last 100 operations to avoid unlimited growth
-    if config.backup_operations.len() > 100 {
-        config.backup_operations.truncate(100);
+    // Replace an earlier record of the same run (its Running placeholder), otherwise
+    // add the new operation to the beginning of the list (most recent first)
+    if let Some(existing) = config.backup_operations.iter_mut().find(|op| op.id == operation.id) {
+        *existing = operation;
+    } else {
+        config.backup_operations.insert(0, operation);
     }
 
+    // Auto-prune to the retention configured in settings
+    let keep_count = config.settings.log_retention_count;
+    let keep_days = config.settings.log_retention_days;
+    prune_operations(&mut config, Some(keep_count), keep_days);
+
     config.updated_at = chrono::Utc::now();
     save_config(&config).await?;
     Ok(())
 }
 
+/// Remove backup operations older than `keep_days` or beyond the newest `keep_count`,
+/// deleting their on-disk log files. Returns the number of operations removed.
+#[command]
+pub async fn prune_backup_logs(keep_count: Option<usize>, keep_days: Option<u32>) -> Result<usize, String> {
+    if keep_count.is_none() && keep_days.is_none() {
+        return Ok(0);
+    }
+
+    let mut config = load_config().await?;
+    let removed = prune_operations(&mut config, keep_count, keep_days);
+
+    if removed > 0 {
+        config.updated_at = chrono::Utc::now();
+        save_config(&config).await?;
+    }
+
+    debug!("Pruned {} backup operations", removed);
+    Ok(removed)
+}
+
+fn prune_operations(config: &mut AppConfig, keep_count: Option<usize>, keep_days: Option<u32>) -> usize {
+    // Newest first so keep_count retains the most recent operations
+    config.backup_operations.sort_by_key(|op| std::cmp::Reverse(op.started_at));
+
+    let cutoff = keep_days.map(|days| Utc::now() - chrono::Duration::days(days as i64));
+    let keep_count = keep_count.unwrap_or(usize::MAX);
+
+    let operations = std::mem::take(&mut config.backup_operations);
+    let mut removed = 0;
+
+    for (index, operation) in operations.into_iter().enumerate() {
+        let too_old = cutoff.is_some_and(|cutoff| operation.started_at < cutoff);
+        if index >= keep_count || too_old {
+            remove_operation_log_file(&operation.id);
+            removed += 1;
+        } else {
+            config.backup_operations.push(operation);
+        }
+    }
+
+    removed
+}
+
+/// Collapse each run of consecutive identical failures in a profile's history into its
+/// latest operation, with repeat_count and first_started_at covering the ones removed.
+/// Failures match when they are the same kind of operation with the same error, numbers
+/// such as times and counts aside. Other operations are kept. Returns how many were removed.
+#[command]
+pub async fn compact_history(profile_id: String) -> Result<usize, String> {
+    let mut config = load_config().await?;
+    let removed = compact_operations(&mut config, &profile_id);
+
+    if removed > 0 {
+        config.updated_at = chrono::Utc::now();
+        save_config(&config).await?;
+    }
+
+    debug!("Compacted {} repeated failures of profile {}", removed, profile_id);
+    Ok(removed)
+}
+
+/// What makes two failed operations the same for compact_history; None for anything else
+fn failure_signature(operation: &crate::models::BackupOperation) -> Option<String> {
+    if !matches!(operation.status, OperationStatus::Failed) {
+        return None;
+    }
+
+    let mut signature = format!("{:?}:", operation.operation_type);
+    let mut in_number = false;
+    for c in operation.error_message.as_deref().unwrap_or("").trim().chars() {
+        if !c.is_ascii_digit() {
+            signature.push(c);
+        } else if !in_number {
+            signature.push('#');
+        }
+        in_number = c.is_ascii_digit();
+    }
+    Some(signature)
+}
+
+fn compact_operations(config: &mut AppConfig, profile_id: &str) -> usize {
+    // Newest first, so each run of failures is merged into its latest operation
+    config.backup_operations.sort_by_key(|op| std::cmp::Reverse(op.started_at));
+
+    let operations = std::mem::take(&mut config.backup_operations);
+    let mut removed = 0;
+    // Where the profile's previous operation was kept, if it was a failure, and its signature
+    let mut previous_failure: Option<(usize, String)> = None;
+
+    for operation in operations {
+        if operation.profile_id != profile_id {
+            config.backup_operations.push(operation);
+            continue;
+        }
+
+        let signature = failure_signature(&operation);
+        if let (Some((index, previous)), Some(signature)) = (&previous_failure, &signature) {
+            if previous == signature {
+                let kept = &mut config.backup_operations[*index];
+                kept.repeat_count += operation.repeat_count;
+                kept.first_started_at = Some(operation.first_started_at.unwrap_or(operation.started_at));
+                remove_operation_log_file(&operation.id);
+                removed += 1;
+                continue;
+            }
+        }
+
+        previous_failure = signature.map(|signature| (config.backup_operations.len(), signature));
+        config.backup_operations.push(operation);
+    }
+
+    removed
+}
+
 #[command]
 pub async fn clear_backup_operations() -> Result<usize, String> {
     let mut config = load_config().await?;
     let count = config.backup_operations.len();
 
+    for operation in &config.backup_operations {
+        remove_operation_log_file(&operation.id);
+    }
     config.backup_operations.clear();
     config.updated_at = chrono::Utc::now();
 
     save_config(&config).await?;
 
-    println!("[DEBUG] Cleared {} backup operations", count);
+    debug!("Cleared {} backup operations", count);
     Ok(count)
 }
 
+/// Directory the scheduled runner scripts log to.
+/// Windows, or with LAVOZ_CONFIG_DIR set: logs/ in get_config_dir
+/// macOS/Linux: ~/.config/cloud-backup-app/logs, even where the config dir is elsewhere
+pub fn get_scheduled_logs_dir() -> Result<PathBuf, String> {
+    if cfg!(windows) || config_dir_override().is_some() {
+        Ok(get_config_dir()?.join("logs"))
+    } else {
+        let home_dir = dirs::home_dir().ok_or("Could not determine home directory")?;
+        Ok(home_dir.join(".config/cloud-backup-app/logs"))
+    }
+}
+
+/// Directory the runner scripts drop their result JSON files into
+pub fn get_scheduled_results_dir() -> Result<PathBuf, String> {
+    Ok(get_scheduled_logs_dir()?.join("results"))
+}
+
+#[command]
+pub async fn scan_scheduled_results() -> Result<u32, String> {
+    let results_dir = get_scheduled_results_dir()?;
+    if !results_dir.exists() {
+        return Ok(0);
+    }
+
+    let mut result_files: Vec<PathBuf> = fs::read_dir(&results_dir)
+        .map_err(|e| format!("Failed to read results directory: {}", e))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    result_files.sort();
+
+    let mut ingested = 0;
+    for path in result_files {
+        match ingest_scheduled_result(&path).await {
+            Ok(true) => ingested += 1,
+            Ok(false) => {}
+            Err(e) => {
+                // Set unreadable results aside so they aren't retried on every startup
+                error!("Failed to ingest scheduled result {:?}: {}", path, e);
+                let _ = fs::rename(&path, path.with_extension("json.invalid"));
+            }
+        }
+    }
+
+    debug!("scan_scheduled_results: Ingested {} scheduled runs", ingested);
+    Ok(ingested)
+}
+
+/// Turn one result file into a backup operation. Returns false if the run was already recorded.
+async fn ingest_scheduled_result(path: &std::path::Path) -> Result<bool, String> {
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    // PowerShell's Out-File writes a UTF-8 BOM
+    let result: ScheduledRunResult = serde_json::from_str(content.trim_start_matches('\u{FEFF}'))
+        .map_err(|e| format!("Invalid result file: {}", e))?;
+
+    // Also match on start time so runs already picked up by sync_scheduled_backup_logs aren't duplicated
+    let config = load_config().await?;
+    let already_saved = config.backup_operations.iter().any(|op| {
+        op.id == result.operation_id
+            || (op.profile_id == result.profile_id
+                && (op.started_at.timestamp() - result.started_at.timestamp()).abs() < 60)
+    });
+
+    if !already_saved {
+        let log_output = result.log_file.as_ref()
+            .and_then(|log_file| fs::read_to_string(log_file).ok())
+            .map(|log| log.trim_start_matches('\u{FEFF}').to_string())
+            .unwrap_or_default();
+
+        save_backup_operation(BackupOperation {
+            id: result.operation_id.clone(),
+            profile_id: result.profile_id.clone(),
+            operation_type: OperationType::Backup,
+            status: result.status.clone(),
+            started_at: result.started_at,
+            completed_at: result.completed_at,
+            files_transferred: result.files_transferred,
+            bytes_transferred: result.bytes_transferred,
+            files_skipped: 0,
+            error_message: result.error_message.clone(),
+            log_output,
+            repeat_count: 1,
+            first_started_at: None,
+        }).await?;
+        update_schedule_after_backup(&result.profile_id, result.started_at).await?;
+    } else {
+        debug!("Scheduled run {} already recorded, skipping", result.operation_id);
+    }
+
+    if let Some(log_file) = &result.log_file {
+        let _ = fs::remove_file(log_file);
+    }
+    fs::remove_file(path).map_err(|e| e.to_string())?;
+
+    Ok(!already_saved)
+}
+
 #[command]
 pub async fn sync_scheduled_backup_logs(profile_id: String) -> Result<u32, String> {
     use std::fs;
     use chrono::{Utc, TimeZone};
     use regex::Regex;
 
-    // Use the same log directory as the backup script
-    // Windows: %APPDATA%\cloud-backup-app\logs (via get_config_dir)
-    // macOS/Linux: ~/.config/cloud-backup-app/logs (hardcoded to match bash script)
-    let logs_dir = if cfg!(windows) {
-        get_config_dir()?.join("logs")
-    } else {
-        let home_dir = dirs::home_dir().ok_or("Could not determine home directory")?;
-        home_dir.join(".config/cloud-backup-app/logs")
-    };
-    let log_file = logs_dir.join(format!("backup-{}.log", profile_id));
+    let log_file = get_scheduled_logs_dir()?.join(format!("backup-{}.log", profile_id));
 
-    println!("[DEBUG] sync_scheduled_backup_logs: Looking for log file at: {:?}", log_file);
+    debug!("sync_scheduled_backup_logs: Looking for log file at: {:?}", log_file);
 
     if !log_file.exists() {
-        println!("[DEBUG] sync_scheduled_backup_logs: Log file does not exist");
+        debug!("sync_scheduled_backup_logs: Log file does not exist");
         return Ok(0); // No log file, no operations to sync
     }
 
@@ -485,15 +1876,15 @@ pub async fn sync_scheduled_backup_logs(profile_id: String) -> Result<u32, Strin
         .filter(|op| op.profile_id == profile_id)
         .collect();
 
-    println!("[DEBUG] sync_scheduled_backup_logs: Found {} existing operations for profile {}",
+    debug!("sync_scheduled_backup_logs: Found {} existing operations for profile {}",
         existing_operations.len(), profile_id);
 
     // Try to read log file, handle UTF-8 errors gracefully
     let mut log_content = match fs::read_to_string(&log_file) {
         Ok(content) => content,
         Err(e) => {
-            println!("[DEBUG] sync_scheduled_backup_logs: Failed to read log file (possibly corrupted): {}", e);
-            println!("[DEBUG] sync_scheduled_backup_logs: Attempting to delete corrupted log file");
+            debug!("sync_scheduled_backup_logs: Failed to read log file (possibly corrupted): {}", e);
+            debug!("sync_scheduled_backup_logs: Attempting to delete corrupted log file");
             let _ = fs::remove_file(&log_file);
             return Ok(0); // Skip corrupted log file
         }
@@ -502,12 +1893,12 @@ pub async fn sync_scheduled_backup_logs(profile_id: String) -> Result<u32, Strin
     // Remove UTF-8 BOM if present (PowerShell adds this on Windows)
     if log_content.starts_with('\u{FEFF}') {
         log_content = log_content.trim_start_matches('\u{FEFF}').to_string();
-        println!("[DEBUG] sync_scheduled_backup_logs: Removed UTF-8 BOM from log file");
+        debug!("sync_scheduled_backup_logs: Removed UTF-8 BOM from log file");
     }
 
     let mut operations_created = 0;
 
-    println!("[DEBUG] sync_scheduled_backup_logs: Log file has {} bytes", log_content.len());
+    debug!("sync_scheduled_backup_logs: Log file has {} bytes", log_content.len());
     
     // Parse the log file for backup operations
     // Support both "Starting backup" and "Starting scheduled backup"
@@ -522,16 +1913,16 @@ pub async fn sync_scheduled_backup_logs(profile_id: String) -> Result<u32, Strin
     let lines: Vec<&str> = log_content.lines().collect();
     let mut current_operation: Option<crate::models::BackupOperation> = None;
 
-    println!("[DEBUG] sync_scheduled_backup_logs: Processing {} lines", lines.len());
+    debug!("sync_scheduled_backup_logs: Processing {} lines", lines.len());
 
     for (i, line) in lines.iter().enumerate() {
         // Debug first 10 lines to see format
         if i < 10 {
-            println!("[DEBUG] Line {}: {}", i, line);
+            debug!("Line {}: {}", i, line);
         }
 
         if let Some(caps) = start_regex.captures(line) {
-            println!("[DEBUG] Matched start line: {}", line);
+            debug!("Matched start line: {}", line);
             let timestamp_str = &caps[1];
             let profile_name = &caps[2];
 
@@ -539,12 +1930,12 @@ pub async fn sync_scheduled_backup_logs(profile_id: String) -> Result<u32, Strin
             // macOS/Linux: "Wed Aug 20 00:22:05 CDT 2025" (timezone abbreviation)
             // Windows: "Thu Jan 08 19:36:02 +00:00 2026" (timezone offset)
             let time_parts: Vec<&str> = timestamp_str.split_whitespace().collect();
-            println!("[DEBUG] Timestamp parts: {:?}", time_parts);
+            debug!("Timestamp parts: {:?}", time_parts);
             if time_parts.len() >= 6 {
                 // Format: ["Wed", "Aug", "20", "00:22:05", "CDT/+00:00", "2025"]
                 // We want: "Aug 20 2025 00:22:05"
                 let date_time_str = format!("{} {} {} {}", time_parts[1], time_parts[2], time_parts[5], time_parts[3]);
-                println!("[DEBUG] Parsing datetime string: {}", date_time_str);
+                debug!("Parsing datetime string: {}", date_time_str);
                 if let Ok(start_time) = chrono::NaiveDateTime::parse_from_str(&date_time_str, "%b %d %Y %H:%M:%S") {
                     // Convert from local time to UTC
                     use chrono::Local;
@@ -553,7 +1944,7 @@ pub async fn sync_scheduled_backup_logs(profile_id: String) -> Result<u32, Strin
                         .map(|local_dt| local_dt.with_timezone(&Utc))
                         .unwrap_or_else(|| Utc.from_utc_datetime(&start_time));
 
-                    println!("[DEBUG] Found backup start at: {:?}", start_time_utc);
+                    debug!("Found backup start at: {:?}", start_time_utc);
                     current_operation = Some(crate::models::BackupOperation {
                         id: uuid::Uuid::new_v4().to_string(),
                         profile_id: profile_id.clone(),
@@ -563,19 +1954,22 @@ pub async fn sync_scheduled_backup_logs(profile_id: String) -> Result<u32, Strin
                         completed_at: None,
                         files_transferred: 0,
                         bytes_transferred: 0,
+                        files_skipped: 0,
                         error_message: None,
                         log_output: format!("Scheduled backup started for profile: {}", profile_name),
+                        repeat_count: 1,
+                        first_started_at: None,
                     });
                 } else {
-                    println!("[DEBUG] Failed to parse datetime: {}", date_time_str);
+                    debug!("Failed to parse datetime: {}", date_time_str);
                 }
             } else {
-                println!("[DEBUG] Unexpected timestamp format, expected 6 parts but got {}", time_parts.len());
+                debug!("Unexpected timestamp format, expected 6 parts but got {}", time_parts.len());
             }
         } else if let Some(_caps) = complete_regex.captures(line) {
-            println!("[DEBUG] Matched completion line: {}", line);
+            debug!("Matched completion line: {}", line);
             if let Some(ref mut op) = current_operation {
-                println!("[DEBUG] Marking operation as completed");
+                debug!("Marking operation as completed");
                 op.status = crate::models::OperationStatus::Completed;
                 op.completed_at = Some(Utc::now());
 
@@ -587,12 +1981,12 @@ pub async fn sync_scheduled_backup_logs(profile_id: String) -> Result<u32, Strin
 
                 if !already_saved {
                     // Save the operation
-                    println!("[DEBUG] Saving completed scheduled backup operation: id={}, started_at={:?}, files={}, bytes={}",
+                    debug!("Saving completed scheduled backup operation: id={}, started_at={:?}, files={}, bytes={}",
                         op.id, op.started_at, op.files_transferred, op.bytes_transferred);
                     save_backup_operation(op.clone()).await?;
                     operations_created += 1;
                 } else {
-                    println!("[DEBUG] Operation already saved, skipping duplicate at {:?}", op.started_at);
+                    debug!("Operation already saved, skipping duplicate at {:?}", op.started_at);
                 }
 
                 // ALWAYS update the schedule's last_run and next_run, even for duplicates
@@ -638,7 +2032,7 @@ pub async fn sync_scheduled_backup_logs(profile_id: String) -> Result<u32, Strin
             save_backup_operation(op).await?;
             operations_created += 1;
         } else {
-            println!("[DEBUG] Final operation already saved, skipping duplicate at {:?}", started_at);
+            debug!("Final operation already saved, skipping duplicate at {:?}", started_at);
         }
 
         // ALWAYS update the schedule's last_run and next_run, even for duplicates
@@ -646,7 +2040,7 @@ pub async fn sync_scheduled_backup_logs(profile_id: String) -> Result<u32, Strin
         update_schedule_after_run(&profile_id, started_at).await?;
     }
 
-    println!("[DEBUG] sync_scheduled_backup_logs: Created {} new operations", operations_created);
+    debug!("sync_scheduled_backup_logs: Created {} new operations", operations_created);
     Ok(operations_created)
 }
 
@@ -672,7 +2066,7 @@ pub async fn update_schedule_after_backup(profile_id: &str, backup_started_at: c
                 profile.updated_at = Utc::now();
                 updated = true;
 
-                println!("[DEBUG] Updated schedule for profile {}: last_run={:?}, next_run={:?}",
+                debug!("Updated schedule for profile {}: last_run={:?}, next_run={:?}",
                     profile_id, schedule.last_run, schedule.next_run);
             }
         }
@@ -686,24 +2080,113 @@ pub async fn update_schedule_after_backup(profile_id: &str, backup_started_at: c
     Ok(())
 }
 
-fn parse_byte_size(size_str: &str) -> Result<u64, String> {
-    let size_str = size_str.replace(",", "").replace(" ", "");
-    
-    if size_str.ends_with("KiB") {
-        let num: f64 = size_str.trim_end_matches("KiB").parse().map_err(|e: std::num::ParseFloatError| e.to_string())?;
-        Ok((num * 1024.0) as u64)
-    } else if size_str.ends_with("MiB") {
-        let num: f64 = size_str.trim_end_matches("MiB").parse().map_err(|e: std::num::ParseFloatError| e.to_string())?;
-        Ok((num * 1024.0 * 1024.0) as u64)
-    } else if size_str.ends_with("GiB") {
-        let num: f64 = size_str.trim_end_matches("GiB").parse().map_err(|e: std::num::ParseFloatError| e.to_string())?;
-        Ok((num * 1024.0 * 1024.0 * 1024.0) as u64)
-    } else if size_str.ends_with("B") {
-        let num: u64 = size_str.trim_end_matches("B").parse().map_err(|e: std::num::ParseIntError| e.to_string())?;
-        Ok(num)
+/// Remote name auto_setup_rclone_complete gives a bucket, e.g. "my_bucket-s3"
+fn default_remote_name(bucket: &str) -> String {
+    format!("{}-s3", bucket.replace("-", "_"))
+}
+
+/// rclone s3 remote parameters for the stored credentials
+fn aws_remote_params(aws_config: &AwsConfig) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    params.insert("access_key_id".to_string(), aws_config.aws_access_key_id.clone());
+    params.insert("secret_access_key".to_string(), aws_config.aws_secret_access_key.clone());
+    params.insert("region".to_string(), aws_config.aws_region.clone());
+    params.insert("provider".to_string(), aws_config.provider.clone());
+    if let Some(session_token) = &aws_config.session_token {
+        params.insert("session_token".to_string(), session_token.clone());
+    }
+    if let Some(endpoint) = &aws_config.endpoint {
+        params.insert("endpoint".to_string(), endpoint.clone());
+    }
+    params
+}
+
+/// Remote parameters from the IAM keys stored for the profile's signed-in user, for
+/// employee profiles that have no AwsConfig of their own
+async fn stored_iam_remote_params(profile: &Profile) -> Result<HashMap<String, String>, String> {
+    const NO_CREDENTIALS: &str = "This profile has no stored credentials to rebuild rclone.conf from";
+
+    let user_id = profile.user_id.clone().ok_or(NO_CREDENTIALS)?;
+    let credentials = crate::iam_storage::get_stored_iam_credentials(user_id).await?
+        .ok_or(NO_CREDENTIALS)?;
+
+    let mut params = HashMap::new();
+    params.insert("access_key_id".to_string(), credentials.access_key_id);
+    params.insert("secret_access_key".to_string(), credentials.secret_access_key);
+    params.insert("region".to_string(), credentials.region);
+    params.insert("provider".to_string(), default_s3_provider());
+    if let Some(session_token) = credentials.session_token.filter(|token| !token.is_empty()) {
+        params.insert("session_token".to_string(), session_token);
+    }
+    Ok(params)
+}
+
+/// Rebuild the profile's remote in its rclone.conf from the stored AwsConfig (admin or
+/// employee keys), or the signed-in user's stored IAM keys, the way
+/// auto_setup_rclone_complete writes it, plus the crypt remote when encryption is on.
+/// Other remotes in the file are kept and the old file is saved as rclone.conf.bak. Both
+/// are written 0600. The result is checked with `rclone config show`.
+#[command]
+pub async fn repair_rclone_config(profile_id: String) -> Result<Profile, String> {
+    let mut config = load_config().await?;
+    let profile = config.profiles.iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    if profile.rclone_config_encrypted {
+        return Err("This profile's rclone.conf is encrypted and can't be rewritten. Repair it with `rclone config`, or remove the config password first.".to_string());
+    }
+
+    let rclone_conf_path = if profile.rclone_conf.is_empty() {
+        get_config_dir()?.join("rclone.conf")
+    } else {
+        PathBuf::from(&profile.rclone_conf)
+    };
+    let remote_name = if profile.remote.is_empty() {
+        default_remote_name(&profile.bucket)
     } else {
-        size_str.parse::<u64>().map_err(|e| e.to_string())
+        profile.remote.clone()
+    };
+    let params = match &profile.aws_config {
+        Some(aws_config) => aws_remote_params(aws_config),
+        None => stored_iam_remote_params(profile).await?,
+    };
+    let stanza = render_remote_config(&remote_name, &RemoteKind::S3, &params)?;
+
+    // An unreadable (e.g. binary garbage) file is replaced outright, but still backed up
+    let existing = fs::read(&rclone_conf_path).ok();
+    if let Some(bytes) = &existing {
+        write_private_file(&rclone_conf_path.with_extension("conf.bak"), bytes)
+            .map_err(|e| format!("Failed to back up {}: {}", rclone_conf_path.display(), e))?;
+    }
+    let contents = existing.and_then(|bytes| String::from_utf8(bytes).ok()).unwrap_or_default();
+    if crate::crypt::is_encrypted_rclone_config(&contents) {
+        return Err(format!("{} is encrypted; set its config password on the profile instead of repairing it", rclone_conf_path.display()));
+    }
+
+    if let Some(parent) = rclone_conf_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    write_private_file(&rclone_conf_path, crate::crypt::upsert_config_section(&contents, &remote_name, &stanza))
+        .map_err(|e| format!("Failed to write rclone config: {}", e))?;
+
+    profile.rclone_conf = rclone_conf_path.to_string_lossy().to_string();
+    profile.remote = remote_name.clone();
+    if profile.encryption_enabled {
+        crate::crypt::write_crypt_remote(profile, &rclone_conf_path).await?;
+    }
+
+    let output = crate::rclone::run_rclone(profile, "config", &["show".to_string(), remote_name.clone()], QUICK_TIMEOUT).await?;
+    if !output.success || !output.stdout.contains(&format!("[{}]", remote_name)) {
+        return Err(format!("Rewrote {} but rclone still can't read remote '{}': {}", rclone_conf_path.display(), remote_name, output.stderr.trim()));
     }
+
+    profile.updated_at = Utc::now();
+    let updated_profile = profile.clone();
+    config.updated_at = Utc::now();
+    save_config(&config).await?;
+
+    info!("Repaired rclone remote '{}' in {}", remote_name, rclone_conf_path.display());
+    Ok(updated_profile)
 }
 
 #[command]
@@ -718,26 +2201,29 @@ pub async fn auto_setup_rclone_complete(profile_id: String) -> Result<Profile, S
     let aws_config = profile.aws_config.as_ref()
         .ok_or("Profile does not have AWS configuration. Please complete admin setup first.")?;
     
-    // 1. Detect rclone binary
-    let rclone_paths = vec![
+    // 1. Detect rclone binary, trying the one chosen in settings first
+    let mut rclone_paths: Vec<String> = config.settings.preferred_rclone_bin.iter().cloned().collect();
+    rclone_paths.extend([
         "/opt/homebrew/bin/rclone",
         "/usr/local/bin/rclone", 
         "/usr/bin/rclone",
         "rclone"
-    ];
+    ].iter().map(|p| p.to_string()));
     
     let mut rclone_bin = None;
     for path in rclone_paths {
-        let result = Command::new(path)
-            .arg("version")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await;
+        let result = run_with_timeout(
+            Command::new(&path)
+                .arg("version")
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()),
+            QUICK_TIMEOUT,
+        )
+        .await;
             
         if let Ok(output) = result {
             if output.status.success() {
-                rclone_bin = Some(path.to_string());
+                rclone_bin = Some(path);
                 break;
             }
         }
@@ -749,23 +2235,8 @@ pub async fn auto_setup_rclone_complete(profile_id: String) -> Result<Profile, S
     let config_dir = get_config_dir()?;
     let rclone_conf_path = config_dir.join("rclone.conf");
     
-    let remote_name = format!("{}-s3", profile.bucket.replace("-", "_"));
-    let rclone_config = format!(
-        "[{}]
-type = s3
-provider = AWS
-access_key_id = {}
-secret_access_key = {}
-region = {}
-location_constraint = {}
-
-",
-        remote_name,
-        aws_config.aws_access_key_id,
-        aws_config.aws_secret_access_key,
-        aws_config.aws_region,
-        aws_config.aws_region
-    );
+    let remote_name = default_remote_name(&profile.bucket);
+    let rclone_config = render_remote_config(&remote_name, &RemoteKind::S3, &aws_remote_params(aws_config))?;
     
     fs::write(&rclone_conf_path, rclone_config).map_err(|e| format!("Failed to write rclone config: {}", e))?;
     
@@ -0,0 +1,103 @@
+use serde_json::Value;
+use tauri::command;
+use tracing::debug;
+
+use crate::models::*;
+use crate::process::DEFAULT_TIMEOUT;
+use crate::rclone::run_rclone;
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// S3 list prices for us-east-1 in USD. Other regions differ, so estimate_cost
+/// takes an override.
+pub const DEFAULT_S3_PRICING: S3Pricing = S3Pricing {
+    standard_per_gb_month: 0.023,
+    standard_ia_per_gb_month: 0.0125,
+    glacier_per_gb_month: 0.0036,
+    deep_archive_per_gb_month: 0.00099,
+    put_per_1000: 0.005,
+    ia_transition_per_1000: 0.01,
+    glacier_transition_per_1000: 0.03,
+    deep_archive_transition_per_1000: 0.05,
+};
+
+/// Rough AWS cost of backing up the profile's sources: monthly storage in the class the
+/// lifecycle policy eventually moves data to, plus the one-time upload and transition requests
+#[command]
+pub async fn estimate_cost(profile: Profile, pricing: Option<S3Pricing>) -> Result<CostEstimate, String> {
+    let pricing = pricing.unwrap_or(DEFAULT_S3_PRICING);
+    let filters = [profile.filter_flags(), profile.backup_filter_flags()?].concat();
+
+    let mut total_bytes = 0u64;
+    let mut total_files = 0u64;
+    for source in &profile.sources {
+        let (files, bytes) = source_size(&profile, source, &filters).await?;
+        total_files += files;
+        total_bytes += bytes;
+    }
+
+    let gb = total_bytes as f64 / BYTES_PER_GB;
+    let thousands = total_files as f64 / 1000.0;
+    let lifecycle = profile.aws_config.as_ref()
+        .map(|aws| aws.lifecycle_config.clone())
+        .filter(|lifecycle| lifecycle.enabled);
+
+    // Where the data settles: Standard with no lifecycle, else the coldest class configured.
+    // Objects going straight to Deep Archive skip the Glacier transition.
+    let (storage_class, per_gb_month, transition_request_cost) = match &lifecycle {
+        None => ("STANDARD", pricing.standard_per_gb_month, 0.0),
+        Some(l) if l.days_to_deep_archive != 999999 => {
+            let glacier_transition = if l.days_to_glacier < l.days_to_deep_archive {
+                pricing.glacier_transition_per_1000
+            } else {
+                0.0
+            };
+            (
+                "DEEP_ARCHIVE",
+                pricing.deep_archive_per_gb_month,
+                thousands * (pricing.ia_transition_per_1000 + glacier_transition + pricing.deep_archive_transition_per_1000),
+            )
+        }
+        Some(l) if l.days_to_glacier == 999999 => (
+            "STANDARD_IA",
+            pricing.standard_ia_per_gb_month,
+            thousands * pricing.ia_transition_per_1000,
+        ),
+        Some(_) => (
+            "GLACIER",
+            pricing.glacier_per_gb_month,
+            thousands * (pricing.ia_transition_per_1000 + pricing.glacier_transition_per_1000),
+        ),
+    };
+
+    debug!("estimate_cost - {} files, {} bytes, settling in {}", total_files, total_bytes, storage_class);
+
+    Ok(CostEstimate {
+        total_bytes,
+        total_files,
+        storage_class: storage_class.to_string(),
+        first_month_storage_cost: gb * pricing.standard_per_gb_month,
+        monthly_storage_cost: gb * per_gb_month,
+        upload_request_cost: thousands * pricing.put_per_1000,
+        transition_request_cost,
+        pricing,
+    })
+}
+
+/// (file count, total bytes) of one source as `rclone size` sees it with the backup's filters
+async fn source_size(profile: &Profile, source: &str, filters: &[String]) -> Result<(u64, u64), String> {
+    let mut args = vec![source.to_string(), "--json".to_string()];
+    args.extend(filters.iter().cloned());
+
+    let output = run_rclone(profile, "size", &args, DEFAULT_TIMEOUT).await?;
+    if !output.success {
+        return Err(format!("Failed to size {}: {}", source, output.stderr));
+    }
+
+    let size: Value = serde_json::from_str(&output.stdout)
+        .map_err(|e| format!("Failed to parse rclone size output: {}", e))?;
+    Ok((
+        size["count"].as_u64().unwrap_or(0),
+        size["bytes"].as_u64().unwrap_or(0),
+    ))
+}
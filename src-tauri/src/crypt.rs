@@ -0,0 +1,245 @@
+use std::fs;
+use std::path::Path;
+use std::process::Stdio;
+use chrono::Utc;
+use tauri::command;
+use tokio::io::AsyncWriteExt;
+use tracing::debug;
+
+use crate::config::{get_config_dir, load_config, save_config};
+use crate::models::*;
+use crate::process::QUICK_TIMEOUT;
+use crate::rclone::{create_command, resolve_rclone_binary};
+
+/// Keychain service the crypt passwords are stored under, one entry per profile
+const KEYCHAIN_SERVICE: &str = "cloud-backup-app";
+
+fn keychain_entry(profile_id: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, &format!("crypt-{}", profile_id))
+        .map_err(|e| format!("Failed to open keychain: {}", e))
+}
+
+fn config_password_entry(profile_id: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, &format!("rclone-config-{}", profile_id))
+        .map_err(|e| format!("Failed to open keychain: {}", e))
+}
+
+/// The password an encrypted rclone.conf is unlocked with, passed to rclone as RCLONE_CONFIG_PASS
+pub fn load_config_password(profile_id: &str) -> Result<Option<String>, String> {
+    match config_password_entry(profile_id)?.get_password() {
+        Ok(password) => Ok(Some(password)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read the rclone config password from keychain: {}", e)),
+    }
+}
+
+pub(crate) fn store_config_password(profile_id: &str, password: &str) -> Result<(), String> {
+    config_password_entry(profile_id)?
+        .set_password(password)
+        .map_err(|e| format!("Failed to store the rclone config password in keychain: {}", e))
+}
+
+/// True for a config encrypted with `rclone config encryption set`
+pub fn is_encrypted_rclone_config(contents: &str) -> bool {
+    contents.contains("RCLONE_ENCRYPT_V0:")
+}
+
+pub fn load_crypt_secrets(profile_id: &str) -> Result<Option<CryptSecrets>, String> {
+    match keychain_entry(profile_id)?.get_password() {
+        Ok(json) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse encryption passwords from keychain: {}", e)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read encryption passwords from keychain: {}", e)),
+    }
+}
+
+pub(crate) fn store_crypt_secrets(profile_id: &str, secrets: &CryptSecrets) -> Result<(), String> {
+    let json = serde_json::to_string(secrets).map_err(|e| e.to_string())?;
+    keychain_entry(profile_id)?
+        .set_password(&json)
+        .map_err(|e| format!("Failed to store encryption passwords in keychain: {}", e))
+}
+
+/// Give `to_id` the keychain entries of `from_id`, for a duplicated profile
+pub fn copy_profile_secrets(from_id: &str, to_id: &str) -> Result<(), String> {
+    if let Some(secrets) = load_crypt_secrets(from_id)? {
+        store_crypt_secrets(to_id, &secrets)?;
+    }
+    if let Some(password) = load_config_password(from_id)? {
+        store_config_password(to_id, &password)?;
+    }
+    Ok(())
+}
+
+/// `rclone obscure`, fed through stdin so the password never shows up in the process list
+async fn obscure(rclone_binary: &str, secret: &str) -> Result<String, String> {
+    let mut child = create_command(rclone_binary)
+        .args(["obscure", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to run rclone obscure: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(secret.as_bytes()).await.map_err(|e| e.to_string())?;
+    }
+
+    let output = tokio::time::timeout(QUICK_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| "rclone obscure timed out".to_string())?
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(format!("rclone obscure failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Replace the `[section]` block in an rclone config (or append it) with `stanza`
+pub(crate) fn upsert_config_section(contents: &str, section: &str, stanza: &str) -> String {
+    let header = format!("[{}]", section);
+    let mut kept = Vec::new();
+    let mut skipping = false;
+
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            skipping = trimmed == header;
+        }
+        if !skipping {
+            kept.push(line);
+        }
+    }
+
+    let mut result = kept.join("\n").trim_end().to_string();
+    if !result.is_empty() {
+        result.push_str("\n\n");
+    }
+    result.push_str(stanza);
+    result
+}
+
+/// Write the profile's crypt remote into `config_path`, using the passwords from the keychain
+pub async fn write_crypt_remote(profile: &Profile, config_path: &Path) -> Result<(), String> {
+    let secrets = load_crypt_secrets(&profile.id)?
+        .ok_or("No encryption passwords in the keychain for this profile")?;
+    let rclone_binary = resolve_rclone_binary(&profile.rclone_bin)?;
+
+    let stanza = format!(
+        "[{}]\ntype = crypt\nremote = {}\nfilename_encryption = standard\ndirectory_name_encryption = true\npassword = {}\npassword2 = {}\n\n",
+        profile.crypt_remote(),
+        profile.storage_path(),
+        obscure(&rclone_binary, &secrets.password).await?,
+        obscure(&rclone_binary, &secrets.password2).await?,
+    );
+
+    let contents = fs::read_to_string(config_path).unwrap_or_default();
+    if is_encrypted_rclone_config(&contents) {
+        return Err(format!(
+            "{} is encrypted, so the crypt remote can't be added to it. Add [{}] with `rclone config`, or use an unencrypted config.",
+            config_path.display(),
+            profile.crypt_remote()
+        ));
+    }
+    fs::write(config_path, upsert_config_section(&contents, &profile.crypt_remote(), &stanza))
+        .map_err(|e| format!("Failed to write {}: {}", config_path.display(), e))
+}
+
+/// Turn on client-side encryption for a profile. Passwords are generated unless given;
+/// keep a copy (see get_encryption_secrets), since without them the backups can't be read.
+#[command]
+pub async fn enable_profile_encryption(profile_id: String, password: Option<String>, password2: Option<String>) -> Result<Profile, String> {
+    let mut config = load_config().await?;
+    let profile = config.profiles.iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+
+    if profile.rclone_conf.is_empty() {
+        return Err("Configure rclone for this profile before enabling encryption".to_string());
+    }
+
+    // Reuse existing passwords so data encrypted earlier stays readable
+    let secrets = match load_crypt_secrets(&profile.id)? {
+        Some(existing) if password.is_none() && password2.is_none() => existing,
+        _ => CryptSecrets {
+            password: password.filter(|p| !p.is_empty()).unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string()),
+            password2: password2.filter(|p| !p.is_empty()).unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string()),
+        },
+    };
+    store_crypt_secrets(&profile.id, &secrets)?;
+
+    write_crypt_remote(profile, Path::new(&profile.rclone_conf)).await?;
+    let scheduled_conf = get_config_dir()?.join("rclone-scheduled.conf");
+    if scheduled_conf.exists() {
+        write_crypt_remote(profile, &scheduled_conf).await?;
+    }
+
+    profile.encryption_enabled = true;
+    profile.updated_at = Utc::now();
+    let updated = profile.clone();
+
+    config.updated_at = Utc::now();
+    save_config(&config).await?;
+
+    debug!("Enabled encryption for profile {} via {}", updated.id, updated.crypt_remote());
+    Ok(updated)
+}
+
+/// Back up unencrypted again. The crypt remote and keychain entry are left in place so
+/// data that was encrypted earlier can still be restored by re-enabling encryption.
+#[command]
+pub async fn disable_profile_encryption(profile_id: String) -> Result<Profile, String> {
+    let mut config = load_config().await?;
+    let profile = config.profiles.iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+
+    profile.encryption_enabled = false;
+    profile.updated_at = Utc::now();
+    let updated = profile.clone();
+
+    config.updated_at = Utc::now();
+    save_config(&config).await?;
+    Ok(updated)
+}
+
+/// Store (or with no password, forget) the password of the profile's encrypted rclone.conf.
+/// Every rclone run for the profile then gets it in RCLONE_CONFIG_PASS.
+#[command]
+pub async fn set_rclone_config_password(profile_id: String, password: Option<String>) -> Result<Profile, String> {
+    let mut config = load_config().await?;
+    let profile = config.profiles.iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+
+    match password.filter(|p| !p.is_empty()) {
+        Some(password) => {
+            store_config_password(&profile.id, &password)?;
+            profile.rclone_config_encrypted = true;
+        }
+        None => {
+            match config_password_entry(&profile.id)?.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => {}
+                Err(e) => return Err(format!("Failed to remove the rclone config password from keychain: {}", e)),
+            }
+            profile.rclone_config_encrypted = false;
+        }
+    }
+    profile.updated_at = Utc::now();
+    let updated = profile.clone();
+
+    config.updated_at = Utc::now();
+    save_config(&config).await?;
+
+    debug!("rclone config password {} for profile {}", if updated.rclone_config_encrypted { "set" } else { "cleared" }, updated.id);
+    Ok(updated)
+}
+
+#[command]
+pub async fn get_encryption_secrets(profile_id: String) -> Result<CryptSecrets, String> {
+    load_crypt_secrets(&profile_id)?
+        .ok_or_else(|| "No encryption passwords stored for this profile".to_string())
+}
@@ -0,0 +1,150 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+
+use crate::models::FileChange;
+
+/// How S3/rclone report a download of an object that is still in Glacier or Deep Archive
+pub const ARCHIVED_OBJECT_MARKERS: [&str; 3] = ["InvalidObjectState", "Object in GLACIER, restore first", "must be restored from archive"];
+
+/// Error returned by the rclone commands. Serializes as `{ "kind": ..., "message": ... }`
+/// so the UI can branch on the kind and still show the message.
+#[derive(Debug, Clone)]
+pub enum BackupError {
+    RcloneNotFound(String),
+    ConfigInvalid(String),
+    RemoteUnreachable(String),
+    AuthFailed(String),
+    SourceMissing(String),
+    Timeout(String),
+    Io(String),
+    Parse(String),
+    /// The files are in Glacier or Deep Archive and need an S3 restore before download
+    ArchivedObjects(String),
+    Other(String),
+    /// A Sync run would delete more files than the profile allows without confirmation;
+    /// also serializes `files_to_delete` so the UI can show them
+    SyncDeletionsPending { message: String, files_to_delete: Vec<FileChange> },
+}
+
+impl BackupError {
+    pub fn message(&self) -> &str {
+        match self {
+            BackupError::RcloneNotFound(m)
+            | BackupError::ConfigInvalid(m)
+            | BackupError::RemoteUnreachable(m)
+            | BackupError::AuthFailed(m)
+            | BackupError::SourceMissing(m)
+            | BackupError::Timeout(m)
+            | BackupError::Io(m)
+            | BackupError::Parse(m)
+            | BackupError::ArchivedObjects(m)
+            | BackupError::Other(m)
+            | BackupError::SyncDeletionsPending { message: m, .. } => m,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BackupError::RcloneNotFound(_) => "RcloneNotFound",
+            BackupError::ConfigInvalid(_) => "ConfigInvalid",
+            BackupError::RemoteUnreachable(_) => "RemoteUnreachable",
+            BackupError::AuthFailed(_) => "AuthFailed",
+            BackupError::SourceMissing(_) => "SourceMissing",
+            BackupError::Timeout(_) => "Timeout",
+            BackupError::Io(_) => "Io",
+            BackupError::Parse(_) => "Parse",
+            BackupError::ArchivedObjects(_) => "ArchivedObjects",
+            BackupError::Other(_) => "Other",
+            BackupError::SyncDeletionsPending { .. } => "SyncDeletionsPending",
+        }
+    }
+
+    /// Pick a kind for a plain error message, from our own wording and rclone's stderr
+    fn classify(message: String) -> Self {
+        let auth_markers = ["AccessDenied", "InvalidAccessKeyId", "SignatureDoesNotMatch", "ExpiredToken", "InvalidToken", "status code: 403"];
+        let network_markers = ["no such host", "connection refused", "dial tcp", "i/o timeout", "TLS handshake timeout", "network is unreachable"];
+        let config_markers = ["Rclone config not found", "didn't find section in config file", "Failed to load config file", "Invalid rclone config", "RCLONE_CONFIG_PASS", "config password"];
+        let source_markers = ["Source directory not found", "Source not found", "Invalid source path"];
+
+        // S3 answers downloads of archived objects with a 403, so check before auth
+        if ARCHIVED_OBJECT_MARKERS.iter().any(|m| message.contains(m)) {
+            BackupError::ArchivedObjects(message)
+        } else if message.contains("timed out") {
+            BackupError::Timeout(message)
+        } else if message.contains("Failed to find rclone binary")
+            || message.contains("Rclone binary not found")
+            || message.starts_with("rclone not found")
+        {
+            BackupError::RcloneNotFound(message)
+        } else if auth_markers.iter().any(|m| message.contains(m)) {
+            BackupError::AuthFailed(message)
+        } else if network_markers.iter().any(|m| message.contains(m)) {
+            BackupError::RemoteUnreachable(message)
+        } else if config_markers.iter().any(|m| message.contains(m)) {
+            BackupError::ConfigInvalid(message)
+        } else if source_markers.iter().any(|m| message.contains(m)) {
+            BackupError::SourceMissing(message)
+        } else {
+            BackupError::Other(message)
+        }
+    }
+}
+
+impl Serialize for BackupError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            BackupError::SyncDeletionsPending { message, files_to_delete } => {
+                let mut state = serializer.serialize_struct("BackupError", 3)?;
+                state.serialize_field("kind", self.kind())?;
+                state.serialize_field("message", message)?;
+                state.serialize_field("files_to_delete", files_to_delete)?;
+                state.end()
+            }
+            _ => {
+                let mut state = serializer.serialize_struct("BackupError", 2)?;
+                state.serialize_field("kind", self.kind())?;
+                state.serialize_field("message", self.message())?;
+                state.end()
+            }
+        }
+    }
+}
+
+impl fmt::Display for BackupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for BackupError {}
+
+impl From<String> for BackupError {
+    fn from(message: String) -> Self {
+        BackupError::classify(message)
+    }
+}
+
+impl From<&str> for BackupError {
+    fn from(message: &str) -> Self {
+        BackupError::classify(message.to_string())
+    }
+}
+
+impl From<std::io::Error> for BackupError {
+    fn from(error: std::io::Error) -> Self {
+        BackupError::Io(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for BackupError {
+    fn from(error: serde_json::Error) -> Self {
+        BackupError::Parse(error.to_string())
+    }
+}
+
+/// Lets String-returning callers keep using `?` on the rclone commands
+impl From<BackupError> for String {
+    fn from(error: BackupError) -> Self {
+        error.to_string()
+    }
+}
@@ -1,6 +1,7 @@
 use tauri::command;
 use serde::{Serialize, Deserialize};
 use std::fs;
+use tracing::{info, warn, error};
 use crate::config::get_config_dir;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -11,6 +12,8 @@ pub struct IAMCredentials {
     pub iam_username: String,
     pub bucket: String,
     pub s3_prefix: String,
+    #[serde(default)]
+    pub session_token: Option<String>, // Only for temporary credentials
 }
 
 #[command]
@@ -27,7 +30,7 @@ pub async fn store_iam_credentials(
 
     let creds_file = config_dir.join(format!("iam-{}.json", user_id));
 
-    println!("Storing IAM credentials for user: {} at: {}", user_id, creds_file.display());
+    info!("Storing IAM credentials for user: {} at: {}", user_id, creds_file.display());
 
     // TODO: Add encryption here using system keychain
     // For macOS: Use Security framework
@@ -41,8 +44,8 @@ pub async fn store_iam_credentials(
     fs::write(&creds_file, json)
         .map_err(|e| format!("Failed to write credentials file: {}", e))?;
 
-    println!("IAM credentials stored successfully");
-    println!("⚠️  WARNING: Credentials are stored unencrypted. Add keychain integration for production!");
+    info!("IAM credentials stored successfully");
+    warn!("Credentials are stored unencrypted. Add keychain integration for production!");
 
     Ok(())
 }
@@ -54,10 +57,10 @@ pub async fn get_stored_iam_credentials(
     let config_dir = get_config_dir()?;
     let creds_file = config_dir.join(format!("iam-{}.json", user_id));
 
-    println!("Looking for stored IAM credentials at: {}", creds_file.display());
+    info!("Looking for stored IAM credentials at: {}", creds_file.display());
 
     if !creds_file.exists() {
-        println!("No stored credentials found for user: {}", user_id);
+        info!("No stored credentials found for user: {}", user_id);
         return Ok(None);
     }
 
@@ -67,7 +70,7 @@ pub async fn get_stored_iam_credentials(
     let credentials: IAMCredentials = serde_json::from_str(&content)
         .map_err(|e| format!("Failed to parse credentials: {}", e))?;
 
-    println!("Found stored IAM credentials for user: {}", user_id);
+    info!("Found stored IAM credentials for user: {}", user_id);
     Ok(Some(credentials))
 }
 
@@ -79,7 +82,7 @@ pub async fn delete_iam_credentials(user_id: String) -> Result<(), String> {
     if creds_file.exists() {
         fs::remove_file(&creds_file)
             .map_err(|e| format!("Failed to delete credentials: {}", e))?;
-        println!("IAM credentials deleted for user: {}", user_id);
+        info!("IAM credentials deleted for user: {}", user_id);
     }
 
     Ok(())
@@ -99,6 +102,11 @@ pub async fn create_scheduled_rclone_config(
 
     let rclone_scheduled_conf = config_dir.join("rclone-scheduled.conf");
 
+    let session_token_line = credentials.session_token.as_ref()
+        .filter(|t| !t.is_empty())
+        .map(|t| format!("session_token = {}\n", t))
+        .unwrap_or_default();
+
     let rclone_config = format!(
         "[aws]
 type = s3
@@ -106,19 +114,28 @@ provider = AWS
 env_auth = false
 access_key_id = {}
 secret_access_key = {}
-region = {}
+{}region = {}
 acl = private
 
 ",
         credentials.access_key_id,
         credentials.secret_access_key,
+        session_token_line,
         credentials.region
     );
 
-    println!("Creating scheduled rclone config at: {}", rclone_scheduled_conf.display());
+    info!("Creating scheduled rclone config at: {}", rclone_scheduled_conf.display());
     fs::write(&rclone_scheduled_conf, &rclone_config)
         .map_err(|e| format!("Failed to write rclone scheduled config: {}", e))?;
 
-    println!("Scheduled rclone config created successfully");
+    // Encrypted profiles back up through their crypt remote, which must exist here too
+    let config = crate::config::load_config().await?;
+    for profile in config.profiles.iter().filter(|p| p.encryption_enabled) {
+        if let Err(e) = crate::crypt::write_crypt_remote(profile, &rclone_scheduled_conf).await {
+            error!("Failed to add crypt remote for profile {} to scheduled config: {}", profile.id, e);
+        }
+    }
+
+    info!("Scheduled rclone config created successfully");
     Ok(rclone_scheduled_conf.to_string_lossy().to_string())
 }
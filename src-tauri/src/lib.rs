@@ -5,6 +5,18 @@ mod schedule;
 mod aws;
 mod binary_resolver;
 mod iam_storage;
+mod process;
+mod size;
+mod run_lock;
+mod crypt;
+mod notify;
+mod error;
+mod cost;
+mod shutdown;
+mod logging;
+mod rc;
+mod operations;
+mod network;
 
 use rclone::*;
 use config::*;
@@ -12,6 +24,14 @@ use schedule::*;
 use aws::*;
 use binary_resolver::*;
 use iam_storage::*;
+use crypt::*;
+use notify::*;
+use cost::*;
+use logging::*;
+use rc::*;
+use operations::*;
+use tauri::Manager;
+use tracing::error;
 
 #[tauri::command]
 async fn ping() -> String {
@@ -20,41 +40,118 @@ async fn ping() -> String {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    init_logging();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![
             ping,
+            get_settings,
+            update_settings,
             get_profiles,
+            get_profiles_summary,
+            get_profiles_filtered,
+            add_profile_tag,
+            remove_profile_tag,
             get_or_create_user_profile,
             create_profile,
             update_profile,
+            validate_profile,
+            get_source_info,
             delete_profile,
+            duplicate_profile,
+            export_profile,
+            import_profile,
+            validate_profile_json,
             get_active_profile,
             set_active_profile,
             auto_configure_rclone,
             generate_rclone_config,
+            generate_remote_config,
             auto_setup_rclone_complete,
+            repair_rclone_config,
             save_backup_operation,
             clear_backup_operations,
+            prune_backup_logs,
+            compact_history,
             sync_scheduled_backup_logs,
+            scan_scheduled_results,
             detect_rclone,
+            get_rclone_version,
             validate_rclone_config,
+            validate_rclone_flags,
+            list_rclone_remotes,
+            get_rclone_config_path,
+            check_config_consistency,
+            reveal_in_file_manager,
+            read_rclone_config,
+            write_rclone_config,
+            test_connection,
+            diagnose_profile,
+            test_write_access,
+            send_test_notification,
+            enable_profile_encryption,
+            disable_profile_encryption,
+            get_encryption_secrets,
+            set_rclone_config_password,
             list_cloud_files,
+            list_cloud_files_page,
+            search_cloud_files,
+            rename_prefix,
+            purge_remote,
+            list_deleted_snapshots,
+            restore_deleted_snapshot,
+            prune_deleted_snapshots,
+            storage_report,
+            estimate_cost,
+            move_cloud_file,
+            delete_cloud_file,
             backup_run,
+            start_backup_with_rc,
+            get_transfer_stats,
+            get_active_operations,
+            get_operation,
             backup_preview,
+            changes_since_last_backup,
             restore_files,
+            restore_from_operation,
+            request_glacier_restore,
+            check_restore_status,
+            restore_preview,
+            list_file_versions,
+            restore_version,
             get_backup_logs,
+            get_operation_log,
+            copy_operation_log,
+            export_operation_log,
+            get_app_log_path,
             schedule_backup,
+            preview_schedule,
+            detect_schedule_conflicts,
             unschedule_backup,
             get_schedule_status,
+            set_schedule_enabled,
+            run_scheduled_now,
+            list_os_schedules,
+            cleanup_orphaned_schedules,
             check_aws_credentials,
             configure_aws_credentials,
+            assume_aws_role,
             validate_aws_permissions,
             setup_aws_infrastructure,
+            get_bucket_info,
+            update_lifecycle_policy,
             generate_employee_rclone_config,
+            list_employees,
+            add_employee,
             get_employee_credentials,
+            test_employee_isolation,
+            create_employee_bundle,
+            remove_employee,
+            rotate_employee_key,
             get_rclone_path,
+            get_aws_path,
             // IAM credential storage
             store_iam_credentials,
             get_stored_iam_credentials,
@@ -65,12 +162,43 @@ pub fn run() {
             // Initialize configuration directory
             tauri::async_runtime::spawn(async move {
                 if let Err(e) = initialize_config().await {
-                    eprintln!("Failed to initialize config: {}", e);
+                    error!("Failed to initialize config: {}", e);
+                }
+
+                // rclone and the AWS CLI get the proxy and bind settings from here
+                match load_config().await {
+                    Ok(config) => network::set_network_settings(&config.settings),
+                    Err(e) => error!("Failed to load network settings: {}", e),
+                }
+
+                // Backups/restores still marked Running were cut off when the app last exited
+                if let Err(e) = recover_interrupted_operations().await {
+                    error!("Failed to recover interrupted operations: {}", e);
+                }
+
+                // Pick up scheduled runs that finished while the app was closed
+                if let Err(e) = scan_scheduled_results().await {
+                    error!("Failed to scan scheduled backup results: {}", e);
                 }
             });
 
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        // Don't let closing the window or quitting orphan a running backup
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                if shutdown::intercept_exit(window.app_handle()) {
+                    api.prevent_close();
+                }
+            }
+        })
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::ExitRequested { api, .. } = event {
+                if shutdown::intercept_exit(app) {
+                    api.prevent_exit();
+                }
+            }
+        });
 }
\ No newline at end of file
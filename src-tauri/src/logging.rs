@@ -0,0 +1,67 @@
+use std::path::PathBuf;
+use tauri::command;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+
+use crate::config::get_config_dir;
+
+const LOG_FILE_PREFIX: &str = "app";
+const LOG_FILE_SUFFIX: &str = "log";
+/// Daily log files kept before the oldest is deleted
+const MAX_LOG_FILES: usize = 7;
+
+/// Used when RUST_LOG is unset: the app's own info messages, and only warnings from
+/// dependencies like the AWS SDK
+const DEFAULT_FILTER: &str = "warn,cloud_backup_app_lib=info";
+
+/// Directory of the app's own log files; operation logs live separately in logs/
+pub fn get_app_logs_dir() -> Result<PathBuf, String> {
+    Ok(get_config_dir()?.join("app-logs"))
+}
+
+/// Send tracing output to stderr and to a daily log file in the config dir.
+/// RUST_LOG overrides the level, e.g. RUST_LOG=cloud_backup_app_lib=debug.
+pub fn init_logging() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let stderr_layer = fmt::layer().with_writer(std::io::stderr);
+
+    // Writes go straight to the file, so nothing is lost when the app exits mid-run
+    let file_layer = get_app_logs_dir()
+        .and_then(|dir| {
+            RollingFileAppender::builder()
+                .rotation(Rotation::DAILY)
+                .filename_prefix(LOG_FILE_PREFIX)
+                .filename_suffix(LOG_FILE_SUFFIX)
+                .max_log_files(MAX_LOG_FILES)
+                .build(dir)
+                .map_err(|e| e.to_string())
+        })
+        .map(|appender| fmt::layer().with_ansi(false).with_writer(appender));
+
+    let file_error = file_layer.as_ref().err().cloned();
+    let _ = tracing_subscriber::registry()
+        .with(filter)
+        .with(stderr_layer)
+        .with(file_layer.ok())
+        .try_init();
+
+    if let Some(e) = file_error {
+        tracing::warn!("Logging to stderr only, could not open the log file: {}", e);
+    }
+}
+
+/// Path of the newest app log file, or the log directory if nothing has been written yet,
+/// so it can be attached to a bug report
+#[command]
+pub async fn get_app_log_path() -> Result<String, String> {
+    let dir = get_app_logs_dir()?;
+    let newest = std::fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(LOG_FILE_PREFIX))
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path);
+
+    Ok(newest.unwrap_or(dir).to_string_lossy().to_string())
+}
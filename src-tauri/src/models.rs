@@ -18,11 +18,99 @@ pub struct Profile {
     pub mode: BackupMode,
     pub schedule: Option<Schedule>,
     pub rclone_flags: Vec<String>,
+    #[serde(default)]
+    pub include_patterns: Vec<String>, // rclone --include filters, applied before excludes
+    #[serde(default)]
+    pub exclude_patterns: Vec<String>, // rclone --exclude filters, e.g. "node_modules/**"
+    #[serde(default)]
+    pub min_size: Option<String>, // Skip smaller files when backing up, e.g. "10K"
+    #[serde(default)]
+    pub max_size: Option<String>, // Skip larger files when backing up, e.g. "5G"
+    #[serde(default)]
+    pub min_age: Option<String>, // Only back up files older than this, e.g. "30d"
+    #[serde(default)]
+    pub max_age: Option<String>, // Only back up files newer than this, e.g. "1y"
+    #[serde(default = "default_max_parallel_sources")]
+    pub max_parallel_sources: u32, // How many sources backup_run transfers at once
+    #[serde(default = "default_transfer_timeout_secs")]
+    pub transfer_timeout_secs: u64, // Kill backup/restore transfers after this long; 0 = no limit
+    #[serde(default = "default_transfers")]
+    pub transfers: u32, // rclone --transfers, parallel file transfers per run
+    #[serde(default = "default_checkers")]
+    pub checkers: u32, // rclone --checkers, parallel equality checks per run
+    #[serde(default = "default_retries")]
+    pub retries: u32, // rclone --retries, attempts at the whole transfer when files fail; at least 1
+    #[serde(default = "default_low_level_retries")]
+    pub low_level_retries: u32, // rclone --low-level-retries, attempts at each failed request
+    #[serde(default)]
+    pub retries_sleep_secs: u64, // rclone --retries-sleep, pause between whole-transfer attempts
+    #[serde(default)]
+    pub hash_check: HashMode, // How rclone decides whether a file changed
+    #[serde(default)]
+    pub log_level: LogLevel, // rclone verbosity for backup and restore logs
+    #[serde(default)]
+    pub symlink_mode: SymlinkMode, // Whether backups skip, store or follow symlinks
+    #[serde(default = "default_stats_interval_secs")]
+    pub stats_interval_secs: u64, // rclone --stats, how often progress goes to the backup/restore log
+    #[serde(default)]
+    pub detailed_stats: bool, // Multi-line stats with per-file progress instead of --stats-one-line
+    #[serde(default)]
+    pub tags: Vec<String>, // Organizational labels only; never affect backup behavior
+    #[serde(default)]
+    pub encryption_enabled: bool, // Back up through the rclone crypt remote wrapping bucket/prefix
+    #[serde(default)]
+    pub rclone_config_encrypted: bool, // rclone.conf has a config password, kept in the keychain
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    #[serde(default = "default_confirm_sync_deletions")]
+    pub confirm_sync_deletions: bool, // Sync runs that would delete more than the threshold need confirming
+    #[serde(default)]
+    pub sync_delete_threshold: u32, // Deletions a Sync run may make unconfirmed; 0 = confirm any
+    #[serde(default)]
+    pub check_write_access: bool, // Test-write to the destination before each backup to fail fast on a read-only policy
+    #[serde(default)]
+    pub use_backup_dir: bool, // Move deleted and replaced files into a dated folder under _deleted instead of losing them
+    #[serde(default)]
+    pub secondary_destinations: Vec<String>, // Extra rclone paths backup_run also copies to, e.g. "/Volumes/Backup/lavoz"
+    #[serde(default)]
+    pub fail_on_secondary_error: bool, // false = a failed secondary destination is reported but the run completes
     pub aws_config: Option<AwsConfig>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+fn default_max_parallel_sources() -> u32 {
+    2
+}
+
+fn default_transfer_timeout_secs() -> u64 {
+    0
+}
+
+fn default_confirm_sync_deletions() -> bool {
+    true
+}
+
+pub fn default_transfers() -> u32 {
+    8
+}
+
+pub fn default_checkers() -> u32 {
+    32
+}
+
+fn default_stats_interval_secs() -> u64 {
+    1
+}
+
+pub fn default_retries() -> u32 {
+    3
+}
+
+pub fn default_low_level_retries() -> u32 {
+    10
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub enum ProfileType {
     Admin,
@@ -38,6 +126,18 @@ pub struct AwsConfig {
     pub bucket_name: String,
     pub lifecycle_config: LifecycleConfig,
     pub employees: Vec<Employee>,
+    #[serde(default = "default_s3_provider")]
+    pub provider: String, // rclone s3 provider: AWS, Minio, Wasabi, Cloudflare, Other...
+    #[serde(default)]
+    pub endpoint: Option<String>, // Custom S3 endpoint URL; None for AWS
+    #[serde(default)]
+    pub session_token: Option<String>, // Set for temporary (STS/MFA) credentials
+    #[serde(default)]
+    pub needs_verification: bool, // Set on copied credentials until the UI re-checks them
+}
+
+pub fn default_s3_provider() -> String {
+    "AWS".to_string()
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -45,6 +145,12 @@ pub struct LifecycleConfig {
     pub enabled: bool,
     pub days_to_ia: u32,
     pub days_to_glacier: u32,
+    #[serde(default = "default_days_to_deep_archive")]
+    pub days_to_deep_archive: u32, // Glacier Deep Archive; 999999 = never, like days_to_glacier
+}
+
+pub fn default_days_to_deep_archive() -> u32 {
+    999999
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -56,6 +162,73 @@ pub struct Employee {
     pub secret_access_key: String,
     pub rclone_config_generated: bool,
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub prefix: Option<String>, // Set when rename_prefix moved their backups away from {username}/
+}
+
+impl Employee {
+    /// The bucket prefix this employee backs up to and their IAM policy allows
+    pub fn backup_prefix(&self) -> &str {
+        self.prefix.as_deref().unwrap_or(&self.username)
+    }
+}
+
+/// Outcome of rotate_employee_key. The new key is live even when there are warnings.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KeyRotation {
+    pub employee: Employee,
+    pub rclone_config: String, // rclone.conf with the new key, to hand to the employee
+    pub bundle_path: Option<String>, // Onboarding bundle rewritten with the new key, if asked for
+    pub warnings: Vec<String>, // e.g. the old key couldn't be deleted and needs removing by hand
+}
+
+/// One listing attempted by test_employee_isolation with an employee's keys
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IsolationCheck {
+    pub target: String, // e.g. "bucket root" or "alice/ (Alice)"
+    pub expect_denied: bool,
+    pub denied: bool, // S3 answered AccessDenied
+    pub error: Option<String>, // Any other failure; the check is then inconclusive
+    pub passed: bool,
+}
+
+/// Result of test_employee_isolation; `isolated` only when every check passed
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IsolationReport {
+    pub employee_id: String,
+    pub username: String,
+    pub checks: Vec<IsolationCheck>,
+    pub isolated: bool,
+}
+
+/// Where to report finished backups. The webhook gets a JSON POST whose "text"/"content"
+/// fields make it work as a Slack or Discord incoming webhook.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct NotificationConfig {
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    #[serde(default = "default_notify_on_success")]
+    pub notify_on_success: bool, // false = only report failures
+}
+
+fn default_notify_on_success() -> bool {
+    true
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: None,
+            notify_on_success: default_notify_on_success(),
+        }
+    }
+}
+
+impl NotificationConfig {
+    /// The configured webhook, if any, ignoring blank values
+    pub fn webhook(&self) -> Option<&str> {
+        self.webhook_url.as_deref().map(str::trim).filter(|url| !url.is_empty())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -70,6 +243,88 @@ impl Default for BackupMode {
     }
 }
 
+/// How rclone compares source and destination files
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub enum HashMode {
+    #[default]
+    Checksum,    // --checksum: size + hash (MD5 on S3), slowest but safest
+    SizeModTime, // rclone's default: size + modification time
+    SizeOnly,    // --size-only: fastest, misses same-size edits
+}
+
+impl HashMode {
+    pub fn rclone_flag(&self) -> Option<&'static str> {
+        match self {
+            HashMode::Checksum => Some("--checksum"),
+            HashMode::SizeModTime => None,
+            HashMode::SizeOnly => Some("--size-only"),
+        }
+    }
+}
+
+/// How much rclone writes to the operation log during backups and restores
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub enum LogLevel {
+    Quiet,   // errors and notices only; files aren't listed, so the run reports 0 files
+    #[default]
+    Normal,  // -v: one line per transferred or deleted file, which the file count comes from
+    Verbose, // -vv: also every file checked and skipped
+    Debug,   // -vv plus HTTP request/response headers, for troubleshooting S3 errors
+}
+
+impl LogLevel {
+    pub fn rclone_flags(&self) -> &'static [&'static str] {
+        match self {
+            LogLevel::Quiet => &[],
+            LogLevel::Normal => &["-v"],
+            LogLevel::Verbose => &["-vv"],
+            LogLevel::Debug => &["-vv", "--dump=headers"],
+        }
+    }
+}
+
+/// What backups do with symlinks in the sources
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub enum SymlinkMode {
+    #[default]
+    Skip,   // rclone's default: left out, with a NOTICE per link in the log
+    Copy,   // --links: stored as small .rclonelink files holding the link target
+    Follow, // --copy-links: the file or folder the link points to is backed up in its place
+}
+
+impl SymlinkMode {
+    pub fn rclone_flag(&self) -> Option<&'static str> {
+        match self {
+            SymlinkMode::Skip => None,
+            SymlinkMode::Copy => Some("--links"),
+            SymlinkMode::Follow => Some("--copy-links"),
+        }
+    }
+}
+
+/// Storage backend an rclone remote points at. Profiles address every kind as
+/// `remote:bucket/prefix`; for Google Drive and Dropbox the bucket is a top-level folder.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub enum RemoteKind {
+    #[default]
+    S3,
+    GoogleDrive,
+    Dropbox,
+    Backblaze,
+}
+
+impl RemoteKind {
+    /// The rclone backend `type` written to rclone.conf
+    pub fn rclone_type(&self) -> &'static str {
+        match self {
+            RemoteKind::S3 => "s3",
+            RemoteKind::GoogleDrive => "drive",
+            RemoteKind::Dropbox => "dropbox",
+            RemoteKind::Backblaze => "b2",
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Schedule {
     pub enabled: bool,
@@ -79,6 +334,35 @@ pub struct Schedule {
     pub next_run: Option<DateTime<Utc>>,
 }
 
+/// Two scheduled profiles writing to overlapping destinations at about the same time
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScheduleConflict {
+    pub profile_id: String,
+    pub profile_name: String,
+    pub other_profile_id: String,
+    pub other_profile_name: String,
+    pub destination: String, // The first profile's overlapping destination
+    pub other_destination: String,
+    pub first_overlap: DateTime<Utc>, // Next time both start within the window
+    pub minutes_apart: i64,
+    pub sync_mode: bool, // Either profile syncs, so one run can delete the other's files
+}
+
+/// A scheduled job or runner script the app left on disk / in the OS scheduler
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct OsScheduleEntry {
+    pub profile_id: String,
+    pub kind: String,     // "launchd", "task_scheduler" or "script"
+    pub location: String, // plist/script path, or the Task Scheduler task name
+    pub orphaned: bool,   // true when no profile with this id exists anymore
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScheduleCleanupResult {
+    pub found: Vec<OsScheduleEntry>,
+    pub removed: Vec<OsScheduleEntry>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum ScheduleFrequency {
     Daily,
@@ -96,6 +380,86 @@ pub struct CloudFile {
     pub mime_type: Option<String>,
 }
 
+/// One page of list_cloud_files_page, sorted directories first
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CloudFilesPage {
+    pub files: Vec<CloudFile>,
+    pub page: usize,
+    pub page_size: usize,
+    pub total_count: usize,
+    pub has_more: bool,
+}
+
+/// Payload of "cloud-files-batch" events, sent while a listing is still running
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CloudFilesBatch {
+    pub path: String, // The path passed to list_cloud_files_page
+    pub files: Vec<CloudFile>, // Unsorted entries since the previous batch
+    pub listed: usize, // Entries listed so far
+}
+
+/// Payload of "cloud-search-results" events, sent as search_cloud_files finds matches
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CloudSearchBatch {
+    pub pattern: String, // The pattern passed to search_cloud_files, trimmed
+    pub files: Vec<CloudFile>, // Matches since the previous batch
+    pub found: usize, // Matches found so far
+}
+
+/// One stored version of a file in a versioned bucket. `version_id` is the name rclone
+/// exposes the version under with --s3-versions (e.g. `report-v2025-01-16-123456-000.txt`).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FileVersion {
+    pub version_id: String,
+    pub path: String,
+    pub size: u64,
+    pub mod_time: DateTime<Utc>,
+    pub is_current: bool,
+}
+
+/// Per-GB and per-request S3 prices in USD used by estimate_cost
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct S3Pricing {
+    pub standard_per_gb_month: f64,
+    pub standard_ia_per_gb_month: f64,
+    pub glacier_per_gb_month: f64,
+    #[serde(default = "default_deep_archive_per_gb_month")]
+    pub deep_archive_per_gb_month: f64,
+    pub put_per_1000: f64,
+    pub ia_transition_per_1000: f64,       // Lifecycle transition into Standard-IA
+    pub glacier_transition_per_1000: f64,  // Lifecycle transition into Glacier
+    #[serde(default = "default_deep_archive_transition_per_1000")]
+    pub deep_archive_transition_per_1000: f64, // Lifecycle transition into Deep Archive
+}
+
+fn default_deep_archive_per_gb_month() -> f64 {
+    crate::cost::DEFAULT_S3_PRICING.deep_archive_per_gb_month
+}
+
+fn default_deep_archive_transition_per_1000() -> f64 {
+    crate::cost::DEFAULT_S3_PRICING.deep_archive_transition_per_1000
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CostEstimate {
+    pub total_bytes: u64,
+    pub total_files: u64,
+    pub storage_class: String, // Class the data ends up in under the lifecycle policy
+    pub first_month_storage_cost: f64, // Everything starts in Standard
+    pub monthly_storage_cost: f64, // Once the lifecycle policy has moved everything
+    pub upload_request_cost: f64, // One-time PUTs for the initial upload
+    pub transition_request_cost: f64, // One-time lifecycle transitions
+    pub pricing: S3Pricing,
+}
+
+/// Storage used under one top-level bucket prefix (usually an employee's folder)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PrefixUsage {
+    pub prefix: String, // First path segment; empty for objects at the bucket root
+    pub total_bytes: u64,
+    pub file_count: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct BackupOperation {
     pub id: String,
@@ -106,8 +470,41 @@ pub struct BackupOperation {
     pub completed_at: Option<DateTime<Utc>>,
     pub files_transferred: u64,
     pub bytes_transferred: u64,
+    #[serde(default)]
+    pub files_skipped: u64, // Restores with resume: files already present locally and not re-downloaded
     pub error_message: Option<String>,
     pub log_output: String,
+    #[serde(default = "default_repeat_count")]
+    pub repeat_count: u32, // Identical failed runs compact_history merged into this one, itself included
+    #[serde(default)]
+    pub first_started_at: Option<DateTime<Utc>>, // Start of the oldest merged run; started_at is the latest
+}
+
+fn default_repeat_count() -> u32 {
+    1
+}
+
+/// Result file a scheduled runner script writes after each run; see scan_scheduled_results
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScheduledRunResult {
+    pub operation_id: String,
+    pub profile_id: String,
+    pub status: OperationStatus,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub files_transferred: u64,
+    pub bytes_transferred: u64,
+    #[serde(default)]
+    pub error_message: Option<String>,
+    #[serde(default)]
+    pub log_file: Option<String>, // Per-run rclone log, folded into the operation's log on ingest
+}
+
+/// One page of backup history; operations carry metadata only (log_output is fetched separately)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BackupLogsPage {
+    pub operations: Vec<BackupOperation>,
+    pub total_count: usize,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -121,6 +518,7 @@ pub enum OperationType {
 pub enum OperationStatus {
     Running,
     Completed,
+    PartialSuccess, // Finished, but some files failed; error_message lists them
     Failed,
     Cancelled,
 }
@@ -134,6 +532,45 @@ pub struct BackupPreview {
     pub total_size: u64,
 }
 
+/// A profile rclone flag (or flag and value) that rclone refused to parse
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct InvalidRcloneFlag {
+    pub flag: String,
+    pub message: String, // rclone's error, e.g. "unknown flag: --fast-lst"
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RcloneRemote {
+    pub name: String,
+    pub remote_type: String, // backend, e.g. "s3", "crypt", "drive"
+}
+
+/// Local changes in one backup source since the last successful backup
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SourceChanges {
+    pub source: String,
+    pub added: Vec<FileChange>,    // Not in the backup yet
+    pub modified: Vec<FileChange>, // Modified after the last backup finished
+    pub deleted: Vec<FileChange>,  // In the backup but gone locally; sync profiles only
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ChangesSinceBackup {
+    pub since: DateTime<Utc>, // When the last successful backup finished
+    pub sources: Vec<SourceChanges>,
+    pub total_files: u64,
+    pub total_size: u64,
+}
+
+/// A run's worth of files that use_backup_dir moved aside, see list_deleted_snapshots
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DeletedSnapshot {
+    pub name: String,                    // Folder name under _deleted
+    pub taken_at: Option<DateTime<Utc>>, // From the name; None for folders the app didn't create
+    pub files: u64,
+    pub bytes: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct FileChange {
     pub path: String,
@@ -150,22 +587,169 @@ pub enum ChangeAction {
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct AppConfig {
+    #[serde(default)]
+    pub schema_version: u32, // Missing in files written before versioning, i.e. version 0
     pub profiles: Vec<Profile>,
     pub active_profile_id: Option<String>,
     #[serde(default)]
     pub backup_operations: Vec<BackupOperation>,
+    #[serde(default)]
+    pub settings: AppSettings,
     pub app_version: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Cross-profile preferences, used as defaults when profiles are created
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AppSettings {
+    #[serde(default = "default_settings_region")]
+    pub default_region: String,
+    #[serde(default)]
+    pub preferred_rclone_bin: Option<String>, // None = bundled binary, then PATH lookup
+    #[serde(default)]
+    pub aws_cli_path: Option<String>, // None = installer/brew locations, then PATH lookup
+    #[serde(default = "default_log_retention_count")]
+    pub log_retention_count: usize, // Backup operations kept in history
+    #[serde(default)]
+    pub log_retention_days: Option<u32>, // Also drop operations older than this
+    #[serde(default = "default_transfers")]
+    pub default_transfers: u32,
+    #[serde(default = "default_checkers")]
+    pub default_checkers: u32,
+    #[serde(default)]
+    pub http_proxy: Option<String>, // e.g. http://proxy.corp:8080; None = inherit from the environment
+    #[serde(default)]
+    pub https_proxy: Option<String>,
+    #[serde(default)]
+    pub no_proxy: Option<String>, // Comma-separated hosts that skip the proxy
+    #[serde(default)]
+    pub bind_address: Option<String>, // rclone --bind: local IP or interface; 0.0.0.0 forces IPv4, ::0 IPv6
+}
+
+fn default_settings_region() -> String {
+    "us-east-1".to_string()
+}
+
+fn default_log_retention_count() -> usize {
+    100
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            default_region: default_settings_region(),
+            preferred_rclone_bin: None,
+            aws_cli_path: None,
+            log_retention_count: default_log_retention_count(),
+            log_retention_days: None,
+            default_transfers: default_transfers(),
+            default_checkers: default_checkers(),
+            http_proxy: None,
+            https_proxy: None,
+            no_proxy: None,
+            bind_address: None,
+        }
+    }
+}
+
+impl AppSettings {
+    /// Apply these defaults to a freshly created profile
+    pub fn apply_profile_defaults(&self, profile: &mut Profile) {
+        profile.transfers = self.default_transfers;
+        profile.checkers = self.default_checkers;
+        if let Some(bin) = &self.preferred_rclone_bin {
+            profile.rclone_bin = bin.clone();
+        }
+    }
+
+    /// Environment variables for the proxy and bind settings. Proxies go in both cases since
+    /// rclone (Go) prefers HTTPS_PROXY and the AWS CLI (Python) https_proxy; rclone reads
+    /// --bind from RCLONE_BIND.
+    pub fn network_env(&self) -> Vec<(&'static str, String)> {
+        let proxies = [
+            (["HTTP_PROXY", "http_proxy"], &self.http_proxy),
+            (["HTTPS_PROXY", "https_proxy"], &self.https_proxy),
+            (["NO_PROXY", "no_proxy"], &self.no_proxy),
+        ];
+
+        let mut env = Vec::new();
+        for (names, value) in proxies {
+            if let Some(value) = value {
+                env.extend(names.map(|name| (name, value.clone())));
+            }
+        }
+        if let Some(bind) = &self.bind_address {
+            env.push(("RCLONE_BIND", bind.clone()));
+        }
+        env
+    }
+}
+
+/// Config file layout version; load_config migrates older files up to this
+pub const CURRENT_SCHEMA_VERSION: u32 = 5;
+
+/// rclone flags with a typed Profile field; transfer_flags drops them from rclone_flags
+const TYPED_VALUE_FLAGS: &[&str] = &["--transfers", "--checkers", "--retries", "--low-level-retries", "--retries-sleep"];
+/// Switches covered by hash_check and symlink_mode, dropped from rclone_flags the same way
+const TYPED_SWITCHES: &[&str] = &["--checksum", "--size-only", "--links", "-l", "--copy-links", "-L"];
+
+/// A backup source and the volume it's on, see get_source_info
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SourceInfo {
+    pub path: String,
+    pub exists: bool,
+    pub mounted: bool,               // false when the external drive it lives on isn't connected
+    pub mount_point: Option<String>, // Drive it lives on, e.g. "/Volumes/USB"; None on the system volume
+    pub total_bytes: Option<u64>,    // Volume sizes; None when the drive isn't mounted
+    pub used_bytes: Option<u64>,
+    pub free_bytes: Option<u64>,     // Available to this user
+}
+
+/// A problem with one field of an imported profile, see validate_profile_json
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProfileFieldError {
+    pub field: String, // e.g. "bucket", "sources[1]" or "schedule.time"; empty for the whole file
+    pub message: String,
+}
+
+impl std::fmt::Display for ProfileFieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.field.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.field, self.message)
+        }
+    }
+}
+
+/// Format version written by export_profile; bump when the export layout changes
+pub const PROFILE_EXPORT_VERSION: u32 = 1;
+
+/// Portable wrapper around a single profile, produced by export_profile
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProfileExport {
+    pub format_version: u32,
+    pub app_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub secrets_included: bool,
+    pub profile: Profile,
+    // The profile's keychain passwords, only exported with its other secrets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crypt_secrets: Option<CryptSecrets>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rclone_config_password: Option<String>,
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         let now = Utc::now();
         Self {
+            schema_version: CURRENT_SCHEMA_VERSION,
             profiles: Vec::new(),
             active_profile_id: None,
             backup_operations: Vec::new(),
+            settings: AppSettings::default(),
             app_version: env!("CARGO_PKG_VERSION").to_string(),
             created_at: now,
             updated_at: now,
@@ -173,6 +757,23 @@ impl Default for AppConfig {
     }
 }
 
+/// Folder in a backup destination that use_backup_dir moves deleted and replaced files into,
+/// one snapshot folder per run named with BACKUP_DIR_STAMP_FORMAT (UTC)
+pub const BACKUP_DIR_FOLDER: &str = "_deleted";
+pub const BACKUP_DIR_STAMP_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+
+/// Append `path` to a remote root such as "aws:bucket/prefix" or "aws-crypt-<id>:"
+pub fn remote_join(base: &str, path: &str) -> String {
+    let path = path.trim_start_matches('/');
+    if path.is_empty() {
+        base.to_string()
+    } else if base.ends_with(':') || base.ends_with('/') {
+        format!("{}{}", base, path)
+    } else {
+        format!("{}/{}", base, path)
+    }
+}
+
 impl Profile {
     pub fn new(name: String, profile_type: ProfileType) -> Self {
         let now = Utc::now();
@@ -191,24 +792,556 @@ impl Profile {
             mode: BackupMode::default(),
             schedule: None,
             rclone_flags: vec![
-                "--checksum".to_string(),
                 "--fast-list".to_string(),
-                "--transfers=8".to_string(),
-                "--checkers=32".to_string(),
             ],
+            include_patterns: Vec::new(),
+            exclude_patterns: Vec::new(),
+            min_size: None,
+            max_size: None,
+            min_age: None,
+            max_age: None,
+            max_parallel_sources: default_max_parallel_sources(),
+            transfer_timeout_secs: default_transfer_timeout_secs(),
+            transfers: default_transfers(),
+            checkers: default_checkers(),
+            retries: default_retries(),
+            low_level_retries: default_low_level_retries(),
+            retries_sleep_secs: 0,
+            hash_check: HashMode::default(),
+            log_level: LogLevel::default(),
+            symlink_mode: SymlinkMode::default(),
+            stats_interval_secs: default_stats_interval_secs(),
+            detailed_stats: false,
+            tags: Vec::new(),
+            encryption_enabled: false,
+            rclone_config_encrypted: false,
+            notifications: NotificationConfig::default(),
+            confirm_sync_deletions: default_confirm_sync_deletions(),
+            sync_delete_threshold: 0,
+            check_write_access: false,
+            use_backup_dir: false,
+            secondary_destinations: Vec::new(),
+            fail_on_secondary_error: false,
             aws_config: None,
             created_at: now,
             updated_at: now,
         }
     }
 
+    /// Where backups go: the crypt remote's root when encryption is on, else remote:bucket/prefix
     pub fn destination(&self) -> String {
+        if self.encryption_enabled {
+            return format!("{}:", self.crypt_remote());
+        }
+        self.storage_path()
+    }
+
+    /// Unencrypted remote:bucket/prefix, which the crypt remote wraps
+    pub fn storage_path(&self) -> String {
         if self.prefix.is_empty() {
             format!("{}:{}", self.remote, self.bucket)
         } else {
             format!("{}:{}/{}", self.remote, self.bucket, self.prefix)
         }
     }
+
+    /// Name of the rclone crypt remote for this profile, e.g. "aws-crypt-<profile id>". Each
+    /// profile wraps its own prefix with its own keys, so profiles sharing a remote can't
+    /// share the crypt remote.
+    pub fn crypt_remote(&self) -> String {
+        format!("{}-crypt-{}", self.remote, self.id)
+    }
+
+    /// Timeout for backup/restore transfers. Duration::MAX disables it (tokio treats it as never).
+    pub fn transfer_timeout(&self) -> std::time::Duration {
+        if self.transfer_timeout_secs == 0 {
+            std::time::Duration::MAX
+        } else {
+            std::time::Duration::from_secs(self.transfer_timeout_secs)
+        }
+    }
+
+    /// Whether a Sync run deleting `count` files must be confirmed first
+    pub fn sync_deletions_need_confirmation(&self, count: usize) -> bool {
+        self.mode == BackupMode::Sync
+            && self.confirm_sync_deletions
+            && count > self.sync_delete_threshold as usize
+    }
+
+    /// Tuning flags from the typed fields followed by the user's custom rclone_flags.
+    /// Any --transfers/--checkers/--checksum/--size-only, retry or symlink flags left in
+    /// rclone_flags are dropped so the typed values win; symlink_mode's own flag comes
+    /// with backup_filter_flags, since it only applies to backups.
+    pub fn transfer_flags(&self) -> Vec<String> {
+        let mut flags = vec![
+            format!("--transfers={}", self.transfers),
+            format!("--checkers={}", self.checkers),
+        ];
+        flags.extend(self.hash_flags());
+        flags.extend(self.retry_flags());
+
+        let mut skip_value = false;
+        for flag in &self.rclone_flags {
+            if skip_value {
+                skip_value = false;
+                continue;
+            }
+            let name = flag.split_once('=').map_or(flag.as_str(), |(name, _)| name);
+            if TYPED_VALUE_FLAGS.contains(&name) {
+                skip_value = name == flag; // "--transfers 8" form, drop the value too
+                continue;
+            }
+            if TYPED_SWITCHES.contains(&flag.as_str()) {
+                continue;
+            }
+            flags.push(flag.clone());
+        }
+
+        flags
+    }
+
+    /// --retries/--low-level-retries/--retries-sleep. rclone retries failed requests
+    /// low_level_retries times, then reruns the whole transfer up to `retries` times in
+    /// total, skipping files that already made it. The app doesn't retry a failed run
+    /// itself, and transfer_timeout_secs covers all attempts together.
+    pub fn retry_flags(&self) -> Vec<String> {
+        let mut flags = vec![
+            format!("--retries={}", self.retries.max(1)),
+            format!("--low-level-retries={}", self.low_level_retries),
+        ];
+        if self.retries_sleep_secs > 0 {
+            flags.push(format!("--retries-sleep={}s", self.retries_sleep_secs));
+        }
+        flags
+    }
+
+    /// The comparison flag for hash_check, if it needs one
+    pub fn hash_flags(&self) -> Vec<String> {
+        self.hash_check.rclone_flag().map(str::to_string).into_iter().collect()
+    }
+
+    /// Progress flags for backups and restores. Both the one-line and the detailed format
+    /// carry the same "x MiB / y MiB, n%" line that progress and final totals are read from.
+    pub fn stats_flags(&self) -> Vec<String> {
+        let mut flags = vec![format!("--stats={}s", self.stats_interval_secs.max(1))];
+        if !self.detailed_stats {
+            flags.push("--stats-one-line".to_string());
+        }
+        flags
+    }
+
+    /// Verbosity flags for log_level
+    pub fn log_flags(&self) -> Vec<String> {
+        self.log_level.rclone_flags().iter().map(|f| f.to_string()).collect()
+    }
+
+    /// Build rclone filter flags from the profile's include/exclude patterns.
+    /// Rclone applies filters in order, so includes are emitted before excludes.
+    pub fn filter_flags(&self) -> Vec<String> {
+        let mut flags = Vec::new();
+
+        for pattern in &self.include_patterns {
+            flags.push("--include".to_string());
+            flags.push(pattern.clone());
+        }
+
+        for pattern in &self.exclude_patterns {
+            flags.push("--exclude".to_string());
+            flags.push(pattern.clone());
+        }
+
+        flags
+    }
+
+    /// --min-size/--max-size for backups, in bytes so rclone can't read a bare number as KiB.
+    /// Errors on a size string that doesn't parse or a minimum above the maximum.
+    pub fn size_flags(&self) -> Result<Vec<String>, String> {
+        let parse = |size: &Option<String>| -> Result<Option<u64>, String> {
+            match size.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+                Some(size) => crate::size::parse_byte_size(size).map(Some),
+                None => Ok(None),
+            }
+        };
+        let min = parse(&self.min_size).map_err(|e| format!("Minimum file size: {}", e))?;
+        let max = parse(&self.max_size).map_err(|e| format!("Maximum file size: {}", e))?;
+
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                return Err("Minimum file size is larger than the maximum".to_string());
+            }
+        }
+
+        let mut flags = Vec::new();
+        if let Some(min) = min {
+            flags.push(format!("--min-size={}B", min));
+        }
+        if let Some(max) = max {
+            flags.push(format!("--max-size={}B", max));
+        }
+        Ok(flags)
+    }
+
+    /// --min-age/--max-age for backups. Like the size filters these only narrow what
+    /// the include/exclude patterns already select, and a file must pass all of them.
+    /// Errors on a malformed age or a window no file can fall into.
+    pub fn age_flags(&self) -> Result<Vec<String>, String> {
+        let parse = |age: &Option<String>| -> Result<Option<(String, Option<f64>)>, String> {
+            match age.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+                Some(age) => parse_rclone_age(age).map(|secs| Some((age.to_string(), secs))),
+                None => Ok(None),
+            }
+        };
+        let min = parse(&self.min_age).map_err(|e| format!("Minimum file age: {}", e))?;
+        let max = parse(&self.max_age).map_err(|e| format!("Maximum file age: {}", e))?;
+
+        // Older than min_age and newer than max_age needs min_age < max_age
+        if let (Some((_, Some(min_secs))), Some((_, Some(max_secs)))) = (&min, &max) {
+            if min_secs >= max_secs {
+                return Err("Minimum file age must be shorter than the maximum age".to_string());
+            }
+        }
+
+        let mut flags = Vec::new();
+        if let Some((min, _)) = min {
+            flags.push(format!("--min-age={}", min));
+        }
+        if let Some((max, _)) = max {
+            flags.push(format!("--max-age={}", max));
+        }
+        Ok(flags)
+    }
+
+    /// How backups read symlinks in the sources. Restores only need --links, for Copy,
+    /// to turn the .rclonelink files back into links; with Follow the backup holds plain
+    /// copies of the targets, so a restore brings back real files instead of links.
+    pub fn symlink_flags(&self) -> Vec<String> {
+        self.symlink_mode.rclone_flag().map(str::to_string).into_iter().collect()
+    }
+
+    /// Size and age filters plus symlink handling, which apply to backups but not
+    /// restores. Source listings use them too, so previews see the same files as the run.
+    pub fn backup_filter_flags(&self) -> Result<Vec<String>, String> {
+        Ok([self.size_flags()?, self.age_flags()?, self.symlink_flags()].concat())
+    }
+}
+
+/// Check an rclone age: a duration like "30d", "1h30m" or "1.5w" (returned in seconds),
+/// or an absolute date/time like "2024-01-31" or "2024-01-31T12:00:00Z" (returned as None)
+pub(crate) fn parse_rclone_age(age: &str) -> Result<Option<f64>, String> {
+    if chrono::NaiveDate::parse_from_str(age, "%Y-%m-%d").is_ok()
+        || chrono::NaiveDateTime::parse_from_str(age, "%Y-%m-%d %H:%M:%S").is_ok()
+        || DateTime::parse_from_rfc3339(age).is_ok()
+    {
+        return Ok(None);
+    }
+
+    let invalid = || format!("'{}' is not a duration like 30d or 1h30m, or a date like 2024-01-31", age);
+    let mut total = 0.0;
+    let mut rest = age;
+    while !rest.is_empty() {
+        let number_len = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+        let number: f64 = rest[..number_len].parse().map_err(|_| invalid())?;
+        rest = &rest[number_len..];
+
+        let unit_len = rest.find(|c: char| c.is_ascii_digit() || c == '.').unwrap_or(rest.len());
+        let seconds = match &rest[..unit_len] {
+            "ms" => 0.001,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            "d" => 86400.0,
+            "w" => 7.0 * 86400.0,
+            "M" => 30.0 * 86400.0,
+            "y" => 365.0 * 86400.0,
+            _ => return Err(invalid()),
+        };
+        rest = &rest[unit_len..];
+        total += number * seconds;
+    }
+    Ok(Some(total))
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ConnectionStatus {
+    Success,
+    AuthFailed,     // 403 - bad, expired or under-privileged credentials
+    BucketNotFound, // 404 - bucket doesn't exist
+    NetworkError,   // DNS, connection or timeout failures
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ConnectionTestResult {
+    pub status: ConnectionStatus,
+    pub message: String,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub warning: Option<String>, // Set when the rclone binary is too old or unidentifiable
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RcloneBinary {
+    pub path: String,
+    pub version: Option<String>, // e.g. "1.66.0", parsed from `rclone version`
+    pub source: String,          // "bundled" or "system"
+    #[serde(default)]
+    pub warning: Option<String>, // Set when the version is below the supported minimum
+}
+
+/// Passwords of a profile's crypt remote, as kept in the OS keychain (not obscured)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CryptSecrets {
+    pub password: String,
+    pub password2: String, // rclone's salt password
+}
+
+/// Outcome of run_scheduled_now: the runner script's own output plus what it logged
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScheduledTestRun {
+    pub success: bool,
+    pub exit_code: i32,
+    pub output: String,      // stdout and stderr of the script
+    pub log_excerpt: String, // Lines appended to backup-{id}.log during this run
+}
+
+/// Result of preview_schedule: what scheduling the profile would write to the system
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SchedulePreview {
+    pub platform: String,               // "macos", "windows" or "linux"
+    pub script_path: String,            // Where the runner script would be written
+    pub script: String,                 // Runner script content
+    pub wrapper_script: Option<String>, // Windows: the VBScript the task starts to hide the PowerShell window
+    pub job_path: Option<String>,       // launchd plist path, or the Task Scheduler task name
+    pub job_definition: Option<String>, // plist XML or schtasks command; None where scheduling isn't supported
+    pub next_run: Option<DateTime<Utc>>,
+}
+
+/// One step of diagnose_profile
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Temporary credentials returned by assume_aws_role
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TemporaryCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: String,
+    pub expiration: String, // ISO 8601, as returned by STS
+    pub profile_name: String, // AWS CLI profile the credentials were stored in
+}
+
+/// Current security settings of a profile's bucket, as reported by get_bucket_info
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BucketInfo {
+    pub bucket_name: String,
+    pub region: String,
+    pub versioning: Option<String>, // "Enabled", "Suspended", or None if never enabled
+    pub encryption: Option<String>, // Default SSE algorithm, e.g. "AES256" or "aws:kms"
+    pub bucket_key_enabled: bool,
+    pub public_access_blocked: bool, // All four public access block settings on
+    pub tls_only_policy: bool,
+    pub lifecycle_rules: Vec<String>, // One readable line per rule
+    pub drift: Vec<String>, // Differences from what setup_aws_infrastructure configures
+}
+
+/// A profile's schedule and latest backup at a glance, from get_profiles_summary
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProfileSummary {
+    pub id: String,
+    pub name: String,
+    pub is_scheduled: bool, // Has a schedule that isn't paused
+    pub next_run: Option<DateTime<Utc>>,
+    pub last_operation_status: Option<OperationStatus>, // Of the most recent backup; restores and previews don't count
+    pub last_run_at: Option<DateTime<Utc>>, // When that backup finished, or started if it's still running
+}
+
+/// Result of rename_prefix
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PrefixRename {
+    pub old_prefix: String,
+    pub new_prefix: String,
+    pub files_moved: u64,
+    pub bytes_moved: u64,
+    pub merged: bool, // The new prefix already had data and the moved files were merged into it
+    pub policy_updated_for: Option<String>, // Employee whose IAM policy now allows the new prefix
+}
+
+/// Result of purge_remote
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PurgeResult {
+    pub location: String, // bucket/prefix that was emptied
+    pub files_deleted: u64,
+    pub bytes_freed: u64,
+    pub all_versions: bool, // Old versions were removed too, not just current files
+}
+
+/// Result of check_config_consistency
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ConfigConsistency {
+    pub remote: String, // The s3 remote compared; the profile's remote, or the one its crypt remote wraps
+    pub config_path: String,
+    pub drift: Vec<String>, // Differences between rclone.conf and the app's stored credentials
+}
+
+/// What setup_aws_infrastructure would do, returned instead of running it when dry_run is set
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AwsSetupPlan {
+    pub bucket_name: String,
+    pub region: String,
+    pub bucket_settings: Vec<String>, // Human-readable list of bucket changes, in the order applied
+    pub bucket_policy: String,
+    pub users: Vec<PlannedIamUser>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PlannedIamUser {
+    pub username: String,
+    pub is_admin: bool,
+    pub policy_name: String,
+    pub policy_document: String,
+}
+
+/// setup_aws_infrastructure result: the created config, or the plan for a dry run
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum AwsSetupResult {
+    Applied(AwsConfig),
+    Planned(AwsSetupPlan),
+}
+
+/// Parsed `rclone version` number; `pre_release` holds suffixes like "beta.7700.abc123"
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RcloneVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub pre_release: Option<String>,
+    pub raw: String,
+    pub supported: bool,
+}
+
+/// Payload of "restore-progress" events; one restore call covers `path_count` remote paths,
+/// in turn or several at once
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RestoreProgress {
+    pub operation_id: String,
+    pub remote_path: String,
+    pub path_index: usize,
+    pub path_count: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub percent: u32,
+    pub speed: Option<String>, // As rclone prints it, e.g. "1.5 MiB/s"
+    pub eta: Option<String>,   // As rclone prints it, e.g. "1m2s"; None when unknown
+}
+
+/// Payload of "backup-started" events, sent by start_backup_with_rc so the UI knows which
+/// operation to poll get_transfer_stats for
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BackupStarted {
+    pub profile_id: String,
+    pub operation_id: String,
+}
+
+/// A backup or restore as it runs, from get_active_operations / get_operation
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LiveOperation {
+    pub operation_id: String,
+    pub profile_id: String,
+    pub operation_type: OperationType,
+    pub status: OperationStatus,
+    pub started_at: DateTime<Utc>,
+    pub elapsed_seconds: i64,
+    pub current_item: Option<String>, // Destination being backed up to, or remote path being restored
+    pub items_done: usize,            // Sources (per destination) or remote paths finished
+    pub items_total: usize,
+    pub files_done: u64,
+    pub bytes_done: u64,
+    pub bytes_total: u64, // 0 while unknown, which it stays for backups
+    pub percent: u32,     // Of the bytes seen so far for restores; 0 for backups
+}
+
+/// Live progress from rclone's rc core/stats, summed over the operation's rclone processes
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct TransferStats {
+    pub operation_id: String,
+    pub bytes: u64,
+    pub total_bytes: u64,
+    pub speed: f64, // Bytes per second
+    pub eta: Option<u64>, // Seconds; None until rclone can estimate it
+    pub transfers: u64, // Files finished
+    pub total_transfers: u64,
+    pub checks: u64,
+    pub errors: u64,
+    pub elapsed_seconds: f64,
+    pub transferring: Vec<TransferringFile>,
+}
+
+/// A file rclone is transferring right now
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TransferringFile {
+    pub name: String,
+    pub size: u64,
+    pub bytes: u64,
+    pub percentage: u32,
+    pub speed: f64, // Bytes per second
+    pub eta: Option<u64>, // Seconds
+}
+
+/// S3 retrieval tier for thawing Glacier/Deep Archive objects; faster tiers cost more
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum GlacierRestoreTier {
+    Expedited,
+    Standard,
+    Bulk,
+}
+
+impl GlacierRestoreTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GlacierRestoreTier::Expedited => "Expedited",
+            GlacierRestoreTier::Standard => "Standard",
+            GlacierRestoreTier::Bulk => "Bulk",
+        }
+    }
+
+    /// AWS's published retrieval times for this tier
+    pub fn estimated_thaw(&self) -> &'static str {
+        match self {
+            GlacierRestoreTier::Expedited => "1-5 minutes for Glacier; not available for Deep Archive",
+            GlacierRestoreTier::Standard => "3-5 hours for Glacier, up to 12 hours for Deep Archive",
+            GlacierRestoreTier::Bulk => "5-12 hours for Glacier, up to 48 hours for Deep Archive",
+        }
+    }
+}
+
+/// Result of request_glacier_restore, counted over every object under the requested paths
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GlacierRestoreRequest {
+    pub tier: GlacierRestoreTier,
+    pub days: u32, // How long S3 keeps the thawed copy
+    pub requested: u64,
+    pub already_in_progress: u64,
+    pub not_archived: u64, // Objects that can be downloaded without a restore
+    pub failed: Vec<String>, // "path: status" for every other failure
+    pub estimated_thaw: String,
+}
+
+/// Thaw state of the archived objects under one path, from check_restore_status
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GlacierRestoreStatus {
+    pub path: String,
+    pub archived_files: u64, // In GLACIER or DEEP_ARCHIVE
+    pub not_requested: u64,
+    pub in_progress: u64,
+    pub restored: u64, // Thawed copy ready to download
+    pub restored_until: Option<String>, // Earliest expiry of the thawed copies
+    pub ready: bool, // Every archived object is restored, so restore_files can download them
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -0,0 +1,119 @@
+use aws_sdk_s3::config::SharedHttpClient;
+use aws_smithy_http_client::{proxy::ProxyConfig, tls, Builder, Connector};
+use std::sync::{Mutex, OnceLock};
+use tokio::process::Command;
+use tracing::warn;
+
+use crate::models::AppSettings;
+
+/// Proxy and bind settings from AppSettings, kept here so rclone and AWS CLI commands and the
+/// in-process HTTP clients can be built without reading config.json. Loaded at startup and
+/// replaced when settings are saved.
+static NETWORK_SETTINGS: OnceLock<Mutex<AppSettings>> = OnceLock::new();
+
+fn network_settings_store() -> &'static Mutex<AppSettings> {
+    NETWORK_SETTINGS.get_or_init(|| Mutex::new(AppSettings::default()))
+}
+
+fn network_settings() -> AppSettings {
+    network_settings_store().lock().map(|settings| settings.clone()).unwrap_or_default()
+}
+
+/// Use the proxy and bind settings of `settings` for processes and clients created from now on
+pub fn set_network_settings(settings: &AppSettings) {
+    if let Ok(mut stored) = network_settings_store().lock() {
+        *stored = settings.clone();
+    }
+}
+
+/// The variables apply_network_env sets, for scripts that start rclone outside the app
+pub fn network_env() -> Vec<(&'static str, String)> {
+    network_settings().network_env()
+}
+
+/// Point a command that talks to S3 at the configured proxy and bind address. Variables
+/// the user didn't set are left as inherited from the app's environment.
+pub fn apply_network_env(command: &mut Command) {
+    for (name, value) in network_env() {
+        command.env(name, value);
+    }
+}
+
+/// The proxy for a ureq request to `url`: the configured one for its scheme, else whatever
+/// the environment says, like rclone and the AWS CLI.
+pub fn ureq_proxy(url: &str) -> Option<ureq::Proxy> {
+    let settings = network_settings();
+    let configured = if url.starts_with("https://") { &settings.https_proxy } else { &settings.http_proxy };
+    let Some(proxy_url) = configured else {
+        return ureq::Proxy::try_from_env();
+    };
+
+    let parsed = match ureq::Proxy::new(proxy_url) {
+        Ok(proxy) => proxy,
+        Err(e) => {
+            warn!("Ignoring invalid proxy {}: {}", proxy_url, e);
+            return None;
+        }
+    };
+    let mut builder = ureq::Proxy::builder(parsed.protocol())
+        .host(parsed.host())
+        .port(parsed.port());
+    if let Some(username) = parsed.username() {
+        builder = builder.username(username);
+    }
+    if let Some(password) = parsed.password() {
+        builder = builder.password(password);
+    }
+    for rule in settings.no_proxy.iter().flat_map(|rules| rules.split(',')) {
+        builder = builder.no_proxy(rule.trim());
+    }
+    builder.build()
+        .map_err(|e| warn!("Ignoring invalid proxy {}: {}", proxy_url, e))
+        .ok()
+}
+
+/// The SDK's proxy setting. S3 and IAM are only reached over HTTPS, so the HTTP proxy is
+/// used only when no HTTPS proxy is set.
+fn sdk_proxy_config(settings: &AppSettings) -> ProxyConfig {
+    let configured = match (&settings.https_proxy, &settings.http_proxy) {
+        (Some(url), _) => ProxyConfig::https(url.as_str()),
+        (None, Some(url)) => ProxyConfig::all(url.as_str()),
+        (None, None) => return ProxyConfig::from_env(),
+    };
+    match configured {
+        Ok(config) => match &settings.no_proxy {
+            Some(rules) => config.no_proxy(rules),
+            None => config,
+        },
+        Err(e) => {
+            warn!("Ignoring invalid proxy for the AWS SDK: {}", e);
+            ProxyConfig::from_env()
+        }
+    }
+}
+
+/// HTTP client for the S3 and IAM SDK clients, going through the configured proxy. The SDK
+/// can't bind to a local IP, so a bind address is only honoured when it names an interface,
+/// and only on Linux.
+pub fn sdk_http_client() -> SharedHttpClient {
+    let settings = network_settings();
+    let proxy_config = sdk_proxy_config(&settings);
+    #[cfg(target_os = "linux")]
+    let interface = settings.bind_address
+        .filter(|bind| bind.parse::<std::net::IpAddr>().is_err());
+
+    Builder::new().build_with_connector_fn(move |connector_settings, components| {
+        let mut builder = Connector::builder().proxy_config(proxy_config.clone());
+        builder.set_connector_settings(connector_settings.cloned());
+        if let Some(components) = components {
+            builder.set_sleep_impl(components.sleep_impl());
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(interface) = &interface {
+            builder.set_interface(interface.clone());
+        }
+        builder
+            .tls_provider(tls::Provider::Rustls(tls::rustls_provider::CryptoMode::AwsLc))
+            .build()
+    })
+}
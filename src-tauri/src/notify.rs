@@ -0,0 +1,101 @@
+use std::time::Duration;
+use serde_json::json;
+use tauri::command;
+use tracing::{debug, warn};
+
+use crate::config::load_config;
+use crate::models::*;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// JSON body for a finished operation. "text" (Slack) and "content" (Discord) carry a
+/// readable summary; the remaining fields are for generic receivers.
+fn webhook_payload(profile: &Profile, operation: &BackupOperation) -> serde_json::Value {
+    let text = match operation.status {
+        OperationStatus::Completed => format!(
+            "Backup of '{}' completed: {} files, {} bytes",
+            profile.name, operation.files_transferred, operation.bytes_transferred
+        ),
+        OperationStatus::PartialSuccess => format!(
+            "Backup of '{}' completed with errors: {} files, {} bytes. {}",
+            profile.name,
+            operation.files_transferred,
+            operation.bytes_transferred,
+            operation.error_message.as_deref().and_then(|e| e.lines().next()).unwrap_or("")
+        ),
+        _ => format!(
+            "Backup of '{}' failed: {}",
+            profile.name,
+            operation.error_message.as_deref().unwrap_or("unknown error")
+        ),
+    };
+
+    json!({
+        "text": text,
+        "content": text,
+        "profile_id": profile.id,
+        "profile_name": profile.name,
+        "operation_id": operation.id,
+        "status": operation.status,
+        "started_at": operation.started_at,
+        "completed_at": operation.completed_at,
+        "files_transferred": operation.files_transferred,
+        "bytes_transferred": operation.bytes_transferred,
+        "error_message": operation.error_message,
+        "scheduled": false,
+    })
+}
+
+async fn post_webhook(url: &str, payload: serde_json::Value) -> Result<(), String> {
+    let url = url.to_string();
+    tokio::task::spawn_blocking(move || {
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .timeout_global(Some(WEBHOOK_TIMEOUT))
+            .proxy(crate::network::ureq_proxy(&url))
+            .build()
+            .into();
+        agent.post(&url)
+            .send_json(&payload)
+            .map(|_| ())
+            .map_err(|e| format!("Webhook request failed: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Report a finished backup to the profile's webhook. Failures are logged, never returned,
+/// so a broken webhook can't fail the backup itself.
+pub async fn notify_backup_result(profile: &Profile, operation: &BackupOperation) {
+    let Some(url) = profile.notifications.webhook() else {
+        return;
+    };
+    if matches!(operation.status, OperationStatus::Completed) && !profile.notifications.notify_on_success {
+        return;
+    }
+
+    match post_webhook(url, webhook_payload(profile, operation)).await {
+        Ok(()) => debug!("Sent backup notification for profile {}", profile.id),
+        Err(e) => warn!("Backup notification for profile {} failed: {}", profile.id, e),
+    }
+}
+
+/// Send a sample notification so the webhook can be checked from settings
+#[command]
+pub async fn send_test_notification(profile_id: String) -> Result<(), String> {
+    let config = load_config().await?;
+    let profile = config.profiles.into_iter()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    let url = profile.notifications.webhook()
+        .ok_or("No webhook URL configured for this profile")?;
+
+    let text = format!("Test notification for backup profile '{}'", profile.name);
+    post_webhook(url, json!({
+        "text": text,
+        "content": text,
+        "profile_id": profile.id,
+        "profile_name": profile.name,
+        "test": true,
+    }))
+    .await
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use chrono::Utc;
+use tauri::command;
+
+use crate::models::{BackupOperation, LiveOperation, OperationStatus};
+use crate::process::shutdown_requested;
+
+/// Backups and restores running in this process, by operation id. Unlike the history in
+/// config.json this is updated while the run progresses and forgets it once it's done.
+static LIVE_OPERATIONS: OnceLock<Mutex<HashMap<String, LiveOperation>>> = OnceLock::new();
+
+fn live_operations() -> &'static Mutex<HashMap<String, LiveOperation>> {
+    LIVE_OPERATIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Keeps an operation in the live store; dropping it removes the entry on every return path
+pub struct LiveOperationGuard {
+    operation_id: String,
+}
+
+impl Drop for LiveOperationGuard {
+    fn drop(&mut self) {
+        if let Ok(mut live) = live_operations().lock() {
+            live.remove(&self.operation_id);
+        }
+    }
+}
+
+/// Start tracking a run from its Running placeholder; `items_total` is how many sources
+/// (times destinations) or remote paths it will work through
+pub fn track_operation(operation: &BackupOperation, items_total: usize) -> LiveOperationGuard {
+    let live = LiveOperation {
+        operation_id: operation.id.clone(),
+        profile_id: operation.profile_id.clone(),
+        operation_type: operation.operation_type.clone(),
+        status: OperationStatus::Running,
+        started_at: operation.started_at,
+        elapsed_seconds: 0,
+        current_item: None,
+        items_done: 0,
+        items_total,
+        files_done: 0,
+        bytes_done: 0,
+        bytes_total: 0,
+        percent: 0,
+    };
+    if let Ok(mut operations) = live_operations().lock() {
+        operations.insert(operation.id.clone(), live);
+    }
+    LiveOperationGuard {
+        operation_id: operation.id.clone(),
+    }
+}
+
+/// Apply `update` to a tracked operation; does nothing once it's no longer tracked
+pub fn update_operation(operation_id: &str, update: impl FnOnce(&mut LiveOperation)) {
+    if let Ok(mut operations) = live_operations().lock() {
+        if let Some(live) = operations.get_mut(operation_id) {
+            update(live);
+        }
+    }
+}
+
+fn snapshot(live: &LiveOperation) -> LiveOperation {
+    let mut live = live.clone();
+    live.elapsed_seconds = (Utc::now() - live.started_at).num_seconds().max(0);
+    if shutdown_requested() {
+        live.status = OperationStatus::Cancelled;
+    }
+    live
+}
+
+/// Backups and restores running right now, oldest first
+#[command]
+pub async fn get_active_operations() -> Result<Vec<LiveOperation>, String> {
+    let operations = live_operations().lock().map_err(|e| e.to_string())?;
+    let mut active: Vec<LiveOperation> = operations.values().map(snapshot).collect();
+    active.sort_by_key(|live| live.started_at);
+    Ok(active)
+}
+
+/// Current state of one operation: live progress while it runs, and its saved result
+/// afterwards. None if the id is unknown.
+#[command]
+pub async fn get_operation(operation_id: String) -> Result<Option<LiveOperation>, String> {
+    if let Some(live) = live_operations().lock().map_err(|e| e.to_string())?.get(&operation_id) {
+        return Ok(Some(snapshot(live)));
+    }
+
+    let config = crate::config::load_config().await?;
+    Ok(config.backup_operations.iter()
+        .find(|op| op.id == operation_id)
+        .map(|op| LiveOperation {
+            operation_id: op.id.clone(),
+            profile_id: op.profile_id.clone(),
+            operation_type: op.operation_type.clone(),
+            status: op.status.clone(),
+            started_at: op.started_at,
+            elapsed_seconds: (op.completed_at.unwrap_or_else(Utc::now) - op.started_at).num_seconds().max(0),
+            current_item: None,
+            items_done: 0,
+            items_total: 0,
+            files_done: op.files_transferred,
+            bytes_done: op.bytes_transferred,
+            bytes_total: op.bytes_transferred,
+            percent: if matches!(op.status, OperationStatus::Completed) { 100 } else { 0 },
+        }))
+}
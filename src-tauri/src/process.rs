@@ -0,0 +1,54 @@
+use std::process::Output;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::sync::watch;
+
+/// Timeout for quick probes like `rclone version` and `aws sts get-caller-identity`
+pub const QUICK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Timeout for listings, dry runs and other metadata calls
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// For bulk deletes and moves that would be left half done if cut off; tokio treats it as
+/// never, and quitting the app still stops them
+pub const NO_TIMEOUT: Duration = Duration::MAX;
+
+/// Error for commands stopped by request_shutdown
+pub const SHUTDOWN_MESSAGE: &str = "Cancelled because the app is quitting";
+
+static SHUTDOWN: OnceLock<watch::Sender<bool>> = OnceLock::new();
+
+fn shutdown_sender() -> &'static watch::Sender<bool> {
+    SHUTDOWN.get_or_init(|| watch::channel(false).0)
+}
+
+/// Stop every running child process: in-flight commands fail with SHUTDOWN_MESSAGE
+/// and the child is killed as it is dropped
+pub fn request_shutdown() {
+    shutdown_sender().send_replace(true);
+}
+
+pub fn shutdown_requested() -> bool {
+    *shutdown_sender().borrow()
+}
+
+/// Resolves once request_shutdown has been called
+pub async fn wait_for_shutdown() {
+    let mut receiver = shutdown_sender().subscribe();
+    let _ = receiver.wait_for(|requested| *requested).await;
+}
+
+/// Run a command to completion, killing the child if it doesn't finish within `timeout`
+/// or the app is quitting
+pub async fn run_with_timeout(command: &mut Command, timeout: Duration) -> Result<Output, String> {
+    command.kill_on_drop(true);
+
+    tokio::select! {
+        result = tokio::time::timeout(timeout, command.output()) => match result {
+            Ok(result) => result.map_err(|e| e.to_string()),
+            Err(_) => Err(format!("Operation timed out after {} seconds", timeout.as_secs())),
+        },
+        _ = wait_for_shutdown() => Err(SHUTDOWN_MESSAGE.to_string()),
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use serde::Deserialize;
+use tauri::command;
+use tracing::debug;
+
+use crate::models::{RcloneOutput, TransferStats, TransferringFile};
+
+/// core/stats is answered from memory, so anything slower means rclone is busy or gone
+const STATS_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// rc addresses of the rclone processes running for each operation; a backup runs one
+/// process per source, so an operation can have several
+static RC_ENDPOINTS: OnceLock<Mutex<HashMap<String, Vec<String>>>> = OnceLock::new();
+
+fn rc_endpoints() -> &'static Mutex<HashMap<String, Vec<String>>> {
+    RC_ENDPOINTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// A local rc port registered for one rclone process; dropping it unregisters the port
+pub struct RcEndpoint {
+    operation_id: String,
+    addr: String,
+}
+
+impl RcEndpoint {
+    /// Flags that make rclone serve the rc API on this endpoint. core/stats needs no
+    /// auth, so the other rc methods stay locked.
+    pub fn flags(&self) -> Vec<String> {
+        vec!["--rc".to_string(), format!("--rc-addr={}", self.addr)]
+    }
+
+    /// Live progress of this endpoint's rclone process alone
+    pub async fn stats(&self) -> Result<TransferStats, String> {
+        let mut stats = TransferStats {
+            operation_id: self.operation_id.clone(),
+            ..Default::default()
+        };
+        add_core_stats(&mut stats, fetch_core_stats(&self.addr).await?);
+        Ok(stats)
+    }
+}
+
+impl Drop for RcEndpoint {
+    fn drop(&mut self) {
+        if let Ok(mut endpoints) = rc_endpoints().lock() {
+            if let Some(addrs) = endpoints.get_mut(&self.operation_id) {
+                addrs.retain(|addr| addr != &self.addr);
+                if addrs.is_empty() {
+                    endpoints.remove(&self.operation_id);
+                }
+            }
+        }
+    }
+}
+
+/// How many ports a run tries for its rc server before going on without one
+pub const RC_PORT_ATTEMPTS: usize = 3;
+
+/// Pick a free localhost port for an rclone process of `operation_id`. The port is only
+/// free when checked; if something else grabs it first, rclone exits before transferring
+/// anything (see rc_start_failed), and the caller retries with a new endpoint.
+pub fn register_rc_endpoint(operation_id: &str) -> Result<RcEndpoint, String> {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map_err(|e| format!("No free port for the rclone rc server: {}", e))?
+        .port();
+    let addr = format!("127.0.0.1:{}", port);
+
+    rc_endpoints().lock()
+        .map_err(|e| e.to_string())?
+        .entry(operation_id.to_string())
+        .or_default()
+        .push(addr.clone());
+
+    Ok(RcEndpoint {
+        operation_id: operation_id.to_string(),
+        addr,
+    })
+}
+
+/// Whether rclone exited because its rc server couldn't start, e.g. the port was taken
+pub fn rc_start_failed(output: &RcloneOutput) -> bool {
+    !output.success && output.stderr.contains("Failed to start remote control")
+}
+
+/// The parts of rclone's core/stats response we show
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct RcCoreStats {
+    bytes: u64,
+    total_bytes: u64,
+    speed: f64,
+    eta: Option<u64>,
+    transfers: u64,
+    total_transfers: u64,
+    checks: u64,
+    errors: u64,
+    elapsed_time: f64,
+    transferring: Vec<RcTransferring>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RcTransferring {
+    name: String,
+    size: u64,
+    bytes: u64,
+    percentage: u32,
+    speed: f64,
+    eta: Option<u64>,
+}
+
+async fn fetch_core_stats(addr: &str) -> Result<RcCoreStats, String> {
+    let url = format!("http://{}/core/stats", addr);
+    tokio::task::spawn_blocking(move || {
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .timeout_global(Some(STATS_TIMEOUT))
+            // rc listens on loopback; never send it through the configured or environment proxy
+            .proxy(None)
+            .build()
+            .into();
+        agent.post(&url)
+            .send_json(serde_json::json!({}))
+            .map_err(|e| format!("rc request to {} failed: {}", url, e))?
+            .body_mut()
+            .read_json::<RcCoreStats>()
+            .map_err(|e| format!("Invalid core/stats response from {}: {}", url, e))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Add one process's core/stats to `stats`
+fn add_core_stats(stats: &mut TransferStats, core: RcCoreStats) {
+    stats.bytes += core.bytes;
+    stats.total_bytes += core.total_bytes;
+    stats.speed += core.speed;
+    stats.transfers += core.transfers;
+    stats.total_transfers += core.total_transfers;
+    stats.checks += core.checks;
+    stats.errors += core.errors;
+    stats.elapsed_seconds = stats.elapsed_seconds.max(core.elapsed_time);
+    // Processes run in parallel, so the operation finishes with the slowest one
+    stats.eta = stats.eta.max(core.eta);
+    stats.transferring.extend(core.transferring.into_iter().map(|file| TransferringFile {
+        name: file.name,
+        size: file.size,
+        bytes: file.bytes,
+        percentage: file.percentage,
+        speed: file.speed,
+        eta: file.eta,
+    }));
+}
+
+/// Live progress of an operation started with rc enabled (see start_backup_with_rc),
+/// summed over its rclone processes. None once the operation has finished, or if it
+/// wasn't started with rc.
+#[command]
+pub async fn get_transfer_stats(operation_id: String) -> Result<Option<TransferStats>, String> {
+    let addrs = match rc_endpoints().lock().map_err(|e| e.to_string())?.get(&operation_id) {
+        Some(addrs) => addrs.clone(),
+        None => return Ok(None),
+    };
+
+    let mut stats = TransferStats {
+        operation_id,
+        ..Default::default()
+    };
+    for addr in &addrs {
+        // A process that is still starting up isn't listening yet; it's counted next poll
+        let core = match fetch_core_stats(addr).await {
+            Ok(core) => core,
+            Err(e) => {
+                debug!("Skipping rc endpoint {}: {}", addr, e);
+                continue;
+            }
+        };
+
+        add_core_stats(&mut stats, core);
+    }
+
+    Ok(Some(stats))
+}
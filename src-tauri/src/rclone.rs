@@ -1,16 +1,35 @@
+use std::collections::{HashMap, VecDeque};
 use std::process::Stdio;
 use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use serde_json::Value;
-use tauri::command;
+use tauri::{command, AppHandle, Emitter};
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::process::Command;
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinHandle;
 use chrono::{DateTime, Utc};
+use tracing::{trace, debug, info, warn, error};
 
 use crate::models::*;
+use crate::size::{format_byte_size, parse_byte_size};
 use crate::binary_resolver::get_rclone_binary_path;
-
-/// Create a Command with Windows-specific flags to hide console window
-fn create_command(program: &str) -> Command {
+use crate::process::{run_with_timeout, shutdown_requested, wait_for_shutdown, DEFAULT_TIMEOUT, NO_TIMEOUT, QUICK_TIMEOUT, SHUTDOWN_MESSAGE};
+use crate::run_lock::acquire_profile_run;
+use crate::notify::notify_backup_result;
+use crate::error::{BackupError, ARCHIVED_OBJECT_MARKERS};
+use crate::crypt::{is_encrypted_rclone_config, load_config_password};
+use crate::rc::{rc_start_failed, register_rc_endpoint, RC_PORT_ATTEMPTS};
+use crate::operations::{track_operation, update_operation};
+use crate::network::apply_network_env;
+
+/// Create a Command with Windows-specific flags to hide console window, and the proxy
+/// and bind settings in its environment
+pub(crate) fn create_command(program: &str) -> Command {
     let mut cmd = Command::new(program);
+    apply_network_env(&mut cmd);
 
     #[cfg(target_os = "windows")]
     {
@@ -23,19 +42,33 @@ fn create_command(program: &str) -> Command {
     cmd
 }
 
+/// A Command for rclone that never prompts, with the profile's config password (if its
+/// rclone.conf is encrypted) in RCLONE_CONFIG_PASS. Without the prompt an encrypted config
+/// with no password fails right away instead of waiting on stdin.
+pub(crate) fn rclone_command(rclone_binary: &str, profile: &Profile) -> Result<Command, String> {
+    let mut cmd = create_command(rclone_binary);
+    cmd.env("RCLONE_ASK_PASSWORD", "false");
+    if profile.rclone_config_encrypted {
+        let password = load_config_password(&profile.id)?
+            .ok_or("rclone.conf is encrypted but no config password is stored for this profile")?;
+        cmd.env("RCLONE_CONFIG_PASS", password);
+    }
+    Ok(cmd)
+}
+
 /// Resolve rclone binary path - use bundled or system rclone
-fn resolve_rclone_binary(profile_rclone_bin: &str) -> Result<String, String> {
+pub(crate) fn resolve_rclone_binary(profile_rclone_bin: &str) -> Result<String, String> {
     // If profile wants bundled or system detection
     if profile_rclone_bin == "bundled" || profile_rclone_bin.contains("bundled") {
         // Use the sidecar function to get the correct path
         match get_rclone_binary_path() {
             Ok(bundled_path) => {
                 let path_str = bundled_path.to_string_lossy().to_string();
-                println!("[DEBUG] Resolved rclone binary to: {}", path_str);
+                debug!("Resolved rclone binary to: {}", path_str);
                 return Ok(path_str);
             }
             Err(e) => {
-                eprintln!("[ERROR] Failed to find rclone binary: {}", e);
+                error!("Failed to find rclone binary: {}", e);
                 return Err(format!("Failed to find rclone binary: {}. Please ensure rclone is bundled with the app or installed via Homebrew.", e));
             }
         }
@@ -45,31 +78,192 @@ fn resolve_rclone_binary(profile_rclone_bin: &str) -> Result<String, String> {
     Ok(profile_rclone_bin.to_string())
 }
 
-#[command]
-pub async fn detect_rclone() -> Result<Vec<String>, String> {
-    let mut candidates = Vec::new();
-    
-    // First try bundled/system rclone
-    if let Ok(rclone_path) = get_rclone_binary_path() {
-        let path_str = rclone_path.to_string_lossy().to_string();
-        if let Ok(output) = create_command(&path_str)
-            .arg("version")
+/// Run `rclone <subcommand> <extra_args> --config <profile config>` with the profile's binary.
+/// A non-zero exit is reported through RcloneOutput::success rather than as an Err;
+/// Err means rclone couldn't be started or hit the timeout.
+pub(crate) async fn run_rclone(profile: &Profile, subcommand: &str, extra_args: &[String], timeout: Duration) -> Result<RcloneOutput, String> {
+    let rclone_binary = resolve_rclone_binary(&profile.rclone_bin)?;
+
+    let mut args = vec![subcommand.to_string()];
+    args.extend(extra_args.iter().cloned());
+    args.push("--config".to_string());
+    args.push(profile.rclone_conf.clone());
+
+    let output = run_with_timeout(
+        rclone_command(&rclone_binary, profile)?
+            .args(&args)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await
-        {
-            if output.status.success() {
-                let label = if path_str.contains("Resources/binaries") {
-                    "bundled"
+            .stderr(Stdio::piped()),
+        timeout,
+    )
+    .await?;
+
+    Ok(RcloneOutput {
+        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        success: output.status.success(),
+        exit_code: output.status.code().unwrap_or(-1),
+    })
+}
+
+/// Like run_rclone, serving rc on a port registered for `operation_id` so
+/// get_transfer_stats can follow it. If rclone can't listen on the port (something took it
+/// after it was picked), the run is retried on a fresh port and finally without rc.
+async fn run_rclone_with_rc(
+    profile: &Profile,
+    subcommand: &str,
+    extra_args: &[String],
+    timeout: Duration,
+    operation_id: &str,
+) -> Result<RcloneOutput, String> {
+    for _ in 0..RC_PORT_ATTEMPTS {
+        let endpoint = match register_rc_endpoint(operation_id) {
+            Ok(endpoint) => endpoint,
+            Err(e) => {
+                warn!("Running without live progress: {}", e);
+                break;
+            }
+        };
+        let mut args = extra_args.to_vec();
+        args.extend(endpoint.flags());
+
+        let output = run_rclone(profile, subcommand, &args, timeout).await?;
+        if !rc_start_failed(&output) {
+            return Ok(output);
+        }
+        warn!("rclone could not serve rc on its port, retrying: {}", output.stderr.trim());
+    }
+    run_rclone(profile, subcommand, extra_args, timeout).await
+}
+
+/// Like run_rclone, but hands each output line to `on_line` as rclone prints it.
+/// Lines are split on '\r' as well as '\n' so --progress updates arrive one by one.
+async fn run_rclone_streaming<F: FnMut(&str)>(
+    profile: &Profile,
+    subcommand: &str,
+    extra_args: &[String],
+    timeout: Duration,
+    mut on_line: F,
+) -> Result<RcloneOutput, String> {
+    let rclone_binary = resolve_rclone_binary(&profile.rclone_bin)?;
+
+    let mut args = vec![subcommand.to_string()];
+    args.extend(extra_args.iter().cloned());
+    args.push("--config".to_string());
+    args.push(profile.rclone_conf.clone());
+
+    let mut child = rclone_command(&rclone_binary, profile)?
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to start rclone: {}", e))?;
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    spawn_line_reader(child.stdout.take(), false, tx.clone());
+    spawn_line_reader(child.stderr.take(), true, tx);
+
+    let collect = async {
+        let mut stdout = String::new();
+        let mut stderr = String::new();
+        while let Some((is_stderr, line)) = rx.recv().await {
+            on_line(&line);
+            let buffer = if is_stderr { &mut stderr } else { &mut stdout };
+            buffer.push_str(&line);
+            buffer.push('\n');
+        }
+
+        let status = child.wait().await.map_err(|e| e.to_string())?;
+        Ok(RcloneOutput {
+            stdout,
+            stderr,
+            success: status.success(),
+            exit_code: status.code().unwrap_or(-1),
+        })
+    };
+
+    // On timeout or quit the child is dropped, and kill_on_drop stops it
+    tokio::select! {
+        result = tokio::time::timeout(timeout, collect) => match result {
+            Ok(result) => result,
+            Err(_) => Err(format!("Operation timed out after {} seconds", timeout.as_secs())),
+        },
+        _ = wait_for_shutdown() => Err(SHUTDOWN_MESSAGE.to_string()),
+    }
+}
+
+fn spawn_line_reader<R: AsyncRead + Unpin + Send + 'static>(
+    reader: Option<R>,
+    is_stderr: bool,
+    tx: mpsc::UnboundedSender<(bool, String)>,
+) {
+    let Some(mut reader) = reader else { return };
+
+    tokio::spawn(async move {
+        let mut chunk = [0u8; 4096];
+        let mut pending = Vec::new();
+        loop {
+            let n = match reader.read(&mut chunk).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            for &byte in &chunk[..n] {
+                if byte == b'\n' || byte == b'\r' {
+                    if !pending.is_empty() {
+                        let _ = tx.send((is_stderr, String::from_utf8_lossy(&pending).to_string()));
+                        pending.clear();
+                    }
                 } else {
-                    "system"
-                };
-                candidates.push(format!("{} ({})", path_str, label));
+                    pending.push(byte);
+                }
             }
         }
+        if !pending.is_empty() {
+            let _ = tx.send((is_stderr, String::from_utf8_lossy(&pending).to_string()));
+        }
+    });
+}
+
+/// Bytes done, bytes total, percent, speed and ETA from one rclone stats line
+type ProgressLine = (u64, u64, u32, Option<String>, Option<String>);
+
+/// Parse an rclone stats line ("1.2 MiB / 2.4 MiB, 50%, 1.5 MiB/s, ETA 1s") into
+/// (bytes done, bytes total, percent, speed, eta). Log prefixes and ANSI codes before it are ignored.
+fn parse_progress_line(line: &str) -> Option<ProgressLine> {
+    use regex::Regex;
+    use std::sync::OnceLock;
+
+    static PROGRESS_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+    let regex = PROGRESS_REGEX.get_or_init(|| {
+        Regex::new(r"([0-9.,]+\s*[KMGTP]?i?B)\s*/\s*([0-9.,]+\s*[KMGTP]?i?B)\s*,\s*(\d+)%(?:\s*,\s*([0-9.,]+\s*[KMGTP]?i?B/s))?(?:\s*,\s*ETA\s*(\S+))?").ok()
+    }).as_ref()?;
+
+    let caps = regex.captures(line)?;
+    let bytes_done = parse_byte_size(&caps[1]).ok()?;
+    let bytes_total = parse_byte_size(&caps[2]).ok()?;
+    let percent = caps[3].parse().ok()?;
+    let speed = caps.get(4).map(|m| m.as_str().to_string());
+    let eta = caps.get(5).map(|m| m.as_str().to_string()).filter(|eta| eta != "-");
+
+    Some((bytes_done, bytes_total, percent, speed, eta))
+}
+
+#[command]
+pub async fn detect_rclone() -> Result<Vec<RcloneBinary>, BackupError> {
+    let mut probes: Vec<(String, &str)> = Vec::new();
+
+    // First try bundled/system rclone
+    if let Ok(rclone_path) = get_rclone_binary_path() {
+        let path_str = rclone_path.to_string_lossy().to_string();
+        let source = if path_str.contains("Resources/binaries") || path_str.contains("binaries/rclone-") {
+            "bundled"
+        } else {
+            "system"
+        };
+        probes.push((path_str, source));
     }
-    
+
     // Then try common system locations for rclone
     let common_paths = vec![
         "/usr/local/bin/rclone",
@@ -77,220 +271,1529 @@ pub async fn detect_rclone() -> Result<Vec<String>, String> {
         "/usr/bin/rclone",
         "rclone", // In PATH
     ];
-
     for path in common_paths {
-        if let Ok(output) = create_command(path)
-            .arg("version")
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await
+        probes.push((path.to_string(), "system"));
+    }
+
+    let mut candidates: Vec<RcloneBinary> = Vec::new();
+    let mut seen: Vec<std::path::PathBuf> = Vec::new();
+
+    for (path, source) in probes {
+        // Resolve symlinks and bare names so the same binary is only listed once
+        let resolved = match find_in_path(&path) {
+            Some(resolved) => resolved,
+            None => continue,
+        };
+        let canonical = std::fs::canonicalize(&resolved).unwrap_or_else(|_| resolved.clone());
+        if seen.contains(&canonical) {
+            debug!("Skipping duplicate rclone candidate: {} -> {:?}", path, canonical);
+            continue;
+        }
+
+        let resolved_str = resolved.to_string_lossy().to_string();
+        if let Ok(output) = run_with_timeout(
+            create_command(&resolved_str)
+                .arg("version")
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()),
+            QUICK_TIMEOUT,
+        )
+        .await
         {
             if output.status.success() {
-                candidates.push(format!("{} (system)", path));
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let version = parse_rclone_version(&stdout);
+                let warning = version_warning(version.as_deref().and_then(parse_semver).as_ref());
+                seen.push(canonical);
+                candidates.push(RcloneBinary {
+                    path: resolved_str,
+                    version,
+                    source: source.to_string(),
+                    warning,
+                });
             }
         }
     }
 
-    // If no rclone found at all, indicate bundled one should work
-    if candidates.is_empty() {
-        if get_rclone_binary_path().is_ok() {
-            candidates.push("bundled rclone available".to_string());
-        } else {
-            candidates.push("rclone (not found - will need to install or bundle)".to_string());
+    debug!("Detected {} rclone binaries", candidates.len());
+    Ok(candidates)
+}
+
+/// Resolve a binary path, searching PATH for bare names like "rclone"
+fn find_in_path(path: &str) -> Option<std::path::PathBuf> {
+    let candidate = Path::new(path);
+    if candidate.components().count() > 1 {
+        return if candidate.exists() { Some(candidate.to_path_buf()) } else { None };
+    }
+
+    let path_var = std::env::var_os("PATH")?;
+    for dir in std::env::split_paths(&path_var) {
+        let full = dir.join(path);
+        if full.is_file() {
+            return Some(full);
+        }
+        #[cfg(target_os = "windows")]
+        {
+            let exe = full.with_extension("exe");
+            if exe.is_file() {
+                return Some(exe);
+            }
         }
     }
+    None
+}
 
-    Ok(candidates)
+/// Extract the version number from the first line of `rclone version` output ("rclone v1.66.0")
+fn parse_rclone_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .next()?
+        .split_whitespace()
+        .find_map(|word| word.strip_prefix('v'))
+        .filter(|version| version.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|version| version.to_string())
+}
+
+/// Oldest rclone that has everything we use; --s3-versions arrived in 1.59
+const MIN_RCLONE_VERSION: (u32, u32, u32) = (1, 59, 0);
+
+/// Parse a version like "1.66.0", "v1.66.0-beta.7700.abc123" or "1.65.0-DEV" into components.
+/// Missing minor/patch numbers count as 0.
+fn parse_semver(version: &str) -> Option<RcloneVersion> {
+    let raw = version.trim();
+    let trimmed = raw.strip_prefix('v').unwrap_or(raw);
+    let (core, pre_release) = match trimmed.split_once(['-', '+']) {
+        Some((core, suffix)) => (core, Some(suffix.to_string()).filter(|s| !s.is_empty())),
+        None => (trimmed, None),
+    };
+
+    let mut parts = core.split('.').map(|part| {
+        let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+        digits.parse::<u32>().ok()
+    });
+    let major = parts.next().flatten()?;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+
+    Some(RcloneVersion {
+        major,
+        minor,
+        patch,
+        pre_release,
+        raw: raw.to_string(),
+        supported: (major, minor, patch) >= MIN_RCLONE_VERSION,
+    })
+}
+
+fn version_warning(version: Option<&RcloneVersion>) -> Option<String> {
+    let (major, minor, patch) = MIN_RCLONE_VERSION;
+    match version {
+        None => Some("Could not determine the rclone version; some features may not work".to_string()),
+        Some(v) if !v.supported => Some(format!(
+            "rclone v{}.{}.{} is older than the minimum supported v{}.{}.{}; file version restore and JSON logs may not work",
+            v.major, v.minor, v.patch, major, minor, patch
+        )),
+        Some(_) => None,
+    }
+}
+
+async fn probe_rclone_version(rclone_binary: &str) -> Result<Option<RcloneVersion>, String> {
+    let output = run_with_timeout(
+        create_command(rclone_binary)
+            .arg("version")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped()),
+        QUICK_TIMEOUT,
+    )
+    .await
+    .map_err(|e| format!("Failed to run {} version: {}", rclone_binary, e))?;
+
+    if !output.status.success() {
+        return Err(format!("{} version failed: {}", rclone_binary, String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_rclone_version(&stdout).as_deref().and_then(parse_semver))
 }
 
+/// Parsed version of the given binary, or the bundled one when none is given
 #[command]
-pub async fn validate_rclone_config(rclone_bin: String, config_path: String) -> Result<bool, String> {
+pub async fn get_rclone_version(rclone_bin: Option<String>) -> Result<RcloneVersion, BackupError> {
+    let rclone_binary = resolve_rclone_binary(rclone_bin.as_deref().unwrap_or("bundled"))?;
+    probe_rclone_version(&rclone_binary)
+        .await?
+        .ok_or_else(|| BackupError::Parse(format!("Could not parse the version reported by {}", rclone_binary)))
+}
+
+/// Check that rclone can read `config_path`. An encrypted config is unlocked with
+/// `config_password`, or else the one stored for `profile_id`; without either it is
+/// reported as needing a password rather than as invalid.
+#[command]
+pub async fn validate_rclone_config(
+    rclone_bin: String,
+    config_path: String,
+    profile_id: Option<String>,
+    config_password: Option<String>,
+) -> Result<bool, BackupError> {
     if !Path::new(&config_path).exists() {
         return Ok(false);
     }
 
-    let output = create_command(&rclone_bin)
-        .args(&["config", "show", "--config", &config_path])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .await
-        .map_err(|e| e.to_string())?;
+    let mut command = create_command(&rclone_bin);
+    command.env("RCLONE_ASK_PASSWORD", "false");
+
+    let contents = std::fs::read_to_string(&config_path).unwrap_or_default();
+    if is_encrypted_rclone_config(&contents) {
+        let password = match config_password.filter(|p| !p.is_empty()) {
+            Some(password) => Some(password),
+            None => profile_id.as_deref().map(load_config_password).transpose()?.flatten(),
+        };
+        let password = password.ok_or_else(|| BackupError::ConfigInvalid(
+            "This rclone config is encrypted. Enter its config password to use it.".to_string()
+        ))?;
+        command.env("RCLONE_CONFIG_PASS", password);
+    }
+
+    let output = run_with_timeout(
+        command
+            .args(["config", "show", "--config", &config_path])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped()),
+        QUICK_TIMEOUT,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
 
     Ok(output.status.success())
 }
 
+/// Remotes defined in `config_path`, so a profile can point at one the user set up
+/// themselves. An encrypted config is unlocked with the password stored for `profile_id`.
 #[command]
-pub async fn list_cloud_files(profile: Profile, path: Option<String>, max_depth: Option<u32>) -> Result<Vec<CloudFile>, String> {
-    // Admin Access Model:
-    // - Admins can BROWSE/VIEW entire bucket (read access)
-    // - Admins can BACKUP only to their own prefix: admins/{user-id}/ (write access restricted)
-    // For admin users, show all files in the bucket (not restricted to their prefix)
-    // For regular users, restrict to their prefix
-    let base_target = if matches!(profile.profile_type, crate::models::ProfileType::Admin) {
-        // Admin sees the entire bucket
-        format!("{}:{}", profile.remote, profile.bucket)
-    } else {
-        // Regular users see only their prefix
-        profile.destination()
-    };
-    
-    let target = if let Some(subpath) = path {
-        format!("{}/{}", base_target, subpath.trim_start_matches('/'))
+pub async fn list_rclone_remotes(
+    rclone_bin: String,
+    config_path: String,
+    profile_id: Option<String>,
+) -> Result<Vec<RcloneRemote>, BackupError> {
+    let rclone_binary = resolve_rclone_binary(&rclone_bin)?;
+    let config_path = if config_path.trim().is_empty() {
+        crate::config::get_config_dir()?.join("rclone.conf").to_string_lossy().to_string()
     } else {
-        base_target
+        config_path
     };
+    if !Path::new(&config_path).exists() {
+        return Ok(Vec::new());
+    }
 
-    let mut args = vec![
-        "lsjson".to_string(),
-        target,
-        "--fast-list".to_string(),
-        "--config".to_string(),
-        profile.rclone_conf.clone(),
-    ];
+    let mut command = create_command(&rclone_binary);
+    command.env("RCLONE_ASK_PASSWORD", "false");
 
-    if let Some(depth) = max_depth {
-        args.push("--max-depth".to_string());
-        args.push(depth.to_string());
-    } else {
-        args.push("--recursive".to_string());
+    let contents = std::fs::read_to_string(&config_path).unwrap_or_default();
+    if is_encrypted_rclone_config(&contents) {
+        let password = profile_id.as_deref().map(load_config_password).transpose()?.flatten()
+            .ok_or_else(|| BackupError::ConfigInvalid(
+                "This rclone config is encrypted. Enter its config password to list its remotes.".to_string()
+            ))?;
+        command.env("RCLONE_CONFIG_PASS", password);
     }
 
-    let rclone_binary = resolve_rclone_binary(&profile.rclone_bin)?;
-    let output = create_command(&rclone_binary)
-        .args(&args)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .await
-        .map_err(|e| e.to_string())?;
+    let output = run_with_timeout(
+        command
+            .args(["listremotes", "--long", "--config", &config_path])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped()),
+        QUICK_TIMEOUT,
+    )
+    .await
+    .map_err(|e| format!("Failed to run {} listremotes: {}", rclone_binary, e))?;
 
     if !output.status.success() {
-        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+        return Err(format!("rclone listremotes failed: {}", String::from_utf8_lossy(&output.stderr).trim()).into());
     }
 
-    let json_output = String::from_utf8_lossy(&output.stdout);
-    let items: Vec<Value> = serde_json::from_str(&json_output)
-        .map_err(|e| format!("Failed to parse rclone output: {}", e))?;
+    let remotes = parse_listremotes(&String::from_utf8_lossy(&output.stdout));
+    debug!("list_rclone_remotes - {} remotes in {}", remotes.len(), config_path);
+    Ok(remotes)
+}
 
-    let mut files = Vec::new();
-    for item in items {
-        if let Some(file) = parse_rclone_item(&item)? {
-            files.push(file);
+/// `rclone listremotes --long` prints one "name:   type" line per remote
+fn parse_listremotes(stdout: &str) -> Vec<RcloneRemote> {
+    stdout.lines()
+        .filter_map(|line| {
+            let (name, remote_type) = line.trim().split_once(':')?;
+            (!name.is_empty()).then(|| RcloneRemote {
+                name: name.to_string(),
+                remote_type: remote_type.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Check rclone_flags one at a time against `rclone version`, which accepts every global
+/// flag and touches nothing. Returns the flags rclone rejected; empty means all are valid.
+#[command]
+pub async fn validate_rclone_flags(rclone_bin: String, flags: Vec<String>) -> Result<Vec<InvalidRcloneFlag>, BackupError> {
+    let rclone_binary = resolve_rclone_binary(&rclone_bin)?;
+    let mut invalid = Vec::new();
+
+    for group in group_rclone_flags(&flags) {
+        let output = run_with_timeout(
+            create_command(&rclone_binary)
+                .arg("version")
+                .args(&group)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()),
+            QUICK_TIMEOUT,
+        )
+        .await
+        .map_err(|e| format!("Failed to run {} version: {}", rclone_binary, e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            invalid.push(InvalidRcloneFlag {
+                flag: group.join(" "),
+                message: flag_error_message(&stderr),
+            });
         }
     }
 
-    files.sort_by(|a, b| {
-        if a.is_dir != b.is_dir {
-            b.is_dir.cmp(&a.is_dir) // Directories first
-        } else {
-            a.name.cmp(&b.name)
+    debug!("validate_rclone_flags - {} of {} flags rejected", invalid.len(), flags.len());
+    Ok(invalid)
+}
+
+/// Split profile flags into what rclone parses as one flag: "--bwlimit=10M" alone,
+/// or "--transfers" followed by its value "8" as a separate entry
+fn group_rclone_flags(flags: &[String]) -> Vec<Vec<String>> {
+    let mut groups: Vec<Vec<String>> = Vec::new();
+    for flag in flags.iter().map(|f| f.trim()).filter(|f| !f.is_empty()) {
+        match groups.last_mut() {
+            Some(group) if !flag.starts_with('-') && group.len() == 1 && !group[0].contains('=') => {
+                group.push(flag.to_string());
+            }
+            _ => groups.push(vec![flag.to_string()]),
         }
-    });
+    }
+    groups
+}
 
-    Ok(files)
+/// The "Error: unknown flag: --fast-lst" line out of rclone's usage dump
+fn flag_error_message(stderr: &str) -> String {
+    let lines = || stderr.lines().map(str::trim).filter(|l| !l.is_empty());
+    lines()
+        .find_map(|l| l.strip_prefix("Error: "))
+        .or_else(|| lines().next())
+        .unwrap_or("rclone rejected this flag")
+        .to_string()
 }
 
-fn parse_rclone_item(item: &Value) -> Result<Option<CloudFile>, String> {
-    let obj = item.as_object().ok_or("Invalid rclone item format")?;
-    
-    let path = obj.get("Path")
-        .and_then(|v| v.as_str())
-        .ok_or("Missing Path in rclone item")?
-        .to_string();
+async fn load_profile(profile_id: &str) -> Result<Profile, String> {
+    let config = crate::config::load_config().await?;
+    config.profiles.into_iter()
+        .find(|p| p.id == profile_id)
+        .ok_or_else(|| "Profile not found".to_string())
+}
 
-    let name = obj.get("Name")
-        .and_then(|v| v.as_str())
-        .unwrap_or(&path)
-        .to_string();
+/// The profile's rclone.conf, falling back to the app's default location when unset
+fn rclone_config_path(profile: &Profile) -> Result<std::path::PathBuf, String> {
+    if profile.rclone_conf.trim().is_empty() {
+        Ok(crate::config::get_config_dir()?.join("rclone.conf"))
+    } else {
+        Ok(std::path::PathBuf::from(&profile.rclone_conf))
+    }
+}
 
-    let size = obj.get("Size")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(0);
+#[command]
+pub async fn get_rclone_config_path(profile_id: String) -> Result<String, BackupError> {
+    let profile = load_profile(&profile_id).await?;
+    Ok(rclone_config_path(&profile)?.to_string_lossy().to_string())
+}
 
-    let is_dir = obj.get("IsDir")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
+/// Show `path` selected in Finder/Explorer/the Linux file manager
+#[command]
+pub async fn reveal_in_file_manager(path: String) -> Result<(), BackupError> {
+    if !Path::new(&path).exists() {
+        return Err(BackupError::Io(format!("Path not found: {}", path)));
+    }
+    tauri_plugin_opener::reveal_item_in_dir(&path)
+        .map_err(|e| format!("Failed to open file manager: {}", e).into())
+}
 
-    let mod_time = obj.get("ModTime")
-        .and_then(|v| v.as_str())
-        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
-        .map(|dt| dt.with_timezone(&Utc))
-        .unwrap_or_else(Utc::now);
+#[command]
+pub async fn read_rclone_config(profile_id: String) -> Result<String, BackupError> {
+    let profile = load_profile(&profile_id).await?;
+    let path = rclone_config_path(&profile)?;
+    std::fs::read_to_string(&path)
+        .map_err(|e| BackupError::Io(format!("Failed to read {}: {}", path.display(), e)))
+}
 
-    let mime_type = obj.get("MimeType")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+/// Replace the profile's rclone.conf. The new contents are checked with `rclone config show`
+/// in a temp file first, and must still define the profile's remote.
+#[command]
+pub async fn write_rclone_config(profile_id: String, contents: String) -> Result<(), BackupError> {
+    let profile = load_profile(&profile_id).await?;
+    let path = rclone_config_path(&profile)?;
+    let temp_path = path.with_extension("conf.tmp");
+
+    // The file holds credentials, so it is never readable by others, not even briefly
+    crate::config::write_private_file(&temp_path, &contents)
+        .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+
+    if let Err(e) = check_rclone_config_file(&profile, &temp_path).await {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(BackupError::ConfigInvalid(e));
+    }
 
-    Ok(Some(CloudFile {
-        path,
-        name,
-        size,
-        mod_time,
-        is_dir,
-        mime_type,
-    }))
+    if path.exists() {
+        let backup_path = path.with_extension("conf.bak");
+        std::fs::copy(&path, &backup_path)
+            .map_err(|e| format!("Failed to back up {}: {}", path.display(), e))?;
+    }
+    std::fs::rename(&temp_path, &path)
+        .map_err(|e| format!("Failed to replace {}: {}", path.display(), e))?;
+
+    debug!("Updated rclone config at {}", path.display());
+    Ok(())
 }
 
-#[command]
-pub async fn backup_preview(profile: Profile) -> Result<BackupPreview, String> {
-    let operation = match profile.mode {
-        BackupMode::Copy => "copy",
-        BackupMode::Sync => "sync",
-    };
+async fn check_rclone_config_file(profile: &Profile, config_path: &Path) -> Result<(), String> {
+    let rclone_binary = resolve_rclone_binary(&profile.rclone_bin)?;
+    let config_path = config_path.to_string_lossy().to_string();
 
-    // All users (including admins) backup to their own designated folder
-    // Admins backup to: admins/{user-id}/
-    // Regular users backup to: users/{user-id}/
-    let destination = profile.destination();
-    let mut all_changes = Vec::new();
+    let output = run_with_timeout(
+        rclone_command(&rclone_binary, profile)?
+            .args(["config", "show", "--config", &config_path])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped()),
+        QUICK_TIMEOUT,
+    )
+    .await?;
 
-    for source in &profile.sources {
-        // Extract the folder name from the source path to preserve folder structure
-        // E.g., /Users/john/Documents -> Documents
-        let source_folder_name = Path::new(source)
-            .file_name()
-            .and_then(|name| name.to_str())
-            .ok_or_else(|| format!("Invalid source path: {}", source))?;
+    if !output.status.success() {
+        return Err(format!("rclone rejected the config: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
 
-        // Append the source folder name to the destination to isolate each source
-        // E.g., aws:bucket/users/john-id/Documents
-        let destination_with_folder = format!("{}/{}", destination, source_folder_name);
+    let section = format!("[{}]", profile.remote);
+    if !String::from_utf8_lossy(&output.stdout).lines().any(|line| line.trim() == section) {
+        return Err(format!("The config does not define the remote {} used by this profile", section));
+    }
 
-        let mut args = vec![
-            operation.to_string(),
-            source.clone(),
-            destination_with_folder.clone(),
-            "--dry-run".to_string(),
-            "--stats=0".to_string(),
-            "--config".to_string(),
-            profile.rclone_conf.clone(),
-        ];
+    Ok(())
+}
 
-        // Add custom flags
-        for flag in &profile.rclone_flags {
-            args.push(flag.clone());
-        }
+/// Compare the S3 remote rclone actually uses (read with `rclone config dump`) against the
+/// credentials the app has stored for the profile, so keys rotated or hand-edited on one
+/// side only show up before a backup fails or writes with the wrong account
+#[command]
+pub async fn check_config_consistency(profile_id: String) -> Result<ConfigConsistency, BackupError> {
+    let profile = load_profile(&profile_id).await?;
+    let aws_config = profile.aws_config.as_ref()
+        .ok_or_else(|| BackupError::ConfigInvalid("Profile does not have stored AWS credentials to compare".to_string()))?;
+    let config_path = rclone_config_path(&profile)?.to_string_lossy().to_string();
+    let rclone_binary = resolve_rclone_binary(&profile.rclone_bin)?;
 
-        let rclone_binary = resolve_rclone_binary(&profile.rclone_bin)?;
-        let output = create_command(&rclone_binary)
-            .args(&args)
+    let output = run_with_timeout(
+        rclone_command(&rclone_binary, &profile)?
+            .args(["config", "dump", "--config", &config_path])
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await
-            .map_err(|e| e.to_string())?;
+            .stderr(Stdio::piped()),
+        QUICK_TIMEOUT,
+    )
+    .await?;
 
-        let output_text = String::from_utf8_lossy(&output.stderr);
-        let changes = parse_dry_run_output(&output_text)?;
-        all_changes.extend(changes);
+    if !output.status.success() {
+        return Err(BackupError::ConfigInvalid(format!(
+            "rclone could not read {}: {}",
+            config_path,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
     }
 
-    let files_to_copy: Vec<FileChange> = all_changes.iter()
-        .filter(|c| matches!(c.action, ChangeAction::Copy))
-        .cloned()
-        .collect();
+    let dump: Value = serde_json::from_slice(&output.stdout)?;
+    let (remote, stanza) = resolve_s3_remote(&dump, &profile.remote)?;
+    let drift = config_drift(aws_config, stanza);
+
+    debug!("check_config_consistency - remote {} has {} difference(s)", remote, drift.len());
+    Ok(ConfigConsistency {
+        remote,
+        config_path,
+        drift,
+    })
+}
+
+/// Find the s3 stanza behind `remote` in `rclone config dump` output, following crypt
+/// remotes (whose `remote` option is e.g. "s3remote:bucket/prefix") down to their backend
+fn resolve_s3_remote<'a>(dump: &'a Value, remote: &str) -> Result<(String, &'a serde_json::Map<String, Value>), String> {
+    let mut name = remote.to_string();
+    // A few hops is plenty; more means the config loops
+    for _ in 0..4 {
+        let stanza = dump.get(&name)
+            .and_then(|v| v.as_object())
+            .ok_or_else(|| format!("rclone.conf does not define the remote [{}]", name))?;
+
+        match stanza.get("type").and_then(|v| v.as_str()) {
+            Some("s3") => return Ok((name, stanza)),
+            Some("crypt") => {
+                let wrapped = stanza.get("remote").and_then(|v| v.as_str()).unwrap_or("");
+                name = wrapped.split(':').next().unwrap_or("").to_string();
+            }
+            other => return Err(format!("Remote [{}] is of type {}, not s3", name, other.unwrap_or("unknown"))),
+        }
+    }
+    Err(format!("Could not find the s3 remote behind [{}]", remote))
+}
+
+/// Differences between the stored AwsConfig and an s3 stanza. The secret key is only
+/// reported as different, never shown.
+fn config_drift(aws_config: &AwsConfig, stanza: &serde_json::Map<String, Value>) -> Vec<String> {
+    let option = |key: &str| stanza.get(key).and_then(|v| v.as_str()).unwrap_or("").trim().to_string();
+    let mut drift = Vec::new();
+
+    let access_key_id = option("access_key_id");
+    if access_key_id.is_empty() {
+        if option("env_auth") == "true" {
+            drift.push("rclone.conf takes credentials from the environment instead of the app's access key".to_string());
+        } else {
+            drift.push("rclone.conf has no access key".to_string());
+        }
+    } else if access_key_id != aws_config.aws_access_key_id {
+        drift.push(format!(
+            "Access key in rclone.conf is {}, the app has {}",
+            access_key_id, aws_config.aws_access_key_id
+        ));
+    } else if option("secret_access_key") != aws_config.aws_secret_access_key {
+        drift.push("Secret key in rclone.conf does not match the app's".to_string());
+    }
+
+    // rclone treats an empty region as us-east-1
+    let region = option("region");
+    let region = if region.is_empty() { "us-east-1".to_string() } else { region };
+    if region != aws_config.aws_region {
+        drift.push(format!("Region in rclone.conf is {}, the app has {}", region, aws_config.aws_region));
+    }
+
+    let provider = option("provider");
+    if !provider.is_empty() && !provider.eq_ignore_ascii_case(&aws_config.provider) {
+        drift.push(format!("Provider in rclone.conf is {}, the app has {}", provider, aws_config.provider));
+    }
+
+    let endpoint = option("endpoint");
+    let stored_endpoint = aws_config.endpoint.as_deref().unwrap_or("").trim();
+    if endpoint.trim_end_matches('/') != stored_endpoint.trim_end_matches('/') {
+        let show = |e: &str| if e.is_empty() { "not set".to_string() } else { e.to_string() };
+        drift.push(format!("Endpoint in rclone.conf is {}, the app has {}", show(&endpoint), show(stored_endpoint)));
+    }
+
+    drift
+}
+
+/// Check that the profile's remote is reachable with working credentials
+#[command]
+pub async fn test_connection(profile: Profile) -> Result<ConnectionTestResult, BackupError> {
+    let started = std::time::Instant::now();
+
+    let args = vec![
+        "lsd".to_string(),
+        profile.destination(),
+        "--config".to_string(),
+        profile.rclone_conf.clone(),
+        "--contimeout=10s".to_string(),
+        "--timeout=20s".to_string(),
+        "--retries=1".to_string(),
+        "--low-level-retries=1".to_string(),
+    ];
+
+    let rclone_binary = resolve_rclone_binary(&profile.rclone_bin)?;
+    let warning = match probe_rclone_version(&rclone_binary).await {
+        Ok(version) => version_warning(version.as_ref()),
+        Err(e) => Some(e),
+    };
+
+    let mut command = rclone_command(&rclone_binary, &profile)?;
+    command.args(&args).stdout(Stdio::piped()).stderr(Stdio::piped());
+    let result = run_with_timeout(&mut command, QUICK_TIMEOUT).await;
+
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    let output = match result {
+        Ok(output) => output,
+        Err(e) if e.contains("timed out") => {
+            return Ok(ConnectionTestResult {
+                status: ConnectionStatus::NetworkError,
+                message: format!("Connection test timed out after {} seconds", QUICK_TIMEOUT.as_secs()),
+                duration_ms,
+                warning,
+            });
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    if output.status.success() {
+        return Ok(ConnectionTestResult {
+            status: ConnectionStatus::Success,
+            message: format!("Connected to {}", profile.destination()),
+            duration_ms,
+            warning,
+        });
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+    // The bucket answered but the prefix has nothing in it yet, e.g. before the first backup
+    if stderr.contains("directory not found") && !stderr.contains("NoSuchBucket") && !stderr.contains("404") {
+        return Ok(ConnectionTestResult {
+            status: ConnectionStatus::Success,
+            message: format!("Connected to {} (nothing backed up there yet)", profile.destination()),
+            duration_ms,
+            warning,
+        });
+    }
+
+    Ok(ConnectionTestResult {
+        status: classify_connection_error(&stderr),
+        message: stderr,
+        duration_ms,
+        warning,
+    })
+}
+
+/// Upload an empty marker file to the profile's destination and delete it again, to catch
+/// credentials that can list the bucket but not write to it. Fails with rclone's error,
+/// e.g. AccessDenied, for whichever step was refused.
+#[command]
+pub async fn test_write_access(profile: Profile) -> Result<(), BackupError> {
+    check_write_access(&profile).await.map_err(BackupError::from)
+}
+
+async fn check_write_access(profile: &Profile) -> Result<(), String> {
+    let destination = profile.destination();
+    let marker = remote_join(&destination, &format!(".lavoz-write-test-{}", uuid::Uuid::new_v4()));
+    // One attempt is enough: a refused write won't be allowed on the next try either
+    let args = [marker.clone(), "--retries=1".to_string(), "--low-level-retries=1".to_string()];
+    let rclone_error = |stderr: &str| {
+        rclone_error_lines(stderr).into_iter().next().unwrap_or_else(|| stderr.trim().to_string())
+    };
+
+    let output = run_rclone(profile, "touch", &args, QUICK_TIMEOUT).await?;
+    if !output.success {
+        return Err(format!("Can't write to {}: {}", destination, rclone_error(&output.stderr)));
+    }
+
+    let output = run_rclone(profile, "deletefile", &args, QUICK_TIMEOUT).await?;
+    if !output.success {
+        return Err(format!(
+            "Wrote to {} but can't delete from it, so Sync runs and snapshots will fail (test file left at {}): {}",
+            destination, marker, rclone_error(&output.stderr)
+        ));
+    }
+
+    debug!("check_write_access - wrote and deleted {}", marker);
+    Ok(())
+}
+
+/// Run every setup check for a profile in order and report each one, so problems
+/// can be fixed together instead of one failed backup at a time
+#[command]
+pub async fn diagnose_profile(profile_id: String) -> Result<Vec<DiagnosticCheck>, BackupError> {
+    let profile = load_profile(&profile_id).await?;
+    let mut checks = Vec::new();
+    let mut check = |name: &str, passed: bool, detail: String| {
+        checks.push(DiagnosticCheck { name: name.to_string(), passed, detail });
+        passed
+    };
+
+    let binary_ok = match resolve_rclone_binary(&profile.rclone_bin) {
+        Ok(binary) => match probe_rclone_version(&binary).await {
+            Ok(version) => {
+                let warning = version_warning(version.as_ref());
+                let detail = match (&version, &warning) {
+                    (_, Some(warning)) => format!("{}: {}", binary, warning),
+                    (Some(v), None) => format!("{} (v{}.{}.{})", binary, v.major, v.minor, v.patch),
+                    (None, None) => binary.clone(),
+                };
+                check("rclone binary", warning.is_none(), detail);
+                true
+            }
+            Err(e) => check("rclone binary", false, e),
+        },
+        Err(e) => check("rclone binary", false, e),
+    };
+
+    let config_ok = match rclone_config_path(&profile) {
+        Ok(path) if !path.exists() => check("rclone config", false, format!("Config file not found: {}", path.display())),
+        Ok(path) if !binary_ok => check("rclone config", false, format!("{} exists but can't be checked without rclone", path.display())),
+        Ok(path) => match check_rclone_config_file(&profile, &path).await {
+            Ok(()) => check("rclone config", true, format!("{} defines remote [{}]", path.display(), profile.remote)),
+            Err(e) => check("rclone config", false, e),
+        },
+        Err(e) => check("rclone config", false, e),
+    };
+
+    if binary_ok && config_ok {
+        let result = test_connection(profile.clone()).await?;
+        let reachable = !matches!(result.status, ConnectionStatus::NetworkError | ConnectionStatus::Unknown);
+        let remote_detail = if reachable {
+            format!("Reached {} in {} ms", profile.remote, result.duration_ms)
+        } else {
+            result.message.trim().to_string()
+        };
+        check("Remote reachable", reachable, remote_detail);
+
+        let bucket_detail = match result.status {
+            ConnectionStatus::Success => format!("Listed {}", profile.destination()),
+            ConnectionStatus::AuthFailed => format!("Access denied to {}: {}", profile.destination(), result.message.trim()),
+            ConnectionStatus::BucketNotFound => format!("Bucket or folder not found: {}", profile.destination()),
+            _ => "Skipped: remote not reachable".to_string(),
+        };
+        let bucket_ok = check("Bucket accessible", matches!(result.status, ConnectionStatus::Success), bucket_detail);
+
+        if !bucket_ok {
+            check("Write access", false, "Skipped: bucket not accessible".to_string());
+        } else {
+            match check_write_access(&profile).await {
+                Ok(()) => check("Write access", true, format!("Wrote and deleted a test file in {}", profile.destination())),
+                Err(e) => check("Write access", false, e),
+            };
+        }
+    } else {
+        check("Remote reachable", false, "Skipped: fix rclone and its config first".to_string());
+        check("Bucket accessible", false, "Skipped: fix rclone and its config first".to_string());
+        check("Write access", false, "Skipped: fix rclone and its config first".to_string());
+    }
+
+    let problems = crate::config::source_path_problems(&profile.sources);
+    if profile.sources.is_empty() {
+        check("Backup sources", false, "No backup sources configured".to_string());
+    } else if problems.is_empty() {
+        check("Backup sources", true, format!("{} source(s) readable", profile.sources.len()));
+    } else {
+        check("Backup sources", false, problems.join("; "));
+    }
+
+    match profile.schedule.as_ref().filter(|s| s.enabled) {
+        None => {
+            check("Scheduled job", true, "No schedule enabled".to_string());
+        }
+        Some(_) => {
+            let jobs = crate::schedule::list_os_schedules().await.unwrap_or_default();
+            let installed = jobs.iter().any(|job| job.profile_id == profile.id && job.kind != "script" && !job.orphaned);
+            let detail = if installed {
+                "OS scheduler job is installed".to_string()
+            } else if cfg!(target_os = "linux") {
+                "Scheduled backups aren't supported on Linux yet".to_string()
+            } else {
+                "Schedule is enabled but no OS scheduler job was found; re-save the schedule".to_string()
+            };
+            check("Scheduled job", installed, detail);
+        }
+    }
+
+    Ok(checks)
+}
+
+fn classify_connection_error(stderr: &str) -> ConnectionStatus {
+    let auth_markers = ["403", "AccessDenied", "InvalidAccessKeyId", "SignatureDoesNotMatch", "ExpiredToken", "InvalidToken"];
+    let not_found_markers = ["404", "NoSuchBucket", "directory not found"];
+    let network_markers = ["no such host", "connection refused", "dial tcp", "i/o timeout", "TLS handshake timeout", "network is unreachable"];
+
+    if auth_markers.iter().any(|m| stderr.contains(m)) {
+        ConnectionStatus::AuthFailed
+    } else if not_found_markers.iter().any(|m| stderr.contains(m)) {
+        ConnectionStatus::BucketNotFound
+    } else if network_markers.iter().any(|m| stderr.contains(m)) {
+        ConnectionStatus::NetworkError
+    } else {
+        ConnectionStatus::Unknown
+    }
+}
+
+/// List `path` (default: the browse root). Only the immediate children are listed unless
+/// `max_depth` is given or `recursive` is set; recursive listings of big buckets are slow.
+#[command]
+pub async fn list_cloud_files(profile: Profile, path: Option<String>, max_depth: Option<u32>, recursive: Option<bool>) -> Result<Vec<CloudFile>, BackupError> {
+    // Admin Access Model:
+    // - Admins can BROWSE/VIEW entire bucket (read access)
+    // - Admins can BACKUP only to their own prefix: admins/{user-id}/ (write access restricted)
+    // For admin users, show all files in the bucket (not restricted to their prefix)
+    // For regular users, restrict to their prefix
+    let base_target = restore_base(&profile);
+    
+    let target = if let Some(subpath) = path {
+        remote_join(&base_target, &subpath)
+    } else {
+        base_target
+    };
+
+    let args = listing_args(target, max_depth, recursive.unwrap_or(false));
+    let output = run_rclone(&profile, "lsjson", &args, DEFAULT_TIMEOUT).await?;
+
+    if !output.success {
+        return Err(output.stderr.into());
+    }
+
+    let items: Vec<Value> = serde_json::from_str(&output.stdout)
+        .map_err(|e| format!("Failed to parse rclone output: {}", e))?;
+
+    let mut files = Vec::new();
+    for item in items {
+        if let Some(file) = parse_rclone_item(&item)? {
+            files.push(file);
+        }
+    }
+
+    sort_cloud_files(&mut files);
+    Ok(files)
+}
+
+/// Files per "cloud-files-batch" event while a listing streams in
+const LISTING_BATCH_SIZE: usize = 500;
+
+/// Listings kept for paging; one per open cloud browser window is plenty
+const LISTING_CACHE_SIZE: usize = 8;
+
+/// A sorted listing and the profile id, remote path and recursion it was listed for
+type CachedListing = (String, Arc<Vec<CloudFile>>);
+
+/// The most recent sorted listings, oldest first, so list_cloud_files_page can serve later
+/// pages without listing again while other targets are browsed in between
+static LISTING_CACHE: OnceLock<Mutex<VecDeque<CachedListing>>> = OnceLock::new();
+
+fn listing_cache() -> &'static Mutex<VecDeque<CachedListing>> {
+    LISTING_CACHE.get_or_init(|| Mutex::new(VecDeque::new()))
+}
+
+/// One page of a directory listing. Page 0 lists the directory afresh and emits
+/// "cloud-files-batch" events as rclone reports entries, so huge flat prefixes show up
+/// incrementally; later pages come from that listing.
+#[command]
+pub async fn list_cloud_files_page(
+    app: AppHandle,
+    profile: Profile,
+    path: Option<String>,
+    page: Option<usize>,
+    page_size: Option<usize>,
+    recursive: Option<bool>,
+) -> Result<CloudFilesPage, BackupError> {
+    let page = page.unwrap_or(0);
+    let page_size = page_size.unwrap_or(LISTING_BATCH_SIZE).max(1);
+    let recursive = recursive.unwrap_or(false);
+    let path = path.unwrap_or_default();
+    let target = remote_join(&restore_base(&profile), &path);
+    let cache_key = format!("{}|{}|{}", profile.id, target, recursive);
+
+    let cached = if page == 0 {
+        None
+    } else {
+        listing_cache().lock().ok()
+            .and_then(|cache| cache.iter().find(|(key, _)| *key == cache_key).map(|(_, files)| files.clone()))
+    };
+
+    let files = match cached {
+        Some(files) => files,
+        None => {
+            let files = Arc::new(stream_listing(&app, &profile, &path, target, recursive).await?);
+            if let Ok(mut cache) = listing_cache().lock() {
+                cache.retain(|(key, _)| *key != cache_key);
+                if cache.len() >= LISTING_CACHE_SIZE {
+                    cache.pop_front();
+                }
+                cache.push_back((cache_key, files.clone()));
+            }
+            files
+        }
+    };
+
+    let total_count = files.len();
+    let start = page.saturating_mul(page_size).min(total_count);
+    let end = start.saturating_add(page_size).min(total_count);
+
+    Ok(CloudFilesPage {
+        files: files[start..end].to_vec(),
+        page,
+        page_size,
+        total_count,
+        has_more: end < total_count,
+    })
+}
+
+/// Run lsjson for one directory listing, emitting "cloud-files-batch" events as it goes
+async fn stream_listing(app: &AppHandle, profile: &Profile, path: &str, target: String, recursive: bool) -> Result<Vec<CloudFile>, String> {
+    let args = listing_args(target, None, recursive);
+    let mut files = stream_lsjson(profile, &args, |files, listed| {
+        let _ = app.emit("cloud-files-batch", &CloudFilesBatch {
+            path: path.to_string(),
+            files,
+            listed,
+        });
+    }).await?;
+
+    debug!("stream_listing - {} entries under '{}'", files.len(), path);
+    sort_cloud_files(&mut files);
+    Ok(files)
+}
+
+/// Run lsjson, handing entries to `on_batch` in batches as rclone prints them (one JSON
+/// object per line) along with the running total. Returns every entry, unsorted.
+async fn stream_lsjson(
+    profile: &Profile,
+    args: &[String],
+    mut on_batch: impl FnMut(Vec<CloudFile>, usize),
+) -> Result<Vec<CloudFile>, String> {
+    let mut files = Vec::new();
+    let mut batch = Vec::new();
+
+    let output = run_rclone_streaming(profile, "lsjson", args, DEFAULT_TIMEOUT, |line| {
+        let entry = line.trim().trim_end_matches(',');
+        if !entry.starts_with('{') {
+            return;
+        }
+        let file = serde_json::from_str::<Value>(entry)
+            .ok()
+            .and_then(|item| parse_rclone_item(&item).ok().flatten());
+        if let Some(file) = file {
+            batch.push(file);
+            if batch.len() >= LISTING_BATCH_SIZE {
+                files.extend(batch.iter().cloned());
+                on_batch(std::mem::take(&mut batch), files.len());
+            }
+        }
+    }).await?;
+
+    if !output.success {
+        return Err(output.stderr);
+    }
+    if !batch.is_empty() {
+        files.extend(batch.iter().cloned());
+        on_batch(batch, files.len());
+    }
+    Ok(files)
+}
+
+/// Find files under `path` (default: the browse root) whose name matches `pattern`,
+/// ignoring case. A pattern with glob characters (`*.pdf`, `report-202?.xlsx`) is used
+/// as an rclone include rule; anything else matches names containing it. rclone does the
+/// filtering, and matches are emitted as "cloud-search-results" events while it runs.
+/// Paths are relative to `path`, like a listing of that folder.
+#[command]
+pub async fn search_cloud_files(app: AppHandle, profile: Profile, pattern: String, path: Option<String>) -> Result<Vec<CloudFile>, BackupError> {
+    let pattern = pattern.trim().to_string();
+    if pattern.is_empty() {
+        return Err("Enter a file name or pattern to search for".into());
+    }
+
+    // Same prefix restriction as list_cloud_files
+    let target = remote_join(&restore_base(&profile), path.as_deref().unwrap_or(""));
+    let args = vec![
+        target.clone(),
+        "--recursive".to_string(),
+        "--files-only".to_string(),
+        "--fast-list".to_string(),
+        "--ignore-case".to_string(),
+        "--include".to_string(),
+        search_include_pattern(&pattern),
+    ];
+
+    debug!("search_cloud_files - '{}' under {}", pattern, target);
+    let mut files = stream_lsjson(&profile, &args, |files, found| {
+        let _ = app.emit("cloud-search-results", &CloudSearchBatch {
+            pattern: pattern.clone(),
+            files,
+            found,
+        });
+    }).await?;
+
+    debug!("search_cloud_files - {} match(es)", files.len());
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// The --include rule for a search: glob patterns as typed, plain text as a substring of
+/// the file name (glob characters can't appear in it, but a backslash still needs escaping)
+fn search_include_pattern(pattern: &str) -> String {
+    if pattern.contains(['*', '?', '[', '{']) {
+        pattern.to_string()
+    } else {
+        format!("*{}*", pattern.replace('\\', "\\\\"))
+    }
+}
+
+fn listing_args(target: String, max_depth: Option<u32>, recursive: bool) -> Vec<String> {
+    let mut args = vec![target];
+    if let Some(depth) = max_depth {
+        args.push("--max-depth".to_string());
+        args.push(depth.to_string());
+    } else if recursive {
+        // --fast-list reads the whole prefix in one go, which only pays off when all of it
+        // is wanted; with a depth limit it would fetch everything below and throw it away
+        args.push("--fast-list".to_string());
+        args.push("--recursive".to_string());
+    } else {
+        args.push("--max-depth".to_string());
+        args.push("1".to_string());
+    }
+    args
+}
+
+fn sort_cloud_files(files: &mut [CloudFile]) {
+    files.sort_by(|a, b| {
+        if a.is_dir != b.is_dir {
+            b.is_dir.cmp(&a.is_dir) // Directories first
+        } else {
+            a.name.cmp(&b.name)
+        }
+    });
+}
+
+/// Join a user-supplied path onto restore_base, refusing paths that could escape it
+fn scoped_remote_path(profile: &Profile, path: &str) -> Result<String, String> {
+    let relative = path.trim_matches('/');
+    if relative.is_empty() {
+        return Err("A file or folder path is required".to_string());
+    }
+    if relative.split('/').any(|segment| segment == ".." || segment == ".") {
+        return Err(format!("Invalid path: {}", path));
+    }
+    Ok(remote_join(&restore_base(profile), relative))
+}
+
+#[command]
+pub async fn move_cloud_file(profile: Profile, from_path: String, to_path: String) -> Result<(), BackupError> {
+    let from = scoped_remote_path(&profile, &from_path)?;
+    let to = scoped_remote_path(&profile, &to_path)?;
+    if from == to {
+        return Err("Source and destination are the same".into());
+    }
+
+    debug!("move_cloud_file - {} -> {}", from, to);
+    let output = run_rclone(&profile, "moveto", &[from.clone(), to.clone()], DEFAULT_TIMEOUT).await?;
+    if !output.success {
+        return Err(format!("Failed to move {} to {}: {}", from_path, to_path, output.stderr).into());
+    }
+
+    Ok(())
+}
+
+/// Delete a file, or a folder and everything in it when `recursive` is set
+#[command]
+pub async fn delete_cloud_file(profile: Profile, path: String, recursive: Option<bool>) -> Result<(), BackupError> {
+    let target = scoped_remote_path(&profile, &path)?;
+
+    let stat = run_rclone(&profile, "lsjson", &[target.clone(), "--stat".to_string()], QUICK_TIMEOUT).await?;
+    if !stat.success {
+        return Err(format!("Failed to find {}: {}", path, stat.stderr).into());
+    }
+    let is_dir = serde_json::from_str::<Value>(&stat.stdout)
+        .ok()
+        .and_then(|item| item.get("IsDir").and_then(|v| v.as_bool()))
+        .unwrap_or(false);
+
+    // Folder deletes remove everything below them, so they must be asked for explicitly
+    if is_dir && !recursive.unwrap_or(false) {
+        return Err(format!("{} is a folder; pass recursive to delete it and all of its contents", path).into());
+    }
+
+    let subcommand = if is_dir { "purge" } else { "deletefile" };
+    debug!("delete_cloud_file - rclone {} {}", subcommand, target);
+    let output = run_rclone(&profile, subcommand, &[target], DEFAULT_TIMEOUT).await?;
+    if !output.success {
+        return Err(format!("Failed to delete {}: {}", path, output.stderr).into());
+    }
+
+    Ok(())
+}
+
+/// Move everything under the profile's prefix to `new_prefix` server-side and point the
+/// profile (and, if the prefix belongs to an employee, their IAM policy) at it. Fails if the
+/// new prefix already has data unless `merge` is set, in which case files with the same
+/// path are overwritten by the moved ones.
+#[command]
+pub async fn rename_prefix(
+    profile_id: String,
+    new_prefix: String,
+    merge: Option<bool>,
+    aws_profile: Option<String>,
+) -> Result<PrefixRename, BackupError> {
+    let profile = load_profile(&profile_id).await?;
+    let old_prefix = profile.prefix.clone();
+    let new_prefix = new_prefix.trim().trim_matches('/').to_string();
+
+    if profile.user_id.is_some() {
+        return Err(BackupError::ConfigInvalid("This profile's prefix comes from its sign-in account and can't be renamed".to_string()));
+    }
+    if old_prefix.is_empty() {
+        return Err(BackupError::ConfigInvalid("This profile backs up to the whole bucket; there is no prefix to rename".to_string()));
+    }
+    if new_prefix.is_empty() || new_prefix.split('/').any(|part| part.is_empty() || part == "." || part == "..") {
+        return Err(BackupError::ConfigInvalid(format!("'{}' is not a valid prefix", new_prefix)));
+    }
+    if new_prefix == old_prefix {
+        return Err("The new prefix is the same as the current one".into());
+    }
+    if new_prefix.starts_with(&format!("{}/", old_prefix)) || old_prefix.starts_with(&format!("{}/", new_prefix)) {
+        return Err(BackupError::ConfigInvalid("The new prefix can't be inside the current one, or the other way round".to_string()));
+    }
+
+    // No backup or restore may write under the old prefix while it moves
+    let _run_guard = acquire_profile_run(&profile.id)?;
+
+    let bucket_root = format!("{}:{}", profile.remote, profile.bucket);
+    let old_path = format!("{}/{}", bucket_root, old_prefix);
+    let new_path = format!("{}/{}", bucket_root, new_prefix);
+
+    let (existing_files, _) = remote_size(&profile, &new_path).await?;
+    let merged = existing_files > 0;
+    if merged && !merge.unwrap_or(false) {
+        return Err(format!(
+            "{} already holds {} file(s). Choose another prefix, or merge to move these backups in alongside them.",
+            new_prefix, existing_files
+        ).into());
+    }
+
+    let (files_moved, bytes_moved) = remote_size(&profile, &old_path).await?;
+    debug!("rename_prefix - moving {} files ({} bytes) from {} to {}", files_moved, bytes_moved, old_path, new_path);
+
+    let mut args = vec![
+        old_path.clone(),
+        new_path.clone(),
+        "--delete-empty-src-dirs".to_string(),
+        "--fast-list".to_string(),
+    ];
+    args.extend(profile.transfer_flags());
+    // Stopping partway would leave the backups split between both prefixes
+    let output = run_rclone(&profile, "move", &args, NO_TIMEOUT).await?;
+    if !output.success {
+        return Err(format!("Failed to move {} to {}: {}", old_prefix, new_prefix, output.stderr).into());
+    }
+
+    let mut config = crate::config::load_config().await?;
+
+    // The IAM policy has to follow the data, or the employee can no longer back up
+    let aws_profile = aws_profile.unwrap_or_else(|| "default".to_string());
+    let mut policy_updated_for = None;
+    let owner = config.profiles.iter_mut()
+        .filter_map(|p| p.aws_config.as_mut())
+        .filter(|aws| aws.bucket_name == profile.bucket)
+        .find_map(|aws| {
+            let region = aws.aws_region.clone();
+            aws.employees.iter_mut()
+                .find(|e| e.backup_prefix() == old_prefix)
+                .map(|employee| (region, employee))
+        });
+    if let Some((region, employee)) = owner {
+        crate::aws::update_employee_policy_prefix(&aws_profile, &region, &profile.bucket, &employee.username, &new_prefix)
+            .await
+            .map_err(|e| format!("Moved the backups to {}, but updating the IAM policy for {} failed: {}", new_prefix, employee.username, e))?;
+        employee.prefix = (new_prefix != employee.username).then(|| new_prefix.clone());
+        policy_updated_for = Some(employee.username.clone());
+    }
+
+    let stored = config.profiles.iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    stored.prefix = new_prefix.clone();
+    stored.updated_at = Utc::now();
+    let updated = stored.clone();
+    config.updated_at = Utc::now();
+    crate::config::save_config(&config).await?;
+
+    // The crypt remote wraps remote:bucket/prefix, so it has to follow too
+    if updated.encryption_enabled {
+        crate::crypt::write_crypt_remote(&updated, &rclone_config_path(&updated)?).await?;
+        let scheduled_conf = crate::config::get_config_dir()?.join("rclone-scheduled.conf");
+        if scheduled_conf.exists() {
+            crate::crypt::write_crypt_remote(&updated, &scheduled_conf).await?;
+        }
+    }
+
+    // The old prefix is now outside the IAM policy, so scheduled runs must stop using it
+    crate::schedule::refresh_runner_script(&updated)
+        .await
+        .map_err(|e| format!("Moved the backups to {}, but updating the scheduled backup script failed: {}", new_prefix, e))?;
+
+    debug!("rename_prefix - {} is now {}", old_prefix, new_prefix);
+    Ok(PrefixRename {
+        old_prefix,
+        new_prefix,
+        files_moved,
+        bytes_moved,
+        merged,
+        policy_updated_for,
+    })
+}
+
+/// Delete everything the profile has backed up: remote:bucket/prefix, or for an admin
+/// profile without a prefix everything in the bucket, though the bucket itself stays.
+/// `confirm_token` must repeat that location ("bucket/prefix", or just "bucket") exactly.
+/// With `all_versions`, old versions kept by a versioned bucket are removed as well, so
+/// nothing is left to restore.
+#[command]
+pub async fn purge_remote(profile: Profile, confirm_token: String, all_versions: Option<bool>) -> Result<PurgeResult, BackupError> {
+    let prefix = profile.prefix.trim().trim_matches('/');
+    let location = if prefix.is_empty() {
+        profile.bucket.clone()
+    } else {
+        format!("{}/{}", profile.bucket, prefix)
+    };
+
+    if prefix.is_empty() && !matches!(profile.profile_type, crate::models::ProfileType::Admin) {
+        return Err(BackupError::ConfigInvalid("Only admin profiles can purge a whole bucket".to_string()));
+    }
+    if confirm_token.trim() != location {
+        return Err(BackupError::ConfigInvalid(format!("Type {} to confirm deleting everything stored there", location)));
+    }
+
+    // No backup may write into the prefix while it's being emptied
+    let _run_guard = acquire_profile_run(&profile.id)?;
+
+    // The bucket path rather than the crypt root, so objects that don't decrypt go too
+    let target = format!("{}:{}", profile.remote, location);
+    let all_versions = all_versions.unwrap_or(false);
+    let (files_deleted, bytes_freed) = remote_size_with(&profile, &target, all_versions).await?;
+    warn!("purge_remote - deleting {} files ({} bytes) under {}{}", files_deleted, bytes_freed, target,
+        if all_versions { " including old versions" } else { "" });
+
+    // delete rather than purge: purge on the bucket root removes the bucket itself, with its
+    // versioning, lifecycle and policy. --rmdirs clears empty folders but keeps the root.
+    if files_deleted > 0 {
+        let args = [target.clone(), "--rmdirs".to_string(), "--fast-list".to_string()];
+        let output = run_rclone(&profile, "delete", &args, NO_TIMEOUT).await?;
+        if !output.success && !output.stderr.contains("directory not found") {
+            return Err(format!("Failed to purge {}: {}", location, output.stderr).into());
+        }
+    }
+
+    // delete only removes current versions; on a versioned bucket it leaves the old ones
+    // behind, hidden under delete markers
+    if all_versions {
+        let output = run_rclone(&profile, "backend", &["cleanup-hidden".to_string(), target.clone()], NO_TIMEOUT).await?;
+        if !output.success {
+            return Err(format!("Deleted the current files under {}, but removing old versions failed: {}", location, output.stderr).into());
+        }
+    }
+
+    info!("purge_remote - purged {}", target);
+    Ok(PurgeResult {
+        location,
+        files_deleted,
+        bytes_freed,
+        all_versions,
+    })
+}
+
+/// The profile's use_backup_dir snapshots, newest first, with what each one holds
+#[command]
+pub async fn list_deleted_snapshots(profile: Profile) -> Result<Vec<DeletedSnapshot>, BackupError> {
+    let root = remote_join(&profile.destination(), BACKUP_DIR_FOLDER);
+    let files = list_files(&root, &profile, &[]).await?;
+
+    let mut snapshots: HashMap<String, DeletedSnapshot> = HashMap::new();
+    for file in files.values() {
+        let Some((name, _)) = file.path.split_once('/') else { continue };
+        let snapshot = snapshots.entry(name.to_string()).or_insert_with(|| DeletedSnapshot {
+            name: name.to_string(),
+            taken_at: snapshot_time(name),
+            files: 0,
+            bytes: 0,
+        });
+        snapshot.files += 1;
+        snapshot.bytes += file.size;
+    }
+
+    // The stamp format sorts by time
+    let mut snapshots: Vec<DeletedSnapshot> = snapshots.into_values().collect();
+    snapshots.sort_by(|a, b| b.name.cmp(&a.name));
+    Ok(snapshots)
+}
+
+/// Download what a use_backup_dir snapshot holds into local_target, one folder per source
+/// as in the backup. `paths` (relative to the snapshot) restores just those files or folders.
+#[command]
+pub async fn restore_deleted_snapshot(
+    app: AppHandle,
+    profile: Profile,
+    snapshot: String,
+    paths: Option<Vec<String>>,
+    local_target: String,
+    overwrite: Option<bool>,
+) -> Result<BackupOperation, BackupError> {
+    check_snapshot_name(&snapshot)?;
+
+    // restore_files reads paths relative to restore_base, which for admins is the bucket
+    let mut snapshot_path = format!("{}/{}", BACKUP_DIR_FOLDER, snapshot);
+    if restore_base(&profile) != profile.destination() {
+        let prefix = profile.prefix.trim_matches('/');
+        if !prefix.is_empty() {
+            snapshot_path = format!("{}/{}", prefix, snapshot_path);
+        }
+    }
+    let remote_paths = match paths.filter(|paths| !paths.is_empty()) {
+        Some(paths) => paths.iter().map(|path| remote_join(&snapshot_path, path)).collect(),
+        None => vec![snapshot_path],
+    };
+
+    restore_files(app, profile, remote_paths, local_target, overwrite.unwrap_or(false), None, None, None, None).await
+}
+
+/// Delete the use_backup_dir snapshots taken more than `keep_days` days ago, including
+/// every file in them. Folders whose names aren't snapshot times are left alone.
+/// Returns the names of the snapshots removed.
+#[command]
+pub async fn prune_deleted_snapshots(profile: Profile, keep_days: u32) -> Result<Vec<String>, BackupError> {
+    // A running backup may be moving files into the newest snapshot
+    let _run_guard = acquire_profile_run(&profile.id)?;
+
+    let root = remote_join(&profile.destination(), BACKUP_DIR_FOLDER);
+    let cutoff = Utc::now() - chrono::Duration::days(keep_days as i64);
+
+    let args = vec![root.clone(), "--dirs-only".to_string()];
+    let output = run_rclone(&profile, "lsjson", &args, DEFAULT_TIMEOUT).await?;
+    if !output.success {
+        if output.stderr.contains("directory not found") {
+            return Ok(Vec::new());
+        }
+        return Err(format!("Failed to list {}: {}", root, output.stderr).into());
+    }
+    let items: Vec<Value> = serde_json::from_str(&output.stdout)
+        .map_err(|e| format!("Failed to parse rclone output: {}", e))?;
+
+    let mut removed = Vec::new();
+    for item in items {
+        let Some(name) = item.get("Name").and_then(|v| v.as_str()) else { continue };
+        if snapshot_time(name).is_none_or(|taken_at| taken_at >= cutoff) {
+            continue;
+        }
+
+        let target = remote_join(&root, name);
+        let output = run_rclone(&profile, "purge", std::slice::from_ref(&target), profile.transfer_timeout()).await?;
+        if !output.success {
+            return Err(format!("Failed to delete snapshot {}: {}", name, output.stderr).into());
+        }
+        info!("prune_deleted_snapshots - deleted {}", target);
+        removed.push(name.to_string());
+    }
+
+    Ok(removed)
+}
+
+/// When a use_backup_dir snapshot was taken, from its folder name
+fn snapshot_time(name: &str) -> Option<DateTime<Utc>> {
+    chrono::NaiveDateTime::parse_from_str(name, BACKUP_DIR_STAMP_FORMAT)
+        .ok()
+        .map(|time| time.and_utc())
+}
+
+fn check_snapshot_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.contains('/') || name.contains('\\') || name == "." || name == ".." {
+        return Err(format!("Invalid snapshot name: {}", name));
+    }
+    Ok(())
+}
+
+/// (file count, total bytes) under a remote path; a path that doesn't exist yet is empty
+async fn remote_size(profile: &Profile, path: &str) -> Result<(u64, u64), String> {
+    remote_size_with(profile, path, false).await
+}
+
+/// remote_size, optionally counting the old versions a versioned bucket keeps
+async fn remote_size_with(profile: &Profile, path: &str, s3_versions: bool) -> Result<(u64, u64), String> {
+    let mut args = vec![path.to_string(), "--json".to_string(), "--fast-list".to_string()];
+    if s3_versions {
+        args.push("--s3-versions".to_string());
+    }
+    let output = run_rclone(profile, "size", &args, DEFAULT_TIMEOUT).await?;
+    if !output.success {
+        if output.stderr.contains("directory not found") {
+            return Ok((0, 0));
+        }
+        return Err(format!("Failed to size {}: {}", path, output.stderr));
+    }
+
+    let size: Value = serde_json::from_str(&output.stdout)
+        .map_err(|e| format!("Failed to parse rclone size output: {}", e))?;
+    Ok((
+        size["count"].as_u64().unwrap_or(0),
+        size["bytes"].as_u64().unwrap_or(0),
+    ))
+}
+
+/// Per-prefix storage totals for the whole bucket, for cost attribution. Admin only.
+#[command]
+pub async fn storage_report(profile: Profile) -> Result<Vec<PrefixUsage>, BackupError> {
+    if !matches!(profile.profile_type, crate::models::ProfileType::Admin) {
+        return Err("Storage reports are only available for admin profiles".into());
+    }
+
+    let args = vec![
+        format!("{}:{}", profile.remote, profile.bucket),
+        "--recursive".to_string(),
+        "--files-only".to_string(),
+        "--fast-list".to_string(),
+    ];
+
+    // A full-bucket listing can take a while, so use the transfer timeout rather than the default
+    let output = run_rclone(&profile, "lsjson", &args, profile.transfer_timeout()).await?;
+    if !output.success {
+        return Err(format!("Failed to list bucket {}: {}", profile.bucket, output.stderr).into());
+    }
+
+    let items: Vec<Value> = serde_json::from_str(&output.stdout)
+        .map_err(|e| format!("Failed to parse rclone output: {}", e))?;
+
+    // Group on the first path segment so prefixes without a registered employee still show up
+    let mut usage: HashMap<String, PrefixUsage> = HashMap::new();
+    for item in items {
+        if let Some(file) = parse_rclone_item(&item)? {
+            let prefix = match file.path.split_once('/') {
+                Some((prefix, _)) => prefix.to_string(),
+                None => String::new(),
+            };
+            let entry = usage.entry(prefix.clone()).or_insert(PrefixUsage {
+                prefix,
+                total_bytes: 0,
+                file_count: 0,
+            });
+            entry.total_bytes += file.size;
+            entry.file_count += 1;
+        }
+    }
+
+    // Registered employees with nothing stored yet are reported as zero
+    if let Some(aws_config) = &profile.aws_config {
+        for employee in &aws_config.employees {
+            usage.entry(employee.backup_prefix().to_string()).or_insert(PrefixUsage {
+                prefix: employee.backup_prefix().to_string(),
+                total_bytes: 0,
+                file_count: 0,
+            });
+        }
+    }
+
+    let mut report: Vec<PrefixUsage> = usage.into_values().collect();
+    report.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes).then_with(|| a.prefix.cmp(&b.prefix)));
+
+    debug!("storage_report - {} prefixes in bucket {}", report.len(), profile.bucket);
+    Ok(report)
+}
+
+fn parse_rclone_item(item: &Value) -> Result<Option<CloudFile>, String> {
+    let obj = item.as_object().ok_or("Invalid rclone item format")?;
+    
+    let path = obj.get("Path")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing Path in rclone item")?
+        .to_string();
+
+    let name = obj.get("Name")
+        .and_then(|v| v.as_str())
+        .unwrap_or(&path)
+        .to_string();
+
+    let size = obj.get("Size")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+
+    let is_dir = obj.get("IsDir")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let mod_time = obj.get("ModTime")
+        .and_then(|v| v.as_str())
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+
+    let mime_type = obj.get("MimeType")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    Ok(Some(CloudFile {
+        path,
+        name,
+        size,
+        mod_time,
+        is_dir,
+        mime_type,
+    }))
+}
+
+#[command]
+pub async fn backup_preview(profile: Profile) -> Result<BackupPreview, BackupError> {
+    // All users (including admins) backup to their own designated folder
+    // Admins backup to: admins/{user-id}/
+    // Regular users backup to: users/{user-id}/
+    preview_destination(&profile, &profile.destination()).await
+}
+
+/// Dry run of backing up every source into `destination`
+async fn preview_destination(profile: &Profile, destination: &str) -> Result<BackupPreview, BackupError> {
+    backup_preflight(profile)?;
+
+    let operation = match profile.mode {
+        BackupMode::Copy => "copy",
+        BackupMode::Sync => "sync",
+    };
+
+    let mut all_changes = Vec::new();
+
+    for source in &profile.sources {
+        // Extract the folder name from the source path to preserve folder structure
+        // E.g., /Users/john/Documents -> Documents
+        let source_folder_name = Path::new(source)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| format!("Invalid source path: {}", source))?;
+
+        // Append the source folder name to the destination to isolate each source
+        // E.g., aws:bucket/users/john-id/Documents
+        let destination_with_folder = remote_join(destination, source_folder_name);
+
+        let mut args = vec![
+            source.clone(),
+            destination_with_folder.clone(),
+            "--dry-run".to_string(),
+            "--stats=0".to_string(),
+        ];
+
+        // Add transfers/checkers and custom flags
+        args.extend(profile.transfer_flags());
+
+        // Add include/exclude and size filters
+        let filters = [profile.filter_flags(), profile.backup_filter_flags()?].concat();
+        args.extend(filters.iter().cloned());
+
+        let output = run_rclone(profile, operation, &args, DEFAULT_TIMEOUT).await?;
+        let mut changes = parse_dry_run_output(&output.stderr)?;
+
+        // Dry-run NOTICE lines don't carry sizes, so look them up from real listings
+        let source_sizes = list_file_sizes(source, profile, &filters).await?;
+        let destination_sizes = list_file_sizes(&destination_with_folder, profile, &[]).await?;
+
+        if destination_sizes.is_empty() {
+            // Destination doesn't exist yet - everything in the source is a new copy
+            changes = source_sizes.iter()
+                .map(|(path, size)| FileChange {
+                    path: path.clone(),
+                    size: *size,
+                    action: ChangeAction::Copy,
+                })
+                .collect();
+            changes.sort_by(|a, b| a.path.cmp(&b.path));
+        } else {
+            for change in changes.iter_mut() {
+                let sizes = match change.action {
+                    ChangeAction::Delete => &destination_sizes,
+                    _ => &source_sizes,
+                };
+                change.size = sizes.get(&change.path).copied().unwrap_or(0);
+            }
+        }
+
+        all_changes.extend(changes);
+    }
+
+    let files_to_copy: Vec<FileChange> = all_changes.iter()
+        .filter(|c| matches!(c.action, ChangeAction::Copy))
+        .cloned()
+        .collect();
 
     let files_to_update: Vec<FileChange> = all_changes.iter()
         .filter(|c| matches!(c.action, ChangeAction::Update))
@@ -305,324 +1808,1480 @@ pub async fn backup_preview(profile: Profile) -> Result<BackupPreview, String> {
     let total_files = all_changes.len() as u64;
     let total_size = all_changes.iter().map(|c| c.size).sum();
 
-    Ok(BackupPreview {
-        files_to_copy,
-        files_to_update,
-        files_to_delete,
-        total_files,
-        total_size,
-    })
+    Ok(BackupPreview {
+        files_to_copy,
+        files_to_update,
+        files_to_delete,
+        total_files,
+        total_size,
+    })
+}
+
+/// Local changes since the profile's last successful backup, by source, for an "N changed
+/// files" indicator. Cheaper than backup_preview: each side is listed once and nothing is
+/// dry-run. Files missing from the backup count as added, files modified after that backup
+/// finished as modified, and backed-up files gone locally as deleted (sync profiles only,
+/// since copy backups keep them). None if the profile has never backed up successfully.
+#[command]
+pub async fn changes_since_last_backup(profile_id: String) -> Result<Option<ChangesSinceBackup>, BackupError> {
+    let config = crate::config::load_config().await?;
+    let profile = config.profiles.iter()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+
+    // A partial success uploaded everything but the failed files, which show up as added
+    let Some(since) = config.backup_operations.iter()
+        .filter(|op| op.profile_id == profile.id && matches!(op.operation_type, OperationType::Backup))
+        .filter(|op| matches!(op.status, OperationStatus::Completed | OperationStatus::PartialSuccess))
+        .filter_map(|op| op.completed_at)
+        .max()
+    else {
+        return Ok(None);
+    };
+
+    backup_preflight(profile)?;
+
+    let destination = profile.destination();
+    let filters = [profile.filter_flags(), profile.backup_filter_flags()?].concat();
+    let mut sources = Vec::new();
+
+    for source in &profile.sources {
+        let source_folder_name = Path::new(source)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| format!("Invalid source path: {}", source))?;
+        let destination_with_folder = remote_join(&destination, source_folder_name);
+
+        let local_files = list_files(source, profile, &filters).await?;
+        let backed_up = list_file_sizes(&destination_with_folder, profile, &filters).await?;
+
+        let mut changes = SourceChanges {
+            source: source.clone(),
+            added: Vec::new(),
+            modified: Vec::new(),
+            deleted: Vec::new(),
+        };
+        for file in local_files.values() {
+            let change = |action| FileChange { path: file.path.clone(), size: file.size, action };
+            if !backed_up.contains_key(&file.path) {
+                changes.added.push(change(ChangeAction::Copy));
+            } else if file.mod_time > since {
+                changes.modified.push(change(ChangeAction::Update));
+            }
+        }
+        if matches!(profile.mode, BackupMode::Sync) {
+            changes.deleted = backed_up.iter()
+                .filter(|(path, _)| !local_files.contains_key(*path))
+                .map(|(path, size)| FileChange { path: path.clone(), size: *size, action: ChangeAction::Delete })
+                .collect();
+        }
+
+        for list in [&mut changes.added, &mut changes.modified, &mut changes.deleted] {
+            list.sort_by(|a, b| a.path.cmp(&b.path));
+        }
+        sources.push(changes);
+    }
+
+    let all_changes = || sources.iter().flat_map(|s| s.added.iter().chain(&s.modified).chain(&s.deleted));
+    let total_files = all_changes().count() as u64;
+    let total_size = all_changes().map(|c| c.size).sum();
+    debug!("changes_since_last_backup - {} file(s) changed in {} since {}", total_files, profile.name, since);
+
+    Ok(Some(ChangesSinceBackup {
+        since,
+        sources,
+        total_files,
+        total_size,
+    }))
+}
+
+/// List every file under `target` with its size, keyed by path relative to `target`.
+/// A missing directory is treated as empty rather than an error.
+async fn list_file_sizes(target: &str, profile: &Profile, filters: &[String]) -> Result<HashMap<String, u64>, String> {
+    let files = list_files(target, profile, filters).await?;
+    Ok(files.into_iter().map(|(path, file)| (path, file.size)).collect())
+}
+
+/// Like list_file_sizes, with each file's full listing entry
+async fn list_files(target: &str, profile: &Profile, filters: &[String]) -> Result<HashMap<String, CloudFile>, String> {
+    let mut args = vec![
+        target.to_string(),
+        "--recursive".to_string(),
+        "--files-only".to_string(),
+    ];
+    args.extend(filters.iter().cloned());
+
+    let output = run_rclone(profile, "lsjson", &args, DEFAULT_TIMEOUT).await?;
+
+    if !output.success {
+        if output.stderr.contains("directory not found") {
+            return Ok(HashMap::new());
+        }
+        return Err(format!("Failed to list {}: {}", target, output.stderr));
+    }
+
+    let items: Vec<Value> = serde_json::from_str(&output.stdout)
+        .map_err(|e| format!("Failed to parse rclone output: {}", e))?;
+
+    let mut files = HashMap::new();
+    for item in items {
+        if let Some(file) = parse_rclone_item(&item)? {
+            files.insert(file.path.clone(), file);
+        }
+    }
+
+    Ok(files)
 }
 
 fn parse_dry_run_output(output: &str) -> Result<Vec<FileChange>, String> {
     let mut changes = Vec::new();
-    
+
     for line in output.lines() {
-        if line.contains("NOTICE:") && line.contains("would copy") {
-            if let Some(path) = extract_file_path_from_notice(line) {
-                changes.push(FileChange {
-                    path,
-                    size: 0, // Size info not always available in dry-run output
-                    action: ChangeAction::Copy,
-                });
-            }
-        } else if line.contains("NOTICE:") && line.contains("would update") {
-            if let Some(path) = extract_file_path_from_notice(line) {
-                changes.push(FileChange {
-                    path,
-                    size: 0,
-                    action: ChangeAction::Update,
-                });
-            }
-        } else if line.contains("NOTICE:") && line.contains("would delete") {
-            if let Some(path) = extract_file_path_from_notice(line) {
-                changes.push(FileChange {
-                    path,
-                    size: 0,
-                    action: ChangeAction::Delete,
-                });
-            }
+        if !line.contains("NOTICE:") {
+            continue;
+        }
+
+        // rclone says "Skipped copy as --dry-run is set"; older builds said "Would copy"
+        let lower = line.to_ascii_lowercase();
+        let action = if lower.contains("would copy") || lower.contains("skipped copy") {
+            ChangeAction::Copy
+        } else if lower.contains("would update") || lower.contains("skipped update") {
+            ChangeAction::Update
+        } else if lower.contains("would delete") || lower.contains("skipped delete") {
+            ChangeAction::Delete
+        } else {
+            continue;
+        };
+
+        if let Some(path) = extract_file_path_from_notice(line) {
+            changes.push(FileChange {
+                path,
+                size: 0, // Size info not always available in dry-run output
+                action,
+            });
         }
     }
-    
+
     Ok(changes)
 }
 
+/// Path from a dry-run NOTICE line such as
+/// `2024/01/15 10:30:45 NOTICE: My Docs/a.txt: Skipped copy as --dry-run is set (size 1.2Ki)`:
+/// everything after the log prefix and before the last ": Would"/": Skipped", unquoted.
 fn extract_file_path_from_notice(line: &str) -> Option<String> {
-    // This is a simplified parser - in reality, rclone output can be complex
-    // We'd need more sophisticated parsing for production use
-    line.split('"').nth(1).map(|s| s.to_string())
+    let (_, message) = line.split_once("NOTICE:")?;
+    let message = message.trim_start();
+
+    // ASCII lowercasing keeps byte offsets valid for slicing `message`
+    let lower = message.to_ascii_lowercase();
+    let end = [": would ", ": skipped "].iter()
+        .filter_map(|marker| lower.rfind(marker))
+        .max()?;
+
+    let path = message[..end].trim();
+    let path = path.strip_prefix('"')
+        .and_then(|p| p.strip_suffix('"'))
+        .unwrap_or(path);
+
+    if path.is_empty() {
+        None
+    } else {
+        Some(path.to_string())
+    }
 }
 
+/// Run a backup. In Sync mode the profile may require confirming deletions: the run is
+/// previewed first and refused with SyncDeletionsPending unless `confirm_deletions` is set.
 #[command]
-pub async fn backup_run(profile: Profile, dry_run: bool) -> Result<BackupOperation, String> {
-    let operation_id = uuid::Uuid::new_v4().to_string();
-    let started_at = Utc::now();
+pub async fn backup_run(profile: Profile, dry_run: bool, confirm_deletions: Option<bool>) -> Result<BackupOperation, BackupError> {
+    start_backup(profile, dry_run, confirm_deletions, None).await
+}
+
+/// Like backup_run, but every rclone process also serves the rc API on a local port so
+/// get_transfer_stats can report structured progress. The operation id to poll with is
+/// sent in a "backup-started" event once the run begins.
+#[command]
+pub async fn start_backup_with_rc(app: AppHandle, profile: Profile, dry_run: bool, confirm_deletions: Option<bool>) -> Result<BackupOperation, BackupError> {
+    start_backup(profile, dry_run, confirm_deletions, Some(&app)).await
+}
+
+/// `rc_app` enables the rc API and receives the "backup-started" event
+async fn start_backup(profile: Profile, dry_run: bool, confirm_deletions: Option<bool>, rc_app: Option<&AppHandle>) -> Result<BackupOperation, BackupError> {
+    // Dry runs don't write anything, so they may overlap a real run
+    let _run_guard = if dry_run { None } else { Some(acquire_profile_run(&profile.id)?) };
+
+    if !dry_run && !confirm_deletions.unwrap_or(false) {
+        check_sync_deletions(&profile).await?;
+    }
+
+    let running = save_running_operation(&profile.id, OperationType::Backup).await;
+    let destination_count = 1 + profile.secondary_destinations.len();
+    let _live = track_operation(&running, profile.sources.len() * destination_count);
+    if let Some(app) = rc_app {
+        let _ = app.emit("backup-started", &BackupStarted {
+            profile_id: profile.id.clone(),
+            operation_id: running.id.clone(),
+        });
+    }
+    let rc = rc_app.is_some();
+    let result = run_backup(profile.clone(), dry_run, running.id.clone(), running.started_at, rc).await;
+    let finished = match &result {
+        Ok(operation) => operation.clone(),
+        Err(e) => fail_running_operation(running, e).await,
+    };
+    if !dry_run {
+        notify_backup_result(&profile, &finished).await;
+    }
+    result.map_err(BackupError::from)
+}
+
+/// Refuse a Sync run whose dry run deletes more files than the profile allows unconfirmed
+/// from any of its destinations; secondary destinations are synced too
+async fn check_sync_deletions(profile: &Profile) -> Result<(), BackupError> {
+    if !profile.sync_deletions_need_confirmation(usize::MAX) {
+        return Ok(());
+    }
+
+    let primary = profile.destination();
+    let destinations = std::iter::once(&primary).chain(&profile.secondary_destinations);
+    for destination in destinations {
+        let preview = preview_destination(profile, destination).await?;
+        let count = preview.files_to_delete.len();
+        if !profile.sync_deletions_need_confirmation(count) {
+            continue;
+        }
+
+        debug!("backup_run - Sync would delete {} files from {}, waiting for confirmation", count, destination);
+        let location = if *destination == primary { "the cloud".to_string() } else { destination.clone() };
+        return Err(BackupError::SyncDeletionsPending {
+            message: format!(
+                "This sync would delete {} files from {} (limit without confirmation: {})",
+                count, location, profile.sync_delete_threshold
+            ),
+            files_to_delete: preview.files_to_delete,
+        });
+    }
+
+    Ok(())
+}
+
+/// Save a Running placeholder so a run that never finishes (e.g. the app is killed)
+/// still shows up in history; the final save replaces it by id
+async fn save_running_operation(profile_id: &str, operation_type: OperationType) -> BackupOperation {
+    let operation = BackupOperation {
+        id: uuid::Uuid::new_v4().to_string(),
+        profile_id: profile_id.to_string(),
+        operation_type,
+        status: OperationStatus::Running,
+        started_at: Utc::now(),
+        completed_at: None,
+        files_transferred: 0,
+        bytes_transferred: 0,
+        files_skipped: 0,
+        error_message: None,
+        log_output: String::new(),
+        repeat_count: 1,
+        first_started_at: None,
+    };
+
+    if let Err(e) = crate::config::save_backup_operation(operation.clone()).await {
+        error!("Failed to save running operation: {}", e);
+    }
+    operation
+}
+
+/// Close out a Running placeholder when the run bailed out with an error before saving a result
+async fn fail_running_operation(mut operation: BackupOperation, error: &str) -> BackupOperation {
+    // Runs stopped because the app is quitting are cancelled, not failed
+    operation.status = if shutdown_requested() {
+        OperationStatus::Cancelled
+    } else {
+        OperationStatus::Failed
+    };
+    operation.completed_at = Some(Utc::now());
+    operation.error_message = Some(error.to_string());
+
+    if let Err(e) = crate::config::save_backup_operation(operation.clone()).await {
+        error!("Failed to save failed operation: {}", e);
+    }
+    operation
+}
+
+/// Checks that the rclone binary, its config and every source exist, shared by backup_run
+/// and backup_preview so both fail with a clear message instead of rclone's stderr
+fn backup_preflight(profile: &Profile) -> Result<(), String> {
+    let rclone_binary = resolve_rclone_binary(&profile.rclone_bin)?;
+    if !Path::new(&rclone_binary).exists() && rclone_binary != "rclone" {
+        return Err(format!("Rclone binary not found at path: {}", rclone_binary));
+    }
+
+    if !Path::new(&profile.rclone_conf).exists() {
+        return Err(format!("Rclone config not found at path: {}", profile.rclone_conf));
+    }
+
+    for source in &profile.sources {
+        if !Path::new(source).exists() {
+            return Err(format!("Source directory not found: {}", source));
+        }
+    }
 
+    Ok(())
+}
+
+async fn run_backup(profile: Profile, dry_run: bool, operation_id: String, started_at: DateTime<Utc>, rc: bool) -> Result<BackupOperation, String> {
     let operation = match profile.mode {
         BackupMode::Copy => "copy",
         BackupMode::Sync => "sync",
     };
 
+    backup_preflight(&profile)?;
+
+    // Fail in seconds rather than after scanning every source
+    if profile.check_write_access && !dry_run {
+        check_write_access(&profile).await?;
+    }
+
     // All users (including admins) backup to their own designated folder
     // Admins backup to: admins/{user-id}/
     // Regular users backup to: users/{user-id}/
-    let destination = profile.destination();
+    // Secondary destinations (e.g. an external drive) follow, one after another
+    let mut destinations = vec![profile.destination()];
+    destinations.extend(profile.secondary_destinations.iter().cloned());
+
+    let shared_profile = Arc::new(profile.clone());
     let mut combined_output = String::new();
     let mut total_files = 0u64;
     let mut total_bytes = 0u64;
+    let mut failure: Option<String> = None;
+    let mut secondary_failures = Vec::new();
+    let mut failed_files = Vec::new();
+
+    for (index, destination) in destinations.iter().enumerate() {
+        let run = backup_to_destination(&shared_profile, destination, operation, dry_run, &operation_id, rc).await?;
+
+        if destinations.len() > 1 {
+            combined_output.push_str(&format!(
+                "##### Destination: {} ({}) - {}: {} files, {} bytes #####\n",
+                destination,
+                if index == 0 { "primary" } else { "secondary" },
+                if run.failure.is_some() { "FAILED" } else if !run.failed_files.is_empty() { "PARTIAL" } else { "OK" },
+                run.files,
+                run.bytes
+            ));
+        }
+        combined_output.push_str(&run.log_output);
+        total_files += run.files;
+        total_bytes += run.bytes;
+
+        if let Some(error_message) = run.failure {
+            if index == 0 {
+                failure = Some(error_message);
+            } else {
+                secondary_failures.push(error_message);
+            }
+        } else if !run.failed_files.is_empty() {
+            if index == 0 {
+                failed_files = run.failed_files;
+            } else {
+                secondary_failures.push(format!("{} file(s) failed to copy to {}", run.failed_files.len(), destination));
+            }
+        }
+    }
+
+    // A failed secondary only fails the operation when the profile asks for it
+    if failure.is_none() && !secondary_failures.is_empty() && profile.fail_on_secondary_error {
+        failure = Some(secondary_failures.join("\n"));
+    }
+
+    // Any source failing on the primary destination fails the whole operation
+    if let Some(error_message) = failure {
+        let failed_operation = BackupOperation {
+            id: operation_id,
+            profile_id: profile.id,
+            operation_type: OperationType::Backup,
+            status: OperationStatus::Failed,
+            started_at,
+            completed_at: Some(Utc::now()),
+            files_transferred: total_files,
+            bytes_transferred: total_bytes,
+            files_skipped: 0,
+            error_message: Some(error_message),
+            log_output: combined_output,
+            repeat_count: 1,
+            first_started_at: None,
+        };
+
+        // Save the failed operation to config
+        if let Err(e) = crate::config::save_backup_operation(failed_operation.clone()).await {
+            error!("Failed to save backup operation: {}", e);
+        }
+
+        return Ok(failed_operation);
+    }
+
+    // Tolerated secondary failures are still reported on the completed operation
+    let mut problems = Vec::new();
+    if !failed_files.is_empty() {
+        problems.push(failed_files_summary(&failed_files));
+    }
+    if !secondary_failures.is_empty() {
+        problems.push(format!("Secondary destination failed: {}", secondary_failures.join("\n")));
+    }
+    let error_message = (!problems.is_empty()).then(|| problems.join("\n\n"));
+
+    let operation = BackupOperation {
+        id: operation_id,
+        profile_id: profile.id.clone(),
+        operation_type: OperationType::Backup,
+        status: if failed_files.is_empty() { OperationStatus::Completed } else { OperationStatus::PartialSuccess },
+        started_at,
+        completed_at: Some(Utc::now()),
+        files_transferred: total_files,
+        bytes_transferred: total_bytes,
+        files_skipped: 0,
+        error_message,
+        log_output: combined_output,
+        repeat_count: 1,
+        first_started_at: None,
+    };
+
+    debug!("Manual backup completed - files: {}, bytes: {}, failed files: {}", total_files, total_bytes, failed_files.len());
+
+    // Save the operation to config
+    if let Err(e) = crate::config::save_backup_operation(operation.clone()).await {
+        error!("Failed to save backup operation: {}", e);
+    }
+
+    // Update the schedule's last_run and next_run after manual backup
+    if let Err(e) = crate::config::update_schedule_after_backup(&profile.id, started_at).await {
+        error!("Failed to update schedule after backup: {}", e);
+    }
+
+    Ok(operation)
+}
+
+/// How many files failed and which, for error_message; the full list stays in the log
+fn failed_files_summary(failed_files: &[String]) -> String {
+    const LISTED: usize = 20;
+    let mut summary = format!("{} file(s) failed to transfer:\n{}", failed_files.len(), failed_files[..failed_files.len().min(LISTED)].join("\n"));
+    if failed_files.len() > LISTED {
+        summary.push_str(&format!("\n... and {} more (see the log)", failed_files.len() - LISTED));
+    }
+    summary
+}
+
+/// Output and stats of backing up every source to one destination
+struct DestinationRun {
+    log_output: String,
+    files: u64,
+    bytes: u64,
+    failure: Option<String>,
+    failed_files: Vec<String>, // Per-file errors from sources whose other files did transfer
+}
+
+/// rclone exits 9 for a successful run that transferred nothing (--error-on-no-transfer)
+const RCLONE_EXIT_NO_TRANSFER: i32 = 9;
+
+/// Exit codes rclone uses when individual files failed while the rest of the run went on:
+/// uncategorised (1), file not found (4), temporary (5), less serious (6), and stopping at
+/// --max-transfer (8) or --max-duration (10). Usage errors (2), a missing directory (3)
+/// and fatal errors (7) mean the run didn't get going.
+fn rclone_exit_is_partial(exit_code: i32) -> bool {
+    matches!(exit_code, 1 | 4 | 5 | 6 | 8 | 10)
+}
+
+/// The "path: Failed to copy: ..." part of rclone's ERROR log lines, without the retry
+/// summaries ("Attempt 1/3 failed with 2 errors ...")
+fn rclone_error_lines(output: &str) -> Vec<String> {
+    output.lines()
+        .filter_map(|line| line.split_once("ERROR : ").map(|(_, message)| message.trim()))
+        .filter(|message| !message.is_empty() && !message.starts_with("Attempt "))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Back up all of the profile's sources into `destination`, one subfolder per source.
+/// Progress goes to the live store under `operation_id`; with `rc` set, each rclone
+/// process also serves rc stats under it.
+async fn backup_to_destination(profile: &Arc<Profile>, destination: &str, operation: &'static str, dry_run: bool, operation_id: &str, rc: bool) -> Result<DestinationRun, String> {
+    update_operation(operation_id, |live| live.current_item = Some(destination.to_string()));
+    let backup_filters = profile.backup_filter_flags()?;
+
+    // Every source of this run moves its deleted and replaced files into the same snapshot
+    let backup_dir = profile.use_backup_dir.then(|| {
+        let stamp = Utc::now().format(BACKUP_DIR_STAMP_FORMAT).to_string();
+        remote_join(&remote_join(destination, BACKUP_DIR_FOLDER), &stamp)
+    });
+
+    // Sources run concurrently, bounded by the profile's parallelism cap
+    let semaphore = Arc::new(Semaphore::new(profile.max_parallel_sources.max(1) as usize));
+    let transfer_timeout = profile.transfer_timeout();
+    let mut handles = Vec::new();
+
+    for source in &profile.sources {
+        // Extract the folder name from the source path to preserve folder structure
+        // E.g., /Users/john/Documents -> Documents
+        let source_folder_name = Path::new(source)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| format!("Invalid source path: {}", source))?;
+
+        // Append the source folder name to the destination to isolate each source
+        // E.g., aws:bucket/users/john-id/Documents
+        let destination_with_folder = remote_join(destination, source_folder_name);
+
+        let mut args = vec![
+            source.clone(),
+            destination_with_folder.clone(),
+            "--progress".to_string(),
+        ];
+        args.extend(profile.stats_flags());
+        // Normal and above log file operations, which the file count is parsed from
+        args.extend(profile.log_flags());
+
+        if dry_run {
+            args.push("--dry-run".to_string());
+        }
+
+        // Under the snapshot, each source keeps its own folder like in the backup itself
+        if let Some(backup_dir) = &backup_dir {
+            args.push(format!("--backup-dir={}", remote_join(backup_dir, source_folder_name)));
+        }
+
+        // Add transfers/checkers and custom flags
+        args.extend(profile.transfer_flags());
+
+        // Add include/exclude and size filters
+        args.extend(profile.filter_flags());
+        args.extend(backup_filters.iter().cloned());
+
+        let semaphore = semaphore.clone();
+        let profile = profile.clone();
+        let source = source.clone();
+        let rc_operation = rc.then(|| operation_id.to_string());
+
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.map_err(|e| e.to_string())?;
+
+            // Registered only once the source starts, so queued sources aren't polled
+            let output = match &rc_operation {
+                Some(operation_id) => run_rclone_with_rc(&profile, operation, &args, transfer_timeout, operation_id).await,
+                None => run_rclone(&profile, operation, &args, transfer_timeout).await,
+            }
+            .map_err(|e| format!("Failed to execute rclone {} with args {:?}: {}", operation, args, e))?;
+
+            Ok::<(String, RcloneOutput), String>((source, output))
+        }));
+    }
+
+    // Await in source order so the log is deterministic
+    let mut run = DestinationRun {
+        log_output: String::new(),
+        files: 0,
+        bytes: 0,
+        failure: None,
+        failed_files: Vec::new(),
+    };
+    for handle in handles {
+        let (source, result) = handle.await.map_err(|e| format!("Backup task failed: {}", e))??;
+        let source = &source;
+        update_operation(operation_id, |live| live.items_done += 1);
+        let stdout = &result.stdout;
+        let stderr = &result.stderr;
+
+        trace!("rclone output for {} -> {}\n----- stdout -----\n{}\n----- stderr -----\n{}", source, destination, stdout, stderr);
+
+        run.log_output.push_str(&format!("=== Source: {} ===\n", source));
+        run.log_output.push_str(stdout);
+        run.log_output.push_str(stderr);
+        run.log_output.push('\n');
+
+        let (files_from_operations, _) = parse_rclone_file_operations(stdout);
+        let parsed_stats = parse_rclone_stats(stdout);
+
+        if !result.success && result.exit_code != RCLONE_EXIT_NO_TRANSFER && !dry_run {
+            let transferred_any = files_from_operations > 0 || parsed_stats.is_some_and(|(_, bytes)| bytes > 0);
+            let failed_files = rclone_error_lines(&format!("{}\n{}", stdout, stderr));
+            if !(rclone_exit_is_partial(result.exit_code) && transferred_any && !failed_files.is_empty()) {
+                if run.failure.is_none() {
+                    run.failure = Some(format!("rclone {} failed for {} -> {}: {}", operation, source, destination, stderr));
+                }
+                continue;
+            }
+
+            // Some files failed but the rest went through; keep their stats
+            warn!("rclone {} for {} -> {} exited {} with {} failed file(s)", operation, source, destination, result.exit_code, failed_files.len());
+            run.failed_files.extend(failed_files.into_iter().map(|line| format!("{}: {}", source, line)));
+        }
+
+        // Parse stats from output - rclone outputs to stdout with --stats-one-line and -v
+        // Parse both bytes and file count from stdout
+        if let Some((_, bytes)) = parsed_stats {
+            debug!("Parsed rclone stats for source {}: {} files, {} bytes", source, files_from_operations, bytes);
+            run.files += files_from_operations;
+            run.bytes += bytes;
+            update_operation(operation_id, |live| {
+                live.files_done += files_from_operations;
+                live.bytes_done += bytes;
+            });
+        } else {
+            debug!("Could not parse rclone stats from stdout for source: {}", source);
+        }
+    }
+
+    Ok(run)
+}
+
+/// Remote root that restores read from.
+/// Admins can restore from the entire bucket; regular users only from their own prefix.
+/// With encryption on, only the profile's own crypt remote can be decrypted.
+fn restore_base(profile: &Profile) -> String {
+    if matches!(profile.profile_type, crate::models::ProfileType::Admin) && !profile.encryption_enabled {
+        format!("{}:{}", profile.remote, profile.bucket)
+    } else {
+        profile.destination()
+    }
+}
+
+#[command]
+pub async fn restore_preview(profile: Profile, remote_paths: Vec<String>, local_target: String) -> Result<BackupPreview, BackupError> {
+    let base_dest = restore_base(&profile);
+    let mut all_changes = Vec::new();
+
+    for remote_path in &remote_paths {
+        let full_remote_path = remote_join(&base_dest, remote_path);
+
+        let mut args = vec![
+            full_remote_path.clone(),
+            local_target.clone(),
+            "--dry-run".to_string(),
+            "--stats=0".to_string(),
+            "--fast-list".to_string(),
+        ];
+
+        args.extend(profile.hash_flags());
+
+        // Add include/exclude filters
+        args.extend(profile.filter_flags());
+
+        let output = run_rclone(&profile, "copy", &args, DEFAULT_TIMEOUT).await?;
+
+        if !output.success {
+            return Err(format!("Restore preview failed for {}: {}", full_remote_path, output.stderr).into());
+        }
+
+        let changes = parse_dry_run_output(&output.stderr)?;
+        let remote_sizes = list_file_sizes(&full_remote_path, &profile, &profile.filter_flags()).await?;
+
+        // Whether a file is overwritten or newly created depends on what's already on disk
+        for mut change in changes {
+            change.size = remote_sizes.get(&change.path).copied().unwrap_or(0);
+            change.action = if Path::new(&local_target).join(&change.path).exists() {
+                ChangeAction::Update
+            } else {
+                ChangeAction::Copy
+            };
+            all_changes.push(change);
+        }
+    }
+
+    let files_to_copy: Vec<FileChange> = all_changes.iter()
+        .filter(|c| matches!(c.action, ChangeAction::Copy))
+        .cloned()
+        .collect();
+
+    let files_to_update: Vec<FileChange> = all_changes.iter()
+        .filter(|c| matches!(c.action, ChangeAction::Update))
+        .cloned()
+        .collect();
+
+    let total_files = all_changes.len() as u64;
+    let total_size = all_changes.iter().map(|c| c.size).sum();
+
+    Ok(BackupPreview {
+        files_to_copy,
+        files_to_update,
+        files_to_delete: Vec::new(), // copy never deletes local files
+        total_files,
+        total_size,
+    })
+}
+
+/// Compare the total size of the files a restore would download against free space on
+/// local_target's volume. Files kept by --ignore-existing aren't counted. With `version_at`
+/// the sizes are those of the versions current at that time.
+async fn check_restore_disk_space(profile: &Profile, base_dest: &str, remote_paths: &[String], local_target: &str, overwrite: bool, version_at: Option<DateTime<Utc>>) -> Result<(), String> {
+    let mut needed = 0u64;
+    let mut listing_flags = profile.filter_flags();
+    if let Some(version_at) = version_at {
+        listing_flags.push(format!("--s3-version-at={}", version_at.to_rfc3339()));
+    }
+
+    for remote_path in remote_paths {
+        let full_remote_path = remote_join(base_dest, remote_path);
+        let remote_sizes = list_file_sizes(&full_remote_path, profile, &listing_flags).await?;
+
+        needed += remote_sizes.iter()
+            .filter(|(path, _)| overwrite || !Path::new(local_target).join(path).exists())
+            .map(|(_, size)| size)
+            .sum::<u64>();
+    }
+
+    // The target may not exist yet; measure the volume it will be created on
+    let existing_dir = Path::new(local_target)
+        .ancestors()
+        .find(|dir| dir.exists())
+        .ok_or_else(|| format!("Restore target {} is not on an accessible volume", local_target))?;
+    let available = fs2::available_space(existing_dir)
+        .map_err(|e| format!("Failed to check free disk space: {}", e))?;
+
+    debug!("restore_files - need {} bytes, {} available at {:?}", needed, available, existing_dir);
+
+    if needed > available {
+        return Err(format!(
+            "Not enough disk space: need {}, have {} available at {}",
+            format_size(needed),
+            format_size(available),
+            existing_dir.display()
+        ));
+    }
+
+    Ok(())
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// `resume` picks up an interrupted restore: files already in local_target are kept rather
+/// than downloaded again, the rest are compared by checksum, and the skipped count is reported.
+///
+/// Modification times are always restored: rclone stores them in the S3 object metadata
+/// (X-Amz-Meta-Mtime) on upload and sets them on download. `preserve_metadata` adds
+/// --metadata, which also restores permissions, owner and access/creation times, but only
+/// for files backed up with --metadata in the profile's rclone flags; S3 keeps nothing else.
+/// Extended attributes, ACLs and Windows attributes never round-trip. (--s3-no-head only
+/// skips the check after an upload, so it plays no part in restores.)
+///
+/// `parallelism` copies up to that many of `remote_paths` at once; by default they go one by one.
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn restore_files(app: AppHandle, profile: Profile, remote_paths: Vec<String>, local_target: String, overwrite: bool, force: Option<bool>, resume: Option<bool>, preserve_metadata: Option<bool>, parallelism: Option<u32>) -> Result<BackupOperation, BackupError> {
+    let _run_guard = acquire_profile_run(&profile.id)?;
+
+    // Refuse to start a restore that would fill the disk, unless the caller forces it
+    if !force.unwrap_or(false) {
+        let base_dest = restore_base(&profile);
+        check_restore_disk_space(&profile, &base_dest, &remote_paths, &local_target, overwrite, None).await?;
+    }
+
+    let running = save_running_operation(&profile.id, OperationType::Restore).await;
+    let _live = track_operation(&running, remote_paths.len());
+    let options = RestoreOptions {
+        overwrite,
+        resume: resume.unwrap_or(false),
+        version_at: None,
+        preserve_metadata: preserve_metadata.unwrap_or(false),
+        parallelism: parallelism.unwrap_or(1).max(1) as usize,
+    };
+    let result = run_restore(app, profile, remote_paths, local_target, options, running.id.clone(), running.started_at).await;
+    if let Err(e) = &result {
+        fail_running_operation(running, e).await;
+    }
+    result.map_err(BackupError::from)
+}
+
+/// Point-in-time restore: bring back `remote_paths` as they were when the backup operation
+/// `operation_id` finished, using the bucket's version history (--s3-version-at, rclone
+/// 1.61+). Needs S3 versioning, which setup_aws_infrastructure enables. Like restore_files,
+/// existing local files are kept unless `overwrite` is set, and the restore is refused if
+/// it wouldn't fit on disk unless `force` is set.
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn restore_from_operation(app: AppHandle, operation_id: String, remote_paths: Vec<String>, local_target: String, overwrite: Option<bool>, force: Option<bool>, preserve_metadata: Option<bool>, parallelism: Option<u32>) -> Result<BackupOperation, BackupError> {
+    let config = crate::config::load_config().await?;
+    let backup = config.backup_operations.iter()
+        .find(|op| op.id == operation_id)
+        .ok_or("Backup operation not found")?;
+    if !matches!(backup.operation_type, OperationType::Backup) {
+        return Err("Only backup operations can be restored from".into());
+    }
+    // A failed run still uploaded some files, so its end is a valid point in time too
+    let version_at = backup.completed_at
+        .ok_or("This backup has not finished, so there is no point in time to restore")?;
+    let profile = config.profiles.into_iter()
+        .find(|p| p.id == backup.profile_id)
+        .ok_or("The profile of this backup no longer exists")?;
+
+    let _run_guard = acquire_profile_run(&profile.id)?;
+    debug!("restore_from_operation - restoring {:?} as of {}", remote_paths, version_at);
+
+    let overwrite = overwrite.unwrap_or(false);
+    if !force.unwrap_or(false) {
+        let base_dest = restore_base(&profile);
+        check_restore_disk_space(&profile, &base_dest, &remote_paths, &local_target, overwrite, Some(version_at)).await?;
+    }
+
+    let running = save_running_operation(&profile.id, OperationType::Restore).await;
+    let _live = track_operation(&running, remote_paths.len());
+    let options = RestoreOptions {
+        overwrite,
+        resume: false,
+        version_at: Some(version_at),
+        preserve_metadata: preserve_metadata.unwrap_or(false),
+        parallelism: parallelism.unwrap_or(1).max(1) as usize,
+    };
+    let result = run_restore(app, profile, remote_paths, local_target, options, running.id.clone(), running.started_at).await;
+    if let Err(e) = &result {
+        fail_running_operation(running, e).await;
+    }
+    result.map_err(BackupError::from)
+}
 
-    for source in &profile.sources {
-        // Resolve the actual rclone binary path
-        let rclone_binary = resolve_rclone_binary(&profile.rclone_bin)?;
+/// How run_restore copies files; see restore_files and restore_from_operation
+struct RestoreOptions {
+    overwrite: bool,
+    resume: bool,
+    version_at: Option<DateTime<Utc>>,
+    preserve_metadata: bool,
+    parallelism: usize, // Remote paths copied at the same time
+}
 
-        // Debug: Check if rclone binary exists
-        if !Path::new(&rclone_binary).exists() && rclone_binary != "rclone" {
-            return Err(format!("Rclone binary not found at path: {}", rclone_binary));
-        }
+/// How often a restore polls rclone's rc for progress
+const RESTORE_PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What the remote paths of one run_restore share
+struct RestoreRun {
+    app: AppHandle,
+    profile: Profile,
+    base_dest: String,
+    local_target: String,
+    options: RestoreOptions,
+    operation_id: String,
+    path_count: usize,
+    stop: AtomicBool, // Set once a path fails, so queued paths aren't started
+}
 
-        // Debug: Check if source directory exists
-        if !Path::new(source).exists() {
-            return Err(format!("Source directory not found: {}", source));
-        }
+/// One remote path's finished copy; the counts are 0 when it failed
+struct PathRestore {
+    full_remote_path: String,
+    output: RcloneOutput,
+    files: u64,
+    bytes: u64,
+    skipped: u64,
+}
 
-        // Debug: Check if rclone config exists
-        if !Path::new(&profile.rclone_conf).exists() {
-            return Err(format!("Rclone config not found at path: {}", profile.rclone_conf));
-        }
+/// Aborts the tasks that are still running when dropped, which kills their rclone
+struct AbortOnDrop<T>(Vec<JoinHandle<T>>);
 
-        // Extract the folder name from the source path to preserve folder structure
-        // E.g., /Users/john/Documents -> Documents
-        let source_folder_name = Path::new(source)
-            .file_name()
-            .and_then(|name| name.to_str())
-            .ok_or_else(|| format!("Invalid source path: {}", source))?;
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        for handle in &self.0 {
+            handle.abort();
+        }
+    }
+}
 
-        // Append the source folder name to the destination to isolate each source
-        // E.g., aws:bucket/users/john-id/Documents
-        let destination_with_folder = format!("{}/{}", destination, source_folder_name);
+async fn run_restore(
+    app: AppHandle,
+    profile: Profile,
+    remote_paths: Vec<String>,
+    local_target: String,
+    options: RestoreOptions,
+    operation_id: String,
+    started_at: DateTime<Utc>,
+) -> Result<BackupOperation, String> {
+    let base_dest = restore_base(&profile);
+
+    debug!("restore_files - Profile type: {:?}", profile.profile_type);
+    debug!("restore_files - base_dest: {}", base_dest);
+    debug!("restore_files - remote_paths: {:?}, {} at a time", remote_paths, options.parallelism);
+
+    // Paths run concurrently up to the parallelism setting; with 1 they run in turn
+    let semaphore = Arc::new(Semaphore::new(options.parallelism.max(1)));
+    let run = Arc::new(RestoreRun {
+        app,
+        profile,
+        base_dest,
+        local_target,
+        options,
+        operation_id,
+        path_count: remote_paths.len(),
+        stop: AtomicBool::new(false),
+    });
 
-        let mut args = vec![
-            operation.to_string(),
-            source.clone(),
-            destination_with_folder.clone(),
-            "--config".to_string(),
-            profile.rclone_conf.clone(),
-            "--progress".to_string(),
-            "--stats=1s".to_string(),
-            "--stats-one-line".to_string(),
-            "-v".to_string(), // Verbose mode to log file operations
-        ];
+    let mut tasks = AbortOnDrop(Vec::new());
+    for (path_index, remote_path) in remote_paths.iter().enumerate() {
+        let semaphore = semaphore.clone();
+        let run = run.clone();
+        let remote_path = remote_path.clone();
 
-        if dry_run {
-            args.push("--dry-run".to_string());
-        }
+        tasks.0.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.map_err(|e| e.to_string())?;
+            if run.stop.load(Ordering::SeqCst) {
+                return Ok(None);
+            }
+            if shutdown_requested() {
+                return Err(SHUTDOWN_MESSAGE.to_string());
+            }
 
-        // Add custom flags
-        for flag in &profile.rclone_flags {
-            args.push(flag.clone());
-        }
+            let result = restore_path(&run, path_index, &remote_path).await;
+            if !matches!(&result, Ok(restored) if restored.output.success) {
+                run.stop.store(true, Ordering::SeqCst);
+            }
+            result.map(Some)
+        }));
+    }
 
-        let output = create_command(&rclone_binary)
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await
-            .map_err(|e| format!("Failed to execute rclone command '{}' with args {:?}: {}", rclone_binary, args, e))?;
+    // Await in path order so the log reads the same at any parallelism. Returning early
+    // drops `tasks`, which stops the paths still copying.
+    let mut combined_output = String::new();
+    let mut total_files = 0u64;
+    let mut total_bytes = 0u64;
+    let mut total_skipped = 0u64;
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
+    for (remote_path, handle) in remote_paths.iter().zip(tasks.0.iter_mut()) {
+        let result = handle.await.map_err(|e| format!("Restore task failed: {}", e))??;
+        // Not started because a path failed; that failure is reported when its turn comes
+        let Some(restored) = result else { continue };
+        let full_remote_path = &restored.full_remote_path;
+        let stdout = &restored.output.stdout;
+        let stderr = &restored.output.stderr;
 
-        println!("[DEBUG] ===== STDOUT for {} =====", source);
-        println!("{}", stdout);
-        println!("[DEBUG] ===== STDERR for {} =====", source);
-        println!("{}", stderr);
-        println!("[DEBUG] ===== END OUTPUT =====");
+        trace!("rclone output for restore {}\n----- stdout -----\n{}\n----- stderr -----\n{}", remote_path, stdout, stderr);
 
-        combined_output.push_str(&format!("=== Source: {} ===\n", source));
-        combined_output.push_str(&stdout);
-        combined_output.push_str(&stderr);
-        combined_output.push_str("\n");
+        combined_output.push_str(&format!("=== Restoring: {} ===\n", remote_path));
+        combined_output.push_str(stdout);
+        combined_output.push_str(stderr);
+        combined_output.push('\n');
+
+        // Retrying can't help until S3 has thawed the archived objects, so fail with what to do
+        if !restored.output.success && ARCHIVED_OBJECT_MARKERS.iter().any(|m| stderr.contains(m)) {
+            warn!("restore_files - {} has archived objects:\n{}", full_remote_path, stderr);
+            let in_progress = match glacier_restore_status(&run.profile, remote_path).await {
+                Ok(status) => status.in_progress,
+                Err(e) => {
+                    warn!("Could not read the restore status of {}: {}", remote_path, e);
+                    0
+                }
+            };
+            return Err(if in_progress > 0 {
+                format!(
+                    "{} file(s) in {} are still being restored from archive. Check the restore status and try again once it is ready.",
+                    in_progress, remote_path
+                )
+            } else {
+                format!(
+                    "Some files in {} are in Glacier or Deep Archive and must be restored from archive before they can be downloaded. \
+                     Request an archive restore for them, wait for it to finish \
+                     (up to 12 hours for Glacier, 48 hours for Deep Archive), then restore again.",
+                    remote_path
+                )
+            });
+        }
 
-        if !output.status.success() && !dry_run {
+        if !restored.output.success {
             let failed_operation = BackupOperation {
-                id: operation_id,
-                profile_id: profile.id,
-                operation_type: OperationType::Backup,
+                id: run.operation_id.clone(),
+                profile_id: run.profile.id.clone(),
+                operation_type: OperationType::Restore,
                 status: OperationStatus::Failed,
                 started_at,
                 completed_at: Some(Utc::now()),
                 files_transferred: total_files,
                 bytes_transferred: total_bytes,
-                error_message: Some(format!("rclone {} failed for {}: {}", operation, source, stderr)),
+                files_skipped: total_skipped,
+                error_message: Some(format!("restore failed for {}: {}", full_remote_path, stderr)),
                 log_output: combined_output,
+                repeat_count: 1,
+                first_started_at: None,
             };
 
             // Save the failed operation to config
             if let Err(e) = crate::config::save_backup_operation(failed_operation.clone()).await {
-                eprintln!("Failed to save backup operation: {}", e);
+                error!("Failed to save restore operation: {}", e);
             }
 
             return Ok(failed_operation);
         }
 
-        // Parse stats from output - rclone outputs to stdout with --stats-one-line and -v
-        // Parse both bytes and file count from stdout
-        let (files_from_operations, _) = parse_rclone_file_operations(&stdout);
-        if let Some((_, bytes)) = parse_rclone_stats(&stdout) {
-            println!("[DEBUG] Parsed rclone stats for source {}: {} files, {} bytes", source, files_from_operations, bytes);
-            total_files += files_from_operations;
-            total_bytes += bytes;
-        } else {
-            println!("[DEBUG] Could not parse rclone stats from stdout for source: {}", source);
-        }
+        total_files += restored.files;
+        total_bytes += restored.bytes;
+        total_skipped += restored.skipped;
     }
 
     let operation = BackupOperation {
-        id: operation_id,
-        profile_id: profile.id.clone(),
-        operation_type: OperationType::Backup,
+        id: run.operation_id.clone(),
+        profile_id: run.profile.id.clone(),
+        operation_type: OperationType::Restore,
         status: OperationStatus::Completed,
         started_at,
         completed_at: Some(Utc::now()),
         files_transferred: total_files,
         bytes_transferred: total_bytes,
+        files_skipped: total_skipped,
         error_message: None,
         log_output: combined_output,
+        repeat_count: 1,
+        first_started_at: None,
     };
 
-    println!("[DEBUG] Manual backup completed - files: {}, bytes: {}", total_files, total_bytes);
+    debug!("Restore completed - files: {}, bytes: {}, skipped: {}", total_files, total_bytes, total_skipped);
 
     // Save the operation to config
     if let Err(e) = crate::config::save_backup_operation(operation.clone()).await {
-        eprintln!("Failed to save backup operation: {}", e);
+        error!("Failed to save restore operation: {}", e);
     }
 
-    // Update the schedule's last_run and next_run after manual backup
-    if let Err(e) = crate::config::update_schedule_after_backup(&profile.id, started_at).await {
-        eprintln!("Failed to update schedule after backup: {}", e);
+    Ok(operation)
+}
+
+/// Forward one path's progress to the UI and the live operation store, if it changed
+fn report_restore_progress(run: &RestoreRun, progress: &mut RestoreProgress, line: ProgressLine) {
+    let (bytes_done, bytes_total, percent, speed, eta) = line;
+    // rclone repeats identical stats every tick; only forward changes
+    if bytes_done == progress.bytes_done && percent == progress.percent && bytes_total == progress.bytes_total {
+        return;
     }
 
-    Ok(operation)
+    // Other paths may be copying at the same time, so the live store is moved by this
+    // path's change rather than set to its figures
+    update_operation(&run.operation_id, |live| {
+        live.bytes_done = (live.bytes_done + bytes_done).saturating_sub(progress.bytes_done);
+        live.bytes_total = (live.bytes_total + bytes_total).saturating_sub(progress.bytes_total);
+        live.percent = (live.bytes_done * 100).checked_div(live.bytes_total).unwrap_or(0).min(100) as u32;
+    });
+    progress.bytes_done = bytes_done;
+    progress.bytes_total = bytes_total;
+    progress.percent = percent;
+    progress.speed = speed;
+    progress.eta = eta;
+    let _ = run.app.emit("restore-progress", &*progress);
 }
 
-#[command]
-pub async fn restore_files(profile: Profile, remote_paths: Vec<String>, local_target: String) -> Result<BackupOperation, String> {
-    let operation_id = uuid::Uuid::new_v4().to_string();
-    let started_at = Utc::now();
+/// Copy one of run_restore's remote paths into the restore target, reporting its progress
+async fn restore_path(run: &RestoreRun, path_index: usize, remote_path: &str) -> Result<PathRestore, String> {
+    let RestoreRun { app, profile, base_dest, local_target, options, operation_id, path_count, .. } = run;
+    let full_remote_path = remote_join(base_dest, remote_path);
+    debug!("restore_files - Attempting to restore from: {}", full_remote_path);
+
+    let mut progress = RestoreProgress {
+        operation_id: operation_id.clone(),
+        remote_path: remote_path.to_string(),
+        path_index,
+        path_count: *path_count,
+        bytes_done: 0,
+        bytes_total: 0,
+        percent: 0,
+        speed: None,
+        eta: None,
+    };
+    let _ = app.emit("restore-progress", &progress);
+    update_operation(operation_id, |live| live.current_item = Some(remote_path.to_string()));
 
-    // For admin users, allow restoring from entire bucket (not restricted to their prefix)
-    // For regular users, restrict to their prefix
-    let base_dest = if matches!(profile.profile_type, crate::models::ProfileType::Admin) {
-        // Admin can restore from entire bucket
-        format!("{}:{}", profile.remote, profile.bucket)
+    let mut args = vec![
+        full_remote_path.clone(),
+        local_target.clone(),
+        "--progress".to_string(),
+        "--fast-list".to_string(),
+    ];
+    args.extend(profile.stats_flags());
+    args.extend(profile.log_flags());
+
+    args.push(format!("--transfers={}", profile.transfers));
+    args.push(format!("--checkers={}", profile.checkers));
+    args.extend(profile.retry_flags());
+    if profile.symlink_mode == SymlinkMode::Copy {
+        args.extend(profile.symlink_flags());
+    }
+    if options.resume {
+        args.push("--checksum".to_string());
     } else {
-        // Regular users restore only from their prefix
-        profile.destination()
+        args.extend(profile.hash_flags());
+    }
+
+    // Preserve local files that already exist; when resuming these are the files
+    // the interrupted run already finished
+    if !options.overwrite || options.resume {
+        args.push("--ignore-existing".to_string());
+    }
+
+    // Add include/exclude filters
+    args.extend(profile.filter_flags());
+
+    if options.preserve_metadata {
+        args.push("--metadata".to_string());
+    }
+
+    // Read the object versions that were current at that time instead of the latest
+    if let Some(version_at) = options.version_at {
+        args.push(format!("--s3-version-at={}", version_at.to_rfc3339()));
+    }
+
+    // Count the source up front so skipped = source files - transferred
+    let source_files = if options.resume {
+        match list_file_sizes(&full_remote_path, profile, &profile.filter_flags()).await {
+            Ok(sizes) => Some(sizes.len() as u64),
+            Err(e) => {
+                warn!("Could not list {} to count skipped files: {}", full_remote_path, e);
+                None
+            }
+        }
+    } else {
+        None
     };
 
-    println!("[DEBUG] restore_files - Profile type: {:?}", profile.profile_type);
-    println!("[DEBUG] restore_files - base_dest: {}", base_dest);
-    println!("[DEBUG] restore_files - remote_paths: {:?}", remote_paths);
+    // Progress is polled from rc so it keeps moving however long stats_interval_secs is;
+    // the --stats lines are only parsed once no rc port could be had
+    let mut rc_attempts = 0;
+    let output = loop {
+        let rc_endpoint = if rc_attempts < RC_PORT_ATTEMPTS {
+            register_rc_endpoint(operation_id)
+                .map_err(|e| warn!("Restore progress falls back to rclone's stats lines: {}", e))
+                .ok()
+        } else {
+            None
+        };
+        rc_attempts += 1;
+        let mut args = args.clone();
+        if let Some(endpoint) = &rc_endpoint {
+            args.extend(endpoint.flags());
+        }
 
-    let mut combined_output = String::new();
-    let mut total_files = 0u64;
-    let mut total_bytes = 0u64;
+        let output = match &rc_endpoint {
+            Some(endpoint) => {
+                let transfer = run_rclone(profile, "copy", &args, profile.transfer_timeout());
+                tokio::pin!(transfer);
+                let mut ticker = tokio::time::interval(RESTORE_PROGRESS_INTERVAL);
+                loop {
+                    tokio::select! {
+                        output = &mut transfer => break output?,
+                        _ = ticker.tick() => {
+                            // Not listening yet while rclone starts up; picked up next tick
+                            if let Ok(stats) = endpoint.stats().await {
+                                let percent = (stats.bytes * 100).checked_div(stats.total_bytes).unwrap_or(0).min(100) as u32;
+                                let speed = (stats.speed > 0.0).then(|| format!("{}/s", format_byte_size(stats.speed)));
+                                let eta = stats.eta.map(|secs| format!("{}s", secs));
+                                report_restore_progress(run, &mut progress, (stats.bytes, stats.total_bytes, percent, speed, eta));
+                            }
+                        }
+                    }
+                }
+            }
+            None => run_rclone_streaming(profile, "copy", &args, profile.transfer_timeout(), |line| {
+                if let Some(line) = parse_progress_line(line) {
+                    report_restore_progress(run, &mut progress, line);
+                }
+            }).await?,
+        };
+
+        if rc_endpoint.is_some() && rc_start_failed(&output) {
+            warn!("rclone could not serve rc on its port, retrying the restore of {}", full_remote_path);
+            continue;
+        }
+        break output;
+    };
 
-    for remote_path in remote_paths {
-        let full_remote_path = format!("{}/{}", base_dest, remote_path.trim_start_matches('/'));
-        println!("[DEBUG] restore_files - Attempting to restore from: {}", full_remote_path);
-        
-        let args = vec![
-            "copy".to_string(),
-            full_remote_path.clone(),
-            local_target.clone(),
-            "--config".to_string(),
-            profile.rclone_conf.clone(),
-            "--progress".to_string(),
-            "--stats=1s".to_string(),
-            "--stats-one-line".to_string(),
-            "-v".to_string(), // Verbose mode to log file operations
-            "--checksum".to_string(),
-            "--fast-list".to_string(),
-        ];
+    if !output.success {
+        return Ok(PathRestore { full_remote_path, output, files: 0, bytes: 0, skipped: 0 });
+    }
 
-        let rclone_binary = resolve_rclone_binary(&profile.rclone_bin)?;
-        let output = create_command(&rclone_binary)
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await
-            .map_err(|e| e.to_string())?;
+    // Parse stats from output - rclone outputs to stdout with --stats-one-line and -v
+    // Parse both bytes and file count from stdout
+    let (files_from_operations, _) = parse_rclone_file_operations(&output.stdout);
+    let (files, bytes) = match parse_rclone_stats(&output.stdout) {
+        Some((_, bytes)) => {
+            debug!("Parsed rclone stats for restore {}: {} files, {} bytes", remote_path, files_from_operations, bytes);
+            (files_from_operations, bytes)
+        }
+        None => {
+            debug!("Could not parse rclone stats from stdout for restore: {}", remote_path);
+            (0, 0)
+        }
+    };
+    update_operation(operation_id, |live| {
+        live.items_done += 1;
+        live.files_done += files;
+        // The final stats replace whatever the last progress line reported
+        live.bytes_done = (live.bytes_done + bytes).saturating_sub(progress.bytes_done);
+    });
+    let skipped = source_files.map_or(0, |source_files| source_files.saturating_sub(files_from_operations));
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
+    Ok(PathRestore { full_remote_path, output, files, bytes, skipped })
+}
 
-        println!("[DEBUG] ===== STDOUT for restore {} =====", remote_path);
-        println!("{}", stdout);
-        println!("[DEBUG] ===== STDERR for restore {} =====", remote_path);
-        println!("{}", stderr);
-        println!("[DEBUG] ===== END OUTPUT =====");
+/// Path of `path` under the restore root on the underlying s3 remote. Restore requests only
+/// work there, so under a crypt remote the path is encrypted first.
+async fn archive_path(profile: &Profile, path: &str) -> Result<String, String> {
+    let path = path.trim_matches('/');
+    if !profile.encryption_enabled {
+        return Ok(remote_join(&restore_base(profile), path));
+    }
+    if path.is_empty() {
+        return Ok(profile.storage_path());
+    }
 
-        combined_output.push_str(&format!("=== Restoring: {} ===\n", remote_path));
-        combined_output.push_str(&stdout);
-        combined_output.push_str(&stderr);
-        combined_output.push_str("\n");
+    let args = vec!["encode".to_string(), profile.destination(), path.to_string()];
+    let output = run_rclone(profile, "backend", &args, QUICK_TIMEOUT).await?;
+    if !output.success {
+        return Err(format!("Failed to encrypt the path {}: {}", path, output.stderr));
+    }
+    // Printed as a JSON array of the encoded names
+    let encoded = serde_json::from_str::<Vec<String>>(&output.stdout)
+        .ok()
+        .and_then(|names| names.into_iter().next())
+        .ok_or_else(|| format!("Unexpected rclone backend encode output: {}", output.stdout.trim()))?;
+    Ok(remote_join(&profile.storage_path(), &encoded))
+}
 
-        if !output.status.success() {
-            let failed_operation = BackupOperation {
-                id: operation_id,
-                profile_id: profile.id,
-                operation_type: OperationType::Restore,
-                status: OperationStatus::Failed,
-                started_at,
-                completed_at: Some(Utc::now()),
-                files_transferred: total_files,
-                bytes_transferred: total_bytes,
-                error_message: Some(format!("restore failed for {}: {}", full_remote_path, stderr)),
-                log_output: combined_output,
-            };
+/// Ask S3 to thaw the Glacier/Deep Archive objects under `paths` (relative to the restore
+/// root, files or folders) so restore_files can download them. The thawed copies are kept
+/// for `days` days; when they are ready is up to the tier, see `estimated_thaw`.
+#[command]
+pub async fn request_glacier_restore(profile: Profile, paths: Vec<String>, tier: GlacierRestoreTier, days: u32) -> Result<GlacierRestoreRequest, BackupError> {
+    if days == 0 {
+        return Err("Restored copies must be kept for at least 1 day".into());
+    }
 
-            // Save the failed operation to config
-            if let Err(e) = crate::config::save_backup_operation(failed_operation.clone()).await {
-                eprintln!("Failed to save restore operation: {}", e);
+    let mut request = GlacierRestoreRequest {
+        tier,
+        days,
+        requested: 0,
+        already_in_progress: 0,
+        not_archived: 0,
+        failed: Vec::new(),
+        estimated_thaw: tier.estimated_thaw().to_string(),
+    };
+
+    for path in &paths {
+        let target = archive_path(&profile, path).await?;
+        let args = vec![
+            "restore".to_string(),
+            target.clone(),
+            format!("-o=priority={}", tier.as_str()),
+            format!("-o=lifetime={}", days),
+        ];
+        let output = run_rclone(&profile, "backend", &args, DEFAULT_TIMEOUT).await?;
+        if !output.success {
+            return Err(format!("Failed to request a restore for {}: {}", path, output.stderr).into());
+        }
+
+        // One {"Remote": ..., "Status": ...} per object; Status is "OK" or S3's error
+        let items: Vec<Value> = serde_json::from_str(&output.stdout)?;
+        for item in items {
+            let status = item["Status"].as_str().unwrap_or("");
+            if status == "OK" {
+                request.requested += 1;
+            } else if status.contains("RestoreAlreadyInProgress") {
+                request.already_in_progress += 1;
+            } else if status.contains("InvalidObjectState") {
+                request.not_archived += 1;
+            } else {
+                request.failed.push(format!("{}: {}", item["Remote"].as_str().unwrap_or(path), status));
             }
+        }
+    }
 
-            return Ok(failed_operation);
+    info!(
+        "Requested {} restore for {} objects ({} already in progress, {} failed)",
+        tier.as_str(), request.requested, request.already_in_progress, request.failed.len()
+    );
+    Ok(request)
+}
+
+/// Whether the archived objects under `path` have been thawed and can be downloaded
+#[command]
+pub async fn check_restore_status(profile: Profile, path: String) -> Result<GlacierRestoreStatus, BackupError> {
+    Ok(glacier_restore_status(&profile, &path).await?)
+}
+
+async fn glacier_restore_status(profile: &Profile, path: &str) -> Result<GlacierRestoreStatus, String> {
+    let target = archive_path(profile, path).await?;
+    let args = vec!["restore-status".to_string(), target, "-o=all".to_string()];
+    let output = run_rclone(profile, "backend", &args, DEFAULT_TIMEOUT).await?;
+    if !output.success {
+        return Err(format!("Failed to read the restore status of {}: {}", path, output.stderr));
+    }
+
+    let items: Vec<Value> = serde_json::from_str(&output.stdout)
+        .map_err(|e| format!("Failed to parse rclone output: {}", e))?;
+
+    let mut status = GlacierRestoreStatus {
+        path: path.to_string(),
+        archived_files: 0,
+        not_requested: 0,
+        in_progress: 0,
+        restored: 0,
+        restored_until: None,
+        ready: false,
+    };
+    for item in &items {
+        // Glacier Instant Retrieval downloads directly, so it isn't counted
+        if !matches!(item["StorageClass"].as_str(), Some("GLACIER") | Some("DEEP_ARCHIVE")) {
+            continue;
         }
+        status.archived_files += 1;
 
-        // Parse stats from output - rclone outputs to stdout with --stats-one-line and -v
-        // Parse both bytes and file count from stdout
-        let (files_from_operations, _) = parse_rclone_file_operations(&stdout);
-        if let Some((_, bytes)) = parse_rclone_stats(&stdout) {
-            println!("[DEBUG] Parsed rclone stats for restore {}: {} files, {} bytes", remote_path, files_from_operations, bytes);
-            total_files += files_from_operations;
-            total_bytes += bytes;
+        let restore = &item["RestoreStatus"];
+        if restore.is_null() {
+            status.not_requested += 1;
+        } else if restore["IsRestoreInProgress"].as_bool().unwrap_or(false) {
+            status.in_progress += 1;
         } else {
-            println!("[DEBUG] Could not parse rclone stats from stdout for restore: {}", remote_path);
+            status.restored += 1;
+            if let Some(expiry) = restore["RestoreExpiryDate"].as_str() {
+                // RFC 3339 timestamps in UTC sort as strings
+                if status.restored_until.as_deref().is_none_or(|until| expiry < until) {
+                    status.restored_until = Some(expiry.to_string());
+                }
+            }
         }
     }
+    status.ready = status.restored == status.archived_files;
 
-    let operation = BackupOperation {
-        id: operation_id,
-        profile_id: profile.id,
-        operation_type: OperationType::Restore,
-        status: OperationStatus::Completed,
-        started_at,
-        completed_at: Some(Utc::now()),
-        files_transferred: total_files,
-        bytes_transferred: total_bytes,
-        error_message: None,
-        log_output: combined_output,
+    Ok(status)
+}
+
+#[command]
+pub async fn list_file_versions(profile: Profile, path: String) -> Result<Vec<FileVersion>, BackupError> {
+    use regex::Regex;
+
+    let path = path.trim_start_matches('/').to_string();
+    let (parent, file_name) = match path.rsplit_once('/') {
+        Some((parent, file_name)) => (parent.to_string(), file_name.to_string()),
+        None => (String::new(), path.clone()),
+    };
+
+    let base = restore_base(&profile);
+    let target = remote_join(&base, &parent);
+
+    let args = vec![
+        target,
+        "--s3-versions".to_string(),
+        "--files-only".to_string(),
+    ];
+
+    let output = run_rclone(&profile, "lsjson", &args, DEFAULT_TIMEOUT).await?;
+
+    if !output.success {
+        return Err(output.stderr.into());
+    }
+
+    let items: Vec<Value> = serde_json::from_str(&output.stdout)
+        .map_err(|e| format!("Failed to parse rclone output: {}", e))?;
+
+    // Old versions are listed as "{stem}-vYYYY-MM-DD-HHMMSS-mmm{ext}" next to the current file
+    let (stem, extension) = match file_name.rfind('.') {
+        Some(index) if index > 0 => (&file_name[..index], &file_name[index..]),
+        _ => (file_name.as_str(), ""),
+    };
+    let version_regex = Regex::new(&format!(
+        r"^{}-v\d{{4}}-\d{{2}}-\d{{2}}-\d{{6}}-\d{{3}}{}$",
+        regex::escape(stem),
+        regex::escape(extension)
+    )).map_err(|e| e.to_string())?;
+
+    let mut versions = Vec::new();
+    for item in items {
+        if let Some(file) = parse_rclone_item(&item)? {
+            let is_current = file.name == file_name;
+            if is_current || version_regex.is_match(&file.name) {
+                versions.push(FileVersion {
+                    version_id: file.name,
+                    path: path.clone(),
+                    size: file.size,
+                    mod_time: file.mod_time,
+                    is_current,
+                });
+            }
+        }
+    }
+
+    // Newest first
+    versions.sort_by_key(|version| std::cmp::Reverse(version.mod_time));
+    Ok(versions)
+}
+
+#[command]
+pub async fn restore_version(profile: Profile, path: String, version_id: String, local_target: String) -> Result<BackupOperation, BackupError> {
+    let _run_guard = acquire_profile_run(&profile.id)?;
+    let operation_id = uuid::Uuid::new_v4().to_string();
+    let started_at = Utc::now();
+
+    let path = path.trim_start_matches('/').to_string();
+    let (parent, file_name) = match path.rsplit_once('/') {
+        Some((parent, file_name)) => (parent.to_string(), file_name.to_string()),
+        None => (String::new(), path.clone()),
     };
 
-    println!("[DEBUG] Restore completed - files: {}, bytes: {}", total_files, total_bytes);
+    let base = restore_base(&profile);
+    let versioned_remote_path = remote_join(&remote_join(&base, &parent), &version_id);
+
+    // Restore under the original file name rather than the versioned one
+    let local_file = Path::new(&local_target).join(&file_name).to_string_lossy().to_string();
+
+    let mut args = vec![
+        versioned_remote_path.clone(),
+        local_file,
+        "--s3-versions".to_string(),
+    ];
+    args.extend(profile.stats_flags());
+    args.extend(profile.log_flags());
+
+    let output = run_rclone(&profile, "copyto", &args, profile.transfer_timeout()).await?;
+    let stdout = &output.stdout;
+    let stderr = &output.stderr;
+    let log_output = format!("=== Restoring version: {} ===\n{}{}\n", version_id, stdout, stderr);
+
+    let operation = if output.success {
+        let bytes = parse_rclone_stats(stdout).map(|(_, bytes)| bytes).unwrap_or(0);
+        BackupOperation {
+            id: operation_id,
+            profile_id: profile.id,
+            operation_type: OperationType::Restore,
+            status: OperationStatus::Completed,
+            started_at,
+            completed_at: Some(Utc::now()),
+            files_transferred: 1,
+            bytes_transferred: bytes,
+            files_skipped: 0,
+            error_message: None,
+            log_output,
+            repeat_count: 1,
+            first_started_at: None,
+        }
+    } else {
+        BackupOperation {
+            id: operation_id,
+            profile_id: profile.id,
+            operation_type: OperationType::Restore,
+            status: OperationStatus::Failed,
+            started_at,
+            completed_at: Some(Utc::now()),
+            files_transferred: 0,
+            bytes_transferred: 0,
+            files_skipped: 0,
+            error_message: Some(format!("restore failed for {}: {}", versioned_remote_path, stderr)),
+            log_output,
+            repeat_count: 1,
+            first_started_at: None,
+        }
+    };
 
     // Save the operation to config
     if let Err(e) = crate::config::save_backup_operation(operation.clone()).await {
-        eprintln!("Failed to save restore operation: {}", e);
+        error!("Failed to save restore operation: {}", e);
     }
 
     Ok(operation)
@@ -645,7 +3304,7 @@ fn parse_rclone_file_operations(output: &str) -> (u64, u64) {
         }
     }
 
-    println!("[DEBUG] Parsed file operations from stderr: {} copied, {} deleted", files_copied, files_deleted);
+    debug!("Parsed file operations from stderr: {} copied, {} deleted", files_copied, files_deleted);
     (files_copied, files_deleted)
 }
 
@@ -655,7 +3314,7 @@ fn parse_rclone_stats(output: &str) -> Option<(u64, u64)> {
     // Rclone with --stats-one-line outputs like:
     // "66 B / 66 B, 100%, 0 B/s, ETA -"
     // "1.234 MiB / 2.468 MiB, 50%, 1.5 MiB/s, ETA 1s"
-    // Also handle verbose format with "Transferred:" prefix
+    // Also handle the detailed format (profile.detailed_stats) with "Transferred:" prefix
 
     let stats_one_line_regex = Regex::new(r"^\s*([0-9.,]+\s*[KMGT]?i?B)\s*/\s*([0-9.,]+\s*[KMGT]?i?B)\s*,\s*(\d+)%").ok()?;
     let transferred_regex = Regex::new(r"Transferred:\s+([0-9.,]+\s*[KMGT]?i?B)\s*/\s*([0-9.,]+\s*[KMGT]?i?B)").ok()?;
@@ -664,13 +3323,13 @@ fn parse_rclone_stats(output: &str) -> Option<(u64, u64)> {
     let files_transferred = 0u64;
 
     for line in output.lines() {
-        println!("[DEBUG] Parsing line: {}", line);
+        debug!("Parsing line: {}", line);
 
         // Try stats-one-line format first (most common with current flags)
         if let Some(caps) = stats_one_line_regex.captures(line) {
             let bytes_str = &caps[1];
             if let Ok(bytes) = parse_byte_size(bytes_str) {
-                println!("[DEBUG] Parsed byte size from stats-one-line '{}': {} bytes", bytes_str, bytes);
+                debug!("Parsed byte size from stats-one-line '{}': {} bytes", bytes_str, bytes);
                 bytes_transferred = bytes;
             }
         }
@@ -678,7 +3337,7 @@ fn parse_rclone_stats(output: &str) -> Option<(u64, u64)> {
         else if let Some(caps) = transferred_regex.captures(line) {
             let bytes_str = &caps[1];
             if let Ok(bytes) = parse_byte_size(bytes_str) {
-                println!("[DEBUG] Parsed byte size from Transferred line '{}': {} bytes", bytes_str, bytes);
+                debug!("Parsed byte size from Transferred line '{}': {} bytes", bytes_str, bytes);
                 bytes_transferred = bytes;
             }
         }
@@ -689,84 +3348,239 @@ fn parse_rclone_stats(output: &str) -> Option<(u64, u64)> {
     // A better approach would be to count "Copied (new)" or similar messages in verbose output
 
     if bytes_transferred > 0 {
-        println!("[DEBUG] Returning stats: files={}, bytes={}", files_transferred, bytes_transferred);
+        debug!("Returning stats: files={}, bytes={}", files_transferred, bytes_transferred);
         Some((files_transferred, bytes_transferred))
     } else {
-        println!("[DEBUG] No stats found in output");
+        debug!("No stats found in output");
         None
     }
 }
 
-fn parse_byte_size(size_str: &str) -> Result<u64, String> {
-    let size_str = size_str.replace(",", "").replace(" ", "");
-
-    if size_str.ends_with("KiB") {
-        let num: f64 = size_str.trim_end_matches("KiB").parse().map_err(|e: std::num::ParseFloatError| e.to_string())?;
-        Ok((num * 1024.0) as u64)
-    } else if size_str.ends_with("KB") {
-        let num: f64 = size_str.trim_end_matches("KB").parse().map_err(|e: std::num::ParseFloatError| e.to_string())?;
-        Ok((num * 1000.0) as u64)
-    } else if size_str.ends_with("MiB") {
-        let num: f64 = size_str.trim_end_matches("MiB").parse().map_err(|e: std::num::ParseFloatError| e.to_string())?;
-        Ok((num * 1024.0 * 1024.0) as u64)
-    } else if size_str.ends_with("MB") {
-        let num: f64 = size_str.trim_end_matches("MB").parse().map_err(|e: std::num::ParseFloatError| e.to_string())?;
-        Ok((num * 1000.0 * 1000.0) as u64)
-    } else if size_str.ends_with("GiB") {
-        let num: f64 = size_str.trim_end_matches("GiB").parse().map_err(|e: std::num::ParseFloatError| e.to_string())?;
-        Ok((num * 1024.0 * 1024.0 * 1024.0) as u64)
-    } else if size_str.ends_with("GB") {
-        let num: f64 = size_str.trim_end_matches("GB").parse().map_err(|e: std::num::ParseFloatError| e.to_string())?;
-        Ok((num * 1000.0 * 1000.0 * 1000.0) as u64)
-    } else if size_str.ends_with("TiB") {
-        let num: f64 = size_str.trim_end_matches("TiB").parse().map_err(|e: std::num::ParseFloatError| e.to_string())?;
-        Ok((num * 1024.0 * 1024.0 * 1024.0 * 1024.0) as u64)
-    } else if size_str.ends_with("TB") {
-        let num: f64 = size_str.trim_end_matches("TB").parse().map_err(|e: std::num::ParseFloatError| e.to_string())?;
-        Ok((num * 1000.0 * 1000.0 * 1000.0 * 1000.0) as u64)
-    } else if size_str.ends_with("B") {
-        let num: u64 = size_str.trim_end_matches("B").parse().map_err(|e: std::num::ParseIntError| e.to_string())?;
-        Ok(num)
-    } else {
-        size_str.parse::<u64>().map_err(|e| e.to_string())
-    }
-}
-
 #[command]
-pub async fn get_backup_logs(profile_id: String, limit: Option<usize>) -> Result<Vec<BackupOperation>, String> {
+pub async fn get_backup_logs(profile_id: String, limit: Option<usize>, offset: Option<usize>) -> Result<BackupLogsPage, BackupError> {
     let config = crate::config::load_config().await?;
 
-    println!("[DEBUG] get_backup_logs called for profile_id: {}", profile_id);
-    println!("[DEBUG] Total operations in config: {}", config.backup_operations.len());
+    debug!("get_backup_logs called for profile_id: {}", profile_id);
+    debug!("Total operations in config: {}", config.backup_operations.len());
 
-    // Filter operations for the specific profile and apply limit
+    // Filter operations for the specific profile
     let mut operations: Vec<BackupOperation> = config.backup_operations
         .into_iter()
-        .filter(|op| {
-            let matches = op.profile_id == profile_id;
-            if !matches {
-                println!("[DEBUG] Filtering out operation with profile_id: {}", op.profile_id);
-            }
-            matches
-        })
+        .filter(|op| op.profile_id == profile_id)
         .collect();
 
-    println!("[DEBUG] Operations after filtering by profile_id: {}", operations.len());
+    debug!("Operations after filtering by profile_id: {}", operations.len());
 
     // Sort by started_at descending (newest first)
     operations.sort_by(|a, b| b.started_at.cmp(&a.started_at));
 
-    // Apply limit if specified
-    if let Some(limit) = limit {
-        println!("[DEBUG] Applying limit: {}", limit);
-        operations.truncate(limit);
+    let total_count = operations.len();
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(total_count);
+
+    // Only return metadata - full log text is fetched on demand via get_operation_log
+    let operations: Vec<BackupOperation> = operations
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|mut op| {
+            op.log_output = String::new();
+            op
+        })
+        .collect();
+
+    debug!("Returning {} of {} operations (offset {})", operations.len(), total_count, offset);
+
+    Ok(BackupLogsPage {
+        operations,
+        total_count,
+    })
+}
+
+#[command]
+pub async fn get_operation_log(operation_id: String) -> Result<String, BackupError> {
+    read_operation_log(&operation_id).await
+}
+
+/// Full log text of one operation, for copying to the clipboard
+#[command]
+pub async fn copy_operation_log(operation_id: String) -> Result<String, BackupError> {
+    read_operation_log(&operation_id).await
+}
+
+/// Write an operation's full log to `output_path`; returns the path written
+#[command]
+pub async fn export_operation_log(operation_id: String, output_path: String) -> Result<String, BackupError> {
+    let log_output = read_operation_log(&operation_id).await?;
+    tokio::fs::write(&output_path, log_output)
+        .await
+        .map_err(|e| BackupError::Io(format!("Failed to write {}: {}", output_path, e)))?;
+
+    debug!("Exported log for operation {} to {}", operation_id, output_path);
+    Ok(output_path)
+}
+
+async fn read_operation_log(operation_id: &str) -> Result<String, BackupError> {
+    let log_file = crate::config::get_operation_log_file(operation_id)?;
+
+    if log_file.exists() {
+        return std::fs::read_to_string(&log_file)
+            .map_err(|e| BackupError::Io(format!("Failed to read operation log: {}", e)));
+    }
+
+    // Operations saved before logs moved to separate files still carry their log inline
+    let config = crate::config::load_config().await?;
+    config.backup_operations
+        .into_iter()
+        .find(|op| op.id == operation_id)
+        .map(|op| op.log_output)
+        .ok_or_else(|| "Operation not found".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_one_line_and_detailed_stats() {
+        let one_line = "1.500 MiB / 3 MiB, 50%, 512 KiB/s, ETA 3s\n3 MiB / 3 MiB, 100%, 1 MiB/s, ETA 0s\n";
+        assert_eq!(parse_rclone_stats(one_line), Some((0, 3 * 1024 * 1024)));
+
+        let detailed = "Transferred:   \t    1.500 MiB / 3 MiB, 50%, 512 KiB/s, ETA 3s\n\
+            Transferred:            1 / 2, 50%\n\
+            Elapsed time:         3.0s\n\
+            Transferring:\n\
+            \x20*                                      big.bin: 50% /3Mi, 512Ki/s, 3s\n";
+        assert_eq!(parse_rclone_stats(detailed), Some((0, 1_572_864)));
+
+        let lines: Vec<_> = detailed.lines().map(parse_progress_line).collect();
+        assert_eq!(lines[0], Some((1_572_864, 3 * 1024 * 1024, 50, Some("512 KiB/s".to_string()), Some("3s".to_string()))));
+        assert!(lines[1..].iter().all(Option::is_none), "{:?}", lines);
+    }
+
+    #[test]
+    fn partial_exit_codes_and_error_lines() {
+        assert!(rclone_exit_is_partial(1));
+        assert!(rclone_exit_is_partial(6));
+        assert!(!rclone_exit_is_partial(2));
+        assert!(!rclone_exit_is_partial(7));
+
+        let stderr = "2025/01/16 12:34:56 ERROR : docs/a.txt: Failed to copy: open docs/a.txt: permission denied\n\
+            2025/01/16 12:34:57 ERROR : Attempt 1/3 failed with 1 errors and: permission denied\n\
+            2025/01/16 12:34:57 INFO  : docs/b.txt: Copied (new)\n";
+        assert_eq!(rclone_error_lines(stderr), vec!["docs/a.txt: Failed to copy: open docs/a.txt: permission denied"]);
+    }
+
+    #[test]
+    fn listremotes_parses_name_and_type() {
+        let remotes = parse_listremotes("aws:             s3\nsecret:          crypt\n\nmy remote:       drive\n");
+        let pairs: Vec<(&str, &str)> = remotes.iter().map(|r| (r.name.as_str(), r.remote_type.as_str())).collect();
+        assert_eq!(pairs, vec![("aws", "s3"), ("secret", "crypt"), ("my remote", "drive")]);
+    }
+
+    #[test]
+    fn config_drift_follows_crypt_and_reports_differences() {
+        let dump = serde_json::json!({
+            "secret": { "type": "crypt", "remote": "aws:bucket/users/alice" },
+            "aws": { "type": "s3", "provider": "AWS", "access_key_id": "AKIAOLD", "secret_access_key": "s", "region": "" }
+        });
+        let aws_config: AwsConfig = serde_json::from_value(serde_json::json!({
+            "aws_access_key_id": "AKIANEW",
+            "aws_secret_access_key": "s",
+            "aws_region": "us-east-1",
+            "aws_sso_configured": false,
+            "bucket_name": "bucket",
+            "lifecycle_config": { "enabled": false, "days_to_ia": 30, "days_to_glacier": 999999 },
+            "employees": []
+        })).unwrap();
+
+        let (remote, stanza) = resolve_s3_remote(&dump, "secret").unwrap();
+        assert_eq!(remote, "aws");
+        assert_eq!(config_drift(&aws_config, stanza), vec!["Access key in rclone.conf is AKIAOLD, the app has AKIANEW"]);
+        assert!(resolve_s3_remote(&dump, "missing").is_err());
     }
 
-    println!("[DEBUG] Returning {} operations", operations.len());
-    if !operations.is_empty() {
-        println!("[DEBUG] Most recent operation: started_at={:?}, files={}, bytes={}",
-            operations[0].started_at, operations[0].files_transferred, operations[0].bytes_transferred);
+    #[test]
+    fn search_pattern_keeps_globs_and_wraps_plain_text() {
+        assert_eq!(search_include_pattern("*.pdf"), "*.pdf");
+        assert_eq!(search_include_pattern("report-202?.xlsx"), "report-202?.xlsx");
+        assert_eq!(search_include_pattern("invoice"), "*invoice*");
+        assert_eq!(search_include_pattern("a\\b"), "*a\\\\b*");
     }
 
-    Ok(operations)
-}
\ No newline at end of file
+    #[test]
+    fn parses_plain_release() {
+        let v = parse_semver("1.66.0").unwrap();
+        assert_eq!((v.major, v.minor, v.patch), (1, 66, 0));
+        assert!(v.pre_release.is_none());
+        assert!(v.supported);
+    }
+
+    #[test]
+    fn parses_beta_with_git_hash() {
+        let v = parse_semver("v1.67.0-beta.7830.a1b2c3d4e").unwrap();
+        assert_eq!((v.major, v.minor, v.patch), (1, 67, 0));
+        assert_eq!(v.pre_release.as_deref(), Some("beta.7830.a1b2c3d4e"));
+    }
+
+    #[test]
+    fn flags_old_and_unparseable_versions() {
+        let old = parse_semver("1.53.3-DEV").unwrap();
+        assert!(!old.supported);
+        assert!(version_warning(Some(&old)).is_some());
+        assert!(parse_semver("DEV").is_none());
+        assert!(version_warning(None).is_some());
+    }
+
+    #[test]
+    fn extracts_version_from_rclone_output() {
+        let output = "rclone v1.65.2\n- os/version: darwin 14.2\n- go/version: go1.21.6\n";
+        assert_eq!(parse_rclone_version(output).as_deref(), Some("1.65.2"));
+    }
+
+    #[test]
+    fn groups_flags_with_separate_values() {
+        let flags: Vec<String> = ["--fast-list", "--transfers", "8", "--bwlimit=10M", "stray", " ", "-v"]
+            .iter().map(|s| s.to_string()).collect();
+        let groups = group_rclone_flags(&flags);
+        let joined: Vec<String> = groups.iter().map(|g| g.join(" ")).collect();
+        assert_eq!(joined, vec!["--fast-list", "--transfers 8", "--bwlimit=10M", "stray", "-v"]);
+        assert_eq!(flag_error_message("Error: unknown flag: --fast-lst\nUsage:\n  rclone version [flags]\n"), "unknown flag: --fast-lst");
+    }
+
+    #[test]
+    fn extracts_unquoted_and_quoted_notice_paths() {
+        let lines = [
+            ("2024/01/15 10:30:45 NOTICE: report.pdf: Skipped copy as --dry-run is set (size 1.2Mi)", "report.pdf"),
+            ("2024/01/15 10:30:45 NOTICE: Tax Returns/2023 final.pdf: Skipped copy as --dry-run is set (size 88Ki)", "Tax Returns/2023 final.pdf"),
+            ("NOTICE: old/notes.txt: Skipped delete as --dry-run is set (size 12)", "old/notes.txt"),
+            ("2024/01/15 10:30:45 NOTICE: \"with spaces.txt\": Would copy", "with spaces.txt"),
+            ("2024/01/15 10:30:45 NOTICE: times: 10:30.txt: Skipped copy as --dry-run is set (size 3)", "times: 10:30.txt"),
+        ];
+        for (line, expected) in lines {
+            assert_eq!(extract_file_path_from_notice(line).as_deref(), Some(expected), "{}", line);
+        }
+        assert!(extract_file_path_from_notice("2024/01/15 10:30:45 NOTICE: : Skipped copy as --dry-run is set").is_none());
+        assert!(extract_file_path_from_notice("2024/01/15 10:30:45 INFO  : report.pdf: Copied (new)").is_none());
+    }
+
+    #[test]
+    fn classifies_dry_run_notices() {
+        let output = "\
+2024/01/15 10:30:45 NOTICE: new.txt: Skipped copy as --dry-run is set (size 5)
+2024/01/15 10:30:45 NOTICE: gone.txt: Skipped delete as --dry-run is set (size 7)
+2024/01/15 10:30:45 NOTICE: touched.txt: Skipped update modification time as --dry-run is set (size 9)
+2024/01/15 10:30:45 NOTICE:
+Transferred:   	          0 B / 12 B, 0%, 0 B/s, ETA -
+";
+        let changes = parse_dry_run_output(output).unwrap();
+        let summary: Vec<(&str, &str)> = changes.iter()
+            .map(|c| (c.path.as_str(), match c.action {
+                ChangeAction::Copy => "copy",
+                ChangeAction::Update => "update",
+                ChangeAction::Delete => "delete",
+            }))
+            .collect();
+        assert_eq!(summary, vec![("new.txt", "copy"), ("gone.txt", "delete"), ("touched.txt", "update")]);
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tracing::{debug, warn};
+
+use crate::config::get_scheduled_logs_dir;
+
+/// Profiles with a backup or restore running in this process
+static ACTIVE_PROFILES: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+fn active_profiles() -> &'static Mutex<HashSet<String>> {
+    ACTIVE_PROFILES.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Held for the duration of a backup/restore; dropping it releases the profile,
+/// whether the run completed, failed or was cancelled.
+///
+/// Besides the in-memory set, the guard owns `logs/locks/<profile_id>.lock` containing
+/// our pid. The scheduled runner scripts create the same file, so a manual run and a
+/// scheduled run can't overlap either.
+pub struct ProfileRunGuard {
+    profile_id: String,
+    lock_file: Option<PathBuf>,
+}
+
+impl Drop for ProfileRunGuard {
+    fn drop(&mut self) {
+        if let Some(lock_file) = &self.lock_file {
+            let _ = fs::remove_file(lock_file);
+        }
+        if let Ok(mut active) = active_profiles().lock() {
+            active.remove(&self.profile_id);
+        }
+    }
+}
+
+/// Number of backups/restores currently running in this process
+pub fn active_run_count() -> usize {
+    active_profiles().lock().map(|active| active.len()).unwrap_or(0)
+}
+
+pub fn acquire_profile_run(profile_id: &str) -> Result<ProfileRunGuard, String> {
+    {
+        let mut active = active_profiles().lock().map_err(|e| e.to_string())?;
+        if !active.insert(profile_id.to_string()) {
+            return Err("A backup is already running for this profile".to_string());
+        }
+    }
+
+    // From here on the guard releases the in-memory entry on every return path
+    let mut guard = ProfileRunGuard {
+        profile_id: profile_id.to_string(),
+        lock_file: None,
+    };
+
+    let lock_file = match get_scheduled_logs_dir() {
+        Ok(dir) => dir.join("locks").join(format!("{}.lock", profile_id)),
+        Err(e) => {
+            warn!("No lock directory, only guarding within the app: {}", e);
+            return Ok(guard);
+        }
+    };
+
+    match create_lock_file(&lock_file) {
+        Ok(true) => {}
+        Ok(false) => {
+            let holder = fs::read_to_string(&lock_file)
+                .ok()
+                .and_then(|content| content.trim().parse::<u32>().ok());
+            // Our own pid here can only be a leftover, since the in-memory set said we're idle
+            if let Some(pid) = holder.filter(|pid| *pid != std::process::id() && process_alive(*pid)) {
+                return Err(format!("A backup is already running for this profile (scheduled run, pid {})", pid));
+            }
+
+            // Left behind by a run that crashed; take it over
+            debug!("Removing stale run lock {:?}", lock_file);
+            let _ = fs::remove_file(&lock_file);
+            if !create_lock_file(&lock_file).unwrap_or(false) {
+                return Err("A backup is already running for this profile".to_string());
+            }
+        }
+        Err(e) => {
+            warn!("Could not create run lock {:?}, only guarding within the app: {}", lock_file, e);
+            return Ok(guard);
+        }
+    }
+
+    guard.lock_file = Some(lock_file);
+    Ok(guard)
+}
+
+/// Atomically create the lock file with our pid; Ok(false) if it already exists
+fn create_lock_file(lock_file: &PathBuf) -> Result<bool, String> {
+    if let Some(parent) = lock_file.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    match fs::OpenOptions::new().write(true).create_new(true).open(lock_file) {
+        Ok(mut file) => {
+            write!(file, "{}", std::process::id()).map_err(|e| e.to_string())?;
+            Ok(true)
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn process_alive(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        std::process::Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+        std::process::Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+            .unwrap_or(false)
+    }
+}
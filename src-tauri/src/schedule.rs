@@ -1,59 +1,164 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
 use tauri::command;
-use chrono::{Utc, Local, NaiveTime, NaiveDate, DateTime, Timelike, Datelike, Duration, TimeZone};
+use chrono::{Utc, Local, NaiveTime, NaiveDate, NaiveDateTime, DateTime, Timelike, Datelike, Duration, TimeZone, LocalResult};
+use tracing::{debug, warn, error};
 
 use crate::models::*;
-use crate::config::{get_config_dir, load_config, save_config};
+use crate::config::{get_config_dir, get_scheduled_logs_dir, load_config, save_config, scan_scheduled_results, source_path_problems};
+use crate::process::run_with_timeout;
+use crate::rclone::create_command;
 
+/// How close two scheduled runs may start before detect_schedule_conflicts reports them
+const DEFAULT_CONFLICT_WINDOW_MINUTES: u32 = 60;
+/// How far ahead schedules are compared; 62 days covers every weekly/monthly combination
+const CONFLICT_HORIZON_DAYS: i64 = 62;
+
+/// Schedule the profile's backups. Returns the enabled schedules it now conflicts with
+/// (see detect_schedule_conflicts) so the user can be warned; they don't block scheduling.
 #[command]
-pub async fn schedule_backup(profile_id: String, mut schedule: Schedule) -> Result<(), String> {
-    println!("[DEBUG] schedule_backup called with profile_id: {}", profile_id);
-    println!("[DEBUG] schedule: {:?}", schedule);
+pub async fn schedule_backup(profile_id: String, mut schedule: Schedule) -> Result<Vec<ScheduleConflict>, String> {
+    debug!("schedule_backup called with profile_id: {}", profile_id);
+    debug!("schedule: {:?}", schedule);
     let mut config = load_config().await?;
     
     if let Some(profile) = config.profiles.iter_mut().find(|p| p.id == profile_id) {
-        // Use simple local time calculation for next_run (for display only)
-        // The actual scheduling uses the time field directly
-        let time = NaiveTime::parse_from_str(&schedule.time, "%H:%M")
+        // next_run is for display only; the OS scheduler uses the time field directly
+        NaiveTime::parse_from_str(&schedule.time, "%H:%M")
             .map_err(|_| "Invalid time format")?;
-        let now_local = Local::now();
-        let today_local = now_local.date_naive();
-        let today_at_time = today_local.and_time(time);
-        let today_local_dt = Local.from_local_datetime(&today_at_time).single()
-            .ok_or("Invalid local time")?;
-        
-        if today_local_dt > now_local {
-            schedule.next_run = Some(today_local_dt.with_timezone(&Utc));
-        } else {
-            let tomorrow_local = today_local + Duration::days(1);
-            let tomorrow_at_time = tomorrow_local.and_time(time);
-            let tomorrow_local_dt = Local.from_local_datetime(&tomorrow_at_time).single()
-                .ok_or("Invalid local time")?;
-            schedule.next_run = Some(tomorrow_local_dt.with_timezone(&Utc));
+        schedule.next_run = calculate_next_run(&schedule);
+
+        // An unattended run can't ask about a missing drive, so refuse up front
+        let problems = source_path_problems(&profile.sources);
+        if !problems.is_empty() {
+            return Err(format!("Cannot schedule backup: {}", problems.join("; ")));
         }
         
         profile.schedule = Some(schedule.clone());
         profile.updated_at = Utc::now();
         
         // Create the actual OS schedule using simplified approach
-        println!("[DEBUG] Creating OS schedule...");
+        debug!("Creating OS schedule...");
         match create_simple_os_schedule(profile, &schedule).await {
-            Ok(_) => println!("[DEBUG] OS schedule created successfully"),
+            Ok(_) => debug!("OS schedule created successfully"),
             Err(e) => {
-                println!("[DEBUG] Failed to create OS schedule: {}", e);
+                debug!("Failed to create OS schedule: {}", e);
                 return Err(format!("Failed to create OS schedule: {}", e));
             }
         }
         
         config.updated_at = Utc::now();
         save_config(&config).await?;
-        Ok(())
+
+        let window = Duration::minutes(DEFAULT_CONFLICT_WINDOW_MINUTES as i64);
+        let conflicts: Vec<ScheduleConflict> = find_schedule_conflicts(&config.profiles, &Local::now(), window)
+            .into_iter()
+            .filter(|conflict| conflict.profile_id == profile_id || conflict.other_profile_id == profile_id)
+            .collect();
+        for conflict in &conflicts {
+            warn!(
+                "Schedule for {} overlaps {}: both write to {} around {}",
+                conflict.profile_name, conflict.other_profile_name, conflict.destination, conflict.first_overlap
+            );
+        }
+        Ok(conflicts)
     } else {
         Err("Profile not found".to_string())
     }
 }
 
+/// Pairs of enabled schedules whose destinations overlap (one is the other or inside it)
+/// and that will start within `window_minutes` (default 60) of each other. Two such runs,
+/// especially in Sync mode, can delete or overwrite each other's files.
+#[command]
+pub async fn detect_schedule_conflicts(window_minutes: Option<u32>) -> Result<Vec<ScheduleConflict>, String> {
+    let config = load_config().await?;
+    let window = Duration::minutes(window_minutes.unwrap_or(DEFAULT_CONFLICT_WINDOW_MINUTES) as i64);
+    Ok(find_schedule_conflicts(&config.profiles, &Local::now(), window))
+}
+
+fn find_schedule_conflicts<Tz: TimeZone>(profiles: &[Profile], now: &DateTime<Tz>, window: Duration) -> Vec<ScheduleConflict> {
+    let scheduled: Vec<(&Profile, &Schedule)> = profiles.iter()
+        .filter_map(|profile| profile.schedule.as_ref().filter(|s| s.enabled).map(|s| (profile, s)))
+        .collect();
+
+    let mut conflicts = Vec::new();
+    for (index, (profile, schedule)) in scheduled.iter().enumerate() {
+        for (other, other_schedule) in &scheduled[index + 1..] {
+            let Some((destination, other_destination)) = overlapping_destinations(profile, other) else {
+                continue;
+            };
+            let Some((first_overlap, apart)) = first_schedule_overlap(schedule, other_schedule, now, window) else {
+                continue;
+            };
+            conflicts.push(ScheduleConflict {
+                profile_id: profile.id.clone(),
+                profile_name: profile.name.clone(),
+                other_profile_id: other.id.clone(),
+                other_profile_name: other.name.clone(),
+                destination,
+                other_destination,
+                first_overlap,
+                minutes_apart: apart.num_minutes(),
+                sync_mode: profile.mode == BackupMode::Sync || other.mode == BackupMode::Sync,
+            });
+        }
+    }
+    conflicts
+}
+
+/// First pair of destinations (primary or secondary) the two profiles share
+fn overlapping_destinations(profile: &Profile, other: &Profile) -> Option<(String, String)> {
+    let destinations = |p: &Profile| {
+        let mut all = vec![p.storage_path()];
+        all.extend(p.secondary_destinations.iter().cloned());
+        all
+    };
+    let other_destinations = destinations(other);
+    destinations(profile).into_iter().find_map(|destination| {
+        other_destinations.iter()
+            .find(|other_destination| destinations_overlap(&destination, other_destination))
+            .map(|other_destination| (destination.clone(), other_destination.clone()))
+    })
+}
+
+/// True when one rclone path is the other or a folder inside it
+fn destinations_overlap(a: &str, b: &str) -> bool {
+    let a = a.trim_end_matches('/');
+    let b = b.trim_end_matches('/');
+    let contains = |outer: &str, inner: &str| {
+        inner.strip_prefix(outer).is_some_and(|rest| rest.is_empty() || rest.starts_with('/') || outer.ends_with(':'))
+    };
+    contains(a, b) || contains(b, a)
+}
+
+/// Earliest run of `a` that starts within `window` of a run of `b` over the comparison
+/// horizon, and how far apart the two starts are
+fn first_schedule_overlap<Tz: TimeZone>(a: &Schedule, b: &Schedule, now: &DateTime<Tz>, window: Duration) -> Option<(DateTime<Utc>, Duration)> {
+    let b_runs = upcoming_runs(b, now);
+    upcoming_runs(a, now).into_iter().find_map(|run| {
+        b_runs.iter()
+            .map(|other_run| (*other_run - run).abs())
+            .filter(|apart| *apart <= window)
+            .min()
+            .map(|apart| (run, apart))
+    })
+}
+
+/// Every run of the schedule within CONFLICT_HORIZON_DAYS of `now`
+fn upcoming_runs<Tz: TimeZone>(schedule: &Schedule, now: &DateTime<Tz>) -> Vec<DateTime<Utc>> {
+    let timezone = now.timezone();
+    let horizon = now.with_timezone(&Utc) + Duration::days(CONFLICT_HORIZON_DAYS);
+    let mut runs = Vec::new();
+    let mut after = now.clone();
+    while let Some(run) = next_run_after(schedule, &after).filter(|run| *run <= horizon) {
+        runs.push(run);
+        after = run.with_timezone(&timezone);
+    }
+    runs
+}
+
 #[command]
 pub async fn unschedule_backup(profile_id: String) -> Result<(), String> {
     let mut config = load_config().await?;
@@ -73,17 +178,172 @@ pub async fn unschedule_backup(profile_id: String) -> Result<(), String> {
     }
 }
 
+#[command]
+pub async fn set_schedule_enabled(profile_id: String, enabled: bool) -> Result<Schedule, String> {
+    let mut config = load_config().await?;
+
+    let profile = config.profiles.iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    let mut schedule = profile.schedule.clone().ok_or("Profile has no schedule")?;
+
+    if enabled {
+        schedule.enabled = true;
+        resume_os_schedule(profile, &schedule).await?;
+    } else {
+        pause_os_schedule(&profile.id).await?;
+        schedule.enabled = false;
+    }
+
+    // calculate_next_run returns None for a paused schedule
+    schedule.next_run = calculate_next_run(&schedule);
+    debug!("Schedule for profile {} {}", profile_id, if enabled { "resumed" } else { "paused" });
+
+    profile.schedule = Some(schedule.clone());
+    profile.updated_at = Utc::now();
+    config.updated_at = Utc::now();
+    save_config(&config).await?;
+    Ok(schedule)
+}
+
 #[command]
 pub async fn get_schedule_status(profile_id: String) -> Result<Option<Schedule>, String> {
     let config = load_config().await?;
     
     if let Some(profile) = config.profiles.iter().find(|p| p.id == profile_id) {
-        Ok(profile.schedule.clone())
+        // Recompute so a stored next_run from before the last run (or a DST change) isn't shown
+        Ok(profile.schedule.clone().map(|mut schedule| {
+            schedule.next_run = calculate_next_run(&schedule);
+            schedule
+        }))
     } else {
         Err("Profile not found".to_string())
     }
 }
 
+/// Run the profile's generated runner script right away, exactly as the OS scheduler would,
+/// so a schedule can be verified without waiting for it to fire
+#[command]
+pub async fn run_scheduled_now(profile_id: String) -> Result<ScheduledTestRun, String> {
+    let config = load_config().await?;
+    let profile = config.profiles.iter()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+
+    let script_ext = if cfg!(windows) { "ps1" } else { "sh" };
+    let script_path = get_config_dir()?
+        .join("scripts")
+        .join(format!("backup-{}.{}", profile.id, script_ext));
+    if !script_path.exists() {
+        return Err("No runner script for this profile yet; schedule a backup first".to_string());
+    }
+
+    let log_file = get_scheduled_logs_dir()?.join(format!("backup-{}.log", profile.id));
+    let log_start = fs::metadata(&log_file).map(|m| m.len()).unwrap_or(0);
+
+    let script = script_path.to_string_lossy().to_string();
+    let mut command = if cfg!(windows) {
+        let mut command = create_command("powershell.exe");
+        command.args(["-ExecutionPolicy", "Bypass", "-NoProfile", "-File", &script]);
+        command
+    } else {
+        let mut command = create_command("bash");
+        command.arg(&script);
+        command
+    };
+
+    debug!("run_scheduled_now - running {}", script);
+    let output = run_with_timeout(
+        command.stdout(Stdio::piped()).stderr(Stdio::piped()),
+        profile.transfer_timeout(),
+    )
+    .await?;
+
+    // Only what this run appended; the log is shared with earlier scheduled runs
+    let log_excerpt = fs::read(&log_file)
+        .map(|bytes| String::from_utf8_lossy(bytes.get(log_start as usize..).unwrap_or(&bytes)).to_string())
+        .unwrap_or_default();
+
+    // The script drops a result file like a real scheduled run; record it in history now
+    if let Err(e) = scan_scheduled_results().await {
+        error!("Failed to scan scheduled backup results: {}", e);
+    }
+
+    Ok(ScheduledTestRun {
+        success: output.status.success(),
+        exit_code: output.status.code().unwrap_or(-1),
+        output: format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr)),
+        log_excerpt,
+    })
+}
+
+/// What schedule_backup would install for `schedule`: the runner script and the OS job
+/// that starts it. Nothing is written or registered, so the current schedule is untouched.
+#[command]
+pub async fn preview_schedule(profile_id: String, schedule: Schedule) -> Result<SchedulePreview, String> {
+    let config = load_config().await?;
+    let profile = config.profiles.iter()
+        .find(|p| p.id == profile_id)
+        .ok_or("Profile not found")?;
+    NaiveTime::parse_from_str(&schedule.time, "%H:%M")
+        .map_err(|_| "Invalid time format")?;
+
+    let scripts_dir = get_config_dir()?.join("scripts");
+    let script_path = runner_script_path(profile, &scripts_dir);
+    let script = runner_script_content(profile)?;
+    let (wrapper_script, job_path, job_definition) = os_job_preview(profile, &schedule, &script_path)?;
+
+    Ok(SchedulePreview {
+        platform: std::env::consts::OS.to_string(),
+        script_path: script_path.to_string_lossy().to_string(),
+        script,
+        wrapper_script,
+        job_path,
+        job_definition,
+        next_run: calculate_next_run(&schedule),
+    })
+}
+
+/// (wrapper script, job location, job definition) for preview_schedule
+type OsJobPreview = (Option<String>, Option<String>, Option<String>);
+
+#[cfg(target_os = "macos")]
+fn os_job_preview(profile: &Profile, schedule: &Schedule, script_path: &PathBuf) -> Result<OsJobPreview, String> {
+    let plist_path = launchd_plist_path(profile)?;
+    Ok((
+        None,
+        Some(plist_path.to_string_lossy().to_string()),
+        Some(launchd_plist(profile, schedule, script_path)?),
+    ))
+}
+
+#[cfg(target_os = "windows")]
+fn os_job_preview(profile: &Profile, schedule: &Schedule, script_path: &PathBuf) -> Result<OsJobPreview, String> {
+    // The task runs the VBScript wrapper, which starts the PowerShell script hidden
+    let vbs_path = script_path.with_extension("vbs");
+    let command = std::iter::once("schtasks".to_string())
+        .chain(schtasks_create_args(profile, schedule, &vbs_path)?.into_iter().map(|arg| {
+            if arg.contains([' ', '"']) {
+                format!("\"{}\"", arg.replace('"', "\\\""))
+            } else {
+                arg
+            }
+        }))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Ok((
+        Some(vbs_wrapper_content(script_path)),
+        Some(windows_task_name(profile)),
+        Some(command),
+    ))
+}
+
+#[cfg(target_os = "linux")]
+fn os_job_preview(_profile: &Profile, _schedule: &Schedule, _script_path: &PathBuf) -> Result<OsJobPreview, String> {
+    // No OS scheduler is set up on Linux yet (see create_systemd_schedule)
+    Ok((None, None, None))
+}
+
 async fn create_simple_os_schedule(profile: &Profile, schedule: &Schedule) -> Result<(), String> {
     let config_dir = get_config_dir()?;
     let scripts_dir = config_dir.join("scripts");
@@ -137,15 +397,82 @@ async fn create_os_schedule(profile: &Profile, schedule: &Schedule) -> Result<()
     Ok(())
 }
 
-async fn create_runner_script(profile: &Profile, scripts_dir: &PathBuf) -> Result<PathBuf, String> {
-    use crate::binary_resolver::get_rclone_binary_path;
+/// Rewrite a scheduled profile's runner script after a change to where or how it backs up.
+/// The OS job runs the script by path, so it picks up the new one on its next run.
+pub async fn refresh_runner_script(profile: &Profile) -> Result<(), String> {
+    if profile.schedule.is_none() {
+        return Ok(());
+    }
+    let scripts_dir = get_config_dir()?.join("scripts");
+    fs::create_dir_all(&scripts_dir).map_err(|e| e.to_string())?;
+    create_runner_script(profile, &scripts_dir).await?;
+    Ok(())
+}
 
+/// Where create_runner_script writes the profile's script
+fn runner_script_path(profile: &Profile, scripts_dir: &Path) -> PathBuf {
     let script_ext = if cfg!(windows) { "ps1" } else { "sh" };
-    let script_name = format!("backup-{}.{}", profile.id, script_ext);
-    let script_path = scripts_dir.join(&script_name);
+    scripts_dir.join(format!("backup-{}.{}", profile.id, script_ext))
+}
+
+async fn create_runner_script(profile: &Profile, scripts_dir: &PathBuf) -> Result<PathBuf, String> {
+    let script_path = runner_script_path(profile, scripts_dir);
+    let script_content = runner_script_content(profile)?;
+
+    fs::write(&script_path, script_content).map_err(|e| e.to_string())?;
+
+    // Make script executable
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&script_path).map_err(|e| e.to_string())?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&script_path, perms).map_err(|e| e.to_string())?;
+    }
+
+    // On Windows, create a VBScript wrapper to run PowerShell invisibly
+    #[cfg(windows)]
+    {
+        let vbs_path = scripts_dir.join(format!("backup-{}.vbs", profile.id));
+        fs::write(&vbs_path, vbs_wrapper_content(&script_path)).map_err(|e| e.to_string())?;
+        return Ok(vbs_path);
+    }
+
+    Ok(script_path)
+}
+
+/// VBScript wrapper the Windows task runs, so the PowerShell window never appears
+#[cfg(windows)]
+fn vbs_wrapper_content(ps_script: &PathBuf) -> String {
+    format!(
+        r#"' VBScript wrapper to run PowerShell script invisibly
+' This prevents the terminal window from appearing during scheduled backups
+
+Set objShell = CreateObject("WScript.Shell")
+command = "powershell.exe -ExecutionPolicy Bypass -NoProfile -WindowStyle Hidden -File ""{}"
+objShell.Run command, 0, False
+"#,
+        ps_script.to_string_lossy().replace("\\", "\\\\")
+    )
+}
+
+/// The runner script for the profile as it would be written now
+fn runner_script_content(profile: &Profile) -> Result<String, String> {
+    use crate::binary_resolver::get_rclone_binary_path;
 
     let destination = profile.destination();
-    let flags = profile.rclone_flags.join(" ");
+    let mut flags = [profile.filter_flags(), profile.transfer_flags(), profile.backup_filter_flags()?].concat();
+    // Nobody is around to confirm a scheduled run, so let rclone stop deleting at the threshold
+    if profile.sync_deletions_need_confirmation(usize::MAX) {
+        flags.push(format!("--max-delete={}", profile.sync_delete_threshold));
+    }
+    // The same verbosity as runs from the app; the file count is parsed from Normal and above
+    flags.extend(profile.log_flags());
+    // Values like --min-age "2 days" or filter patterns can hold spaces and globs
+    let quote = if cfg!(windows) { powershell_quote } else { bash_quote };
+    let flags = flags.iter().map(|flag| quote(flag)).collect::<Vec<_>>().join(" ");
+    // Each run picks its snapshot folder under this when it starts
+    let backup_dir = profile.use_backup_dir.then(|| remote_join(&destination, BACKUP_DIR_FOLDER));
 
     let operation = match profile.mode {
         BackupMode::Copy => "copy",
@@ -170,6 +497,9 @@ async fn create_runner_script(profile: &Profile, scripts_dir: &PathBuf) -> Resul
     let scheduled_config = config_dir.join("rclone-scheduled.conf");
     let rclone_config = if scheduled_config.exists() {
         scheduled_config.to_string_lossy().to_string()
+    } else if profile.rclone_config_encrypted {
+        // The runner script has no way to reach the keychain for the config password
+        return Err("Scheduled backups can't unlock an encrypted rclone.conf. Create the scheduled config, or use an unencrypted rclone.conf for this profile.".to_string());
     } else {
         // Fallback to regular config (temporary credentials - will fail)
         profile.rclone_conf.clone()
@@ -180,6 +510,7 @@ async fn create_runner_script(profile: &Profile, scripts_dir: &PathBuf) -> Resul
         // Use hardcoded log path instead of $env:APPDATA since task runs as SYSTEM
         let log_dir = config_dir.join("logs");
         let log_file_path = log_dir.join(format!("backup-{}.log", profile.id));
+        let results_dir = log_dir.join("results");
 
         format!(
             r#"# Cloud Backup App - Scheduled Backup Script
@@ -193,21 +524,43 @@ $RCLONE_BIN = "{}"
 $RCLONE_CONFIG = "{}"
 $DESTINATION = "{}"
 $OPERATION = "{}"
-$FLAGS = "{}"
 
+$PROFILE_ID = "{}"
+$PROFILE_LABEL = {}
+$WEBHOOK_URL = {}
+$NOTIFY_ON_SUCCESS = ${}
+{}
 # Log file (hardcoded path since task runs as SYSTEM)
 $LOG_DIR = "{}"
 $LOG_FILE = "{}"
-if (!(Test-Path $LOG_DIR)) {{
-    New-Item -ItemType Directory -Path $LOG_DIR -Force | Out-Null
+$RESULTS_DIR = "{}"
+if (!(Test-Path $RESULTS_DIR)) {{
+    New-Item -ItemType Directory -Path $RESULTS_DIR -Force | Out-Null
 }}
 
+{}
+
 function Write-Log {{
     param($Message)
     $Timestamp = Get-Date -Format "ddd MMM dd HH:mm:ss K yyyy"
     "$Timestamp : $Message" | Out-File -FilePath $LOG_FILE -Append -Encoding UTF8
 }}
 
+# Per-profile run lock shared with the app, holding the pid of the running backup
+$LOCK_FILE = Join-Path $LOG_DIR "locks\$PROFILE_ID.lock"
+New-Item -ItemType Directory -Path (Split-Path $LOCK_FILE) -Force | Out-Null
+try {{
+    $LockStream = [System.IO.File]::Open($LOCK_FILE, [System.IO.FileMode]::CreateNew)
+    $LockStream.Close()
+}} catch {{
+    $LockPid = Get-Content -Path $LOCK_FILE -ErrorAction SilentlyContinue | Select-Object -First 1
+    if ($LockPid -and (Get-Process -Id $LockPid -ErrorAction SilentlyContinue)) {{
+        Write-Log "Skipping scheduled backup: another backup is already running for this profile (pid $LockPid)"
+        exit 0
+    }}
+}}
+Set-Content -Path $LOCK_FILE -Value "$PID" -Encoding ASCII -NoNewline
+
 Write-Log "Starting scheduled backup for profile {}"
 Write-Log "Using rclone: $RCLONE_BIN"
 Write-Log "Using config: $RCLONE_CONFIG"
@@ -221,6 +574,10 @@ try {{
     $BackupSuccess = $false
 }}
 
+Write-Result
+Send-WebhookNotification
+Remove-Item -Path $LOCK_FILE -Force -ErrorAction SilentlyContinue
+
 if ($BackupSuccess) {{
     Write-Log "Backup completed for profile {}"
 }} else {{
@@ -233,11 +590,17 @@ if ($BackupSuccess) {{
             rclone_config.replace("\\", "\\\\"),
             destination,
             operation,
-            flags,
+            profile.id,
+            powershell_quote(&notification_label(&profile.name)),
+            powershell_quote(profile.notifications.webhook().unwrap_or("")),
+            profile.notifications.notify_on_success,
+            network_env_lines(|name, value| format!("$env:{} = {}", name, powershell_quote(value))),
             log_dir.to_string_lossy().replace("\\", "\\\\"),
             log_file_path.to_string_lossy().replace("\\", "\\\\"),
+            results_dir.to_string_lossy().replace("\\", "\\\\"),
+            POWERSHELL_RESULT_HELPERS,
             profile.name,
-            generate_backup_commands_windows(&profile.sources, &destination, operation, &flags),
+            generate_backup_commands_windows(&profile.sources, &destination, operation, &flags, backup_dir.as_deref()),
             profile.name,
             profile.name
         )
@@ -256,11 +619,33 @@ RCLONE_BIN="{}"
 RCLONE_CONFIG="{}"
 DESTINATION="{}"
 OPERATION="{}"
-FLAGS="{}"
 
+PROFILE_ID="{}"
+SOURCE_COUNT={}
+PROFILE_LABEL={}
+WEBHOOK_URL={}
+NOTIFY_ON_SUCCESS={}
+{}
 # Log file
-LOG_FILE="$HOME/.config/cloud-backup-app/logs/backup-{}.log"
-mkdir -p "$(dirname "$LOG_FILE")"
+LOGS_DIR={}
+LOG_FILE="$LOGS_DIR/backup-$PROFILE_ID.log"
+RESULTS_DIR="$LOGS_DIR/results"
+mkdir -p "$(dirname "$LOG_FILE")" "$RESULTS_DIR"
+
+{}
+
+# Per-profile run lock shared with the app, holding the pid of the running backup
+LOCK_FILE="$LOGS_DIR/locks/$PROFILE_ID.lock"
+mkdir -p "$(dirname "$LOCK_FILE")"
+if ! (set -o noclobber; echo "$$" > "$LOCK_FILE") 2>/dev/null; then
+    LOCK_PID="$(cat "$LOCK_FILE" 2>/dev/null || true)"
+    if [ -n "$LOCK_PID" ] && kill -0 "$LOCK_PID" 2>/dev/null; then
+        echo "$(date): Skipping scheduled backup: another backup is already running for this profile (pid $LOCK_PID)" >> "$LOG_FILE"
+        exit 0
+    fi
+    echo "$$" > "$LOCK_FILE"
+fi
+trap 'rm -f "$LOCK_FILE"' EXIT
 
 echo "$(date): Starting scheduled backup for profile {}" >> "$LOG_FILE"
 echo "$(date): Using rclone: $RCLONE_BIN" >> "$LOG_FILE"
@@ -269,7 +654,14 @@ echo "$(date): Using config: $RCLONE_CONFIG" >> "$LOG_FILE"
 # Backup each source
 {}
 
-echo "$(date): Backup completed for profile {}" >> "$LOG_FILE"
+write_result
+notify_webhook
+
+if [ "$FAILED_SOURCES" -gt 0 ]; then
+    echo "$(date): Backup completed with errors for profile {}" >> "$LOG_FILE"
+else
+    echo "$(date): Backup completed for profile {}" >> "$LOG_FILE"
+fi
 "#,
             profile.name,
             Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
@@ -277,51 +669,221 @@ echo "$(date): Backup completed for profile {}" >> "$LOG_FILE"
             rclone_config,
             destination,
             operation,
-            flags,
             profile.id,
+            profile.sources.len(),
+            bash_quote(&notification_label(&profile.name)),
+            bash_quote(profile.notifications.webhook().unwrap_or("")),
+            profile.notifications.notify_on_success,
+            network_env_lines(|name, value| format!("export {}={}", name, bash_quote(value))),
+            bash_quote(&get_scheduled_logs_dir()?.to_string_lossy()),
+            BASH_RESULT_HELPERS,
+            profile.name,
+            generate_backup_commands(&profile.sources, &destination, operation, &flags, backup_dir.as_deref()),
             profile.name,
-            generate_backup_commands(&profile.sources, &destination, operation, &flags),
             profile.name
         )
     };
 
-    fs::write(&script_path, script_content).map_err(|e| e.to_string())?;
+    Ok(script_content)
+}
 
-    // Make script executable
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let mut perms = fs::metadata(&script_path).map_err(|e| e.to_string())?.permissions();
-        perms.set_mode(0o755);
-        fs::set_permissions(&script_path, perms).map_err(|e| e.to_string())?;
-    }
+/// Shell helpers shared by every generated bash runner. Each source logs to its own temp
+/// file so its final rclone stats can be totalled, then the run is summarised in a result
+/// JSON under $RESULTS_DIR that scan_scheduled_results ingests into the backup history.
+const BASH_RESULT_HELPERS: &str = r#"OPERATION_ID="scheduled-$(date -u +%Y%m%d%H%M%S)-$$"
+RUN_LOG="$RESULTS_DIR/$OPERATION_ID.log"
+STARTED_AT="$(date -u +%Y-%m-%dT%H:%M:%SZ)"
+FAILED_SOURCES=0
+TOTAL_FILES=0
+TOTAL_BYTES=0
+
+# Append a source's rclone log to the main and per-run logs and add its final stats to the totals
+record_source_log() {
+    local source_log="$1"
+    local files bytes
+    cat "$source_log" >> "$LOG_FILE"
+    cat "$source_log" >> "$RUN_LOG"
+    files=$(sed -n 's/.*Transferred:[[:space:]]*\([0-9][0-9]*\) \/ [0-9][0-9]*, .*/\1/p' "$source_log" | tail -1)
+    bytes=$(sed -n 's/.*Transferred:[[:space:]]*\([0-9.][0-9.]*\)[[:space:]]*\([KMGTP]\{0,1\}\)i\{0,1\}B \/.*/\1 \2/p' "$source_log" | tail -1 \
+        | awk '{ m = 1; if ($2 == "K") m = 1024; else if ($2 == "M") m = 1024^2; else if ($2 == "G") m = 1024^3; else if ($2 == "T") m = 1024^4; else if ($2 == "P") m = 1024^5; printf "%.0f", $1 * m }')
+    TOTAL_FILES=$((TOTAL_FILES + ${files:-0}))
+    TOTAL_BYTES=$((TOTAL_BYTES + ${bytes:-0}))
+    rm -f "$source_log"
+}
 
-    // On Windows, create a VBScript wrapper to run PowerShell invisibly
-    #[cfg(windows)]
-    {
-        let vbs_path = scripts_dir.join(format!("backup-{}.vbs", profile.id));
-        let ps_script_path = script_path.to_string_lossy().replace("\\", "\\\\");
+# Write the machine-readable result; rename last so the app never reads a partial file
+write_result() {
+    local status="Completed"
+    local error_message="null"
+    if [ "$FAILED_SOURCES" -gt 0 ]; then
+        status="Failed"
+        error_message="\"$FAILED_SOURCES of $SOURCE_COUNT sources failed\""
+    fi
+    cat > "$RESULTS_DIR/$OPERATION_ID.json.tmp" <<EOF
+{
+  "operation_id": "$OPERATION_ID",
+  "profile_id": "$PROFILE_ID",
+  "status": "$status",
+  "started_at": "$STARTED_AT",
+  "completed_at": "$(date -u +%Y-%m-%dT%H:%M:%SZ)",
+  "files_transferred": $TOTAL_FILES,
+  "bytes_transferred": $TOTAL_BYTES,
+  "error_message": $error_message,
+  "log_file": "$RUN_LOG"
+}
+EOF
+    mv "$RESULTS_DIR/$OPERATION_ID.json.tmp" "$RESULTS_DIR/$OPERATION_ID.json"
+}
 
-        let vbs_content = format!(
-            r#"' VBScript wrapper to run PowerShell script invisibly
-' This prevents the terminal window from appearing during scheduled backups
+# POST the run summary to $WEBHOOK_URL, in the same shape the app sends. Never fails the run.
+notify_webhook() {
+    local status="Completed"
+    local error_message="null"
+    local text="Scheduled backup of '$PROFILE_LABEL' completed: $TOTAL_FILES files, $TOTAL_BYTES bytes"
+    [ -n "$WEBHOOK_URL" ] || return 0
+    if [ "$FAILED_SOURCES" -gt 0 ]; then
+        status="Failed"
+        error_message="\"$FAILED_SOURCES of $SOURCE_COUNT sources failed\""
+        text="Scheduled backup of '$PROFILE_LABEL' failed: $FAILED_SOURCES of $SOURCE_COUNT sources failed"
+    elif [ "$NOTIFY_ON_SUCCESS" != "true" ]; then
+        return 0
+    fi
+    curl -fsS -m 15 -X POST -H "Content-Type: application/json" --data @- "$WEBHOOK_URL" >/dev/null 2>>"$LOG_FILE" <<EOF \
+        || echo "$(date): Webhook notification failed" >> "$LOG_FILE"
+{
+  "text": "$text",
+  "content": "$text",
+  "profile_id": "$PROFILE_ID",
+  "profile_name": "$PROFILE_LABEL",
+  "operation_id": "$OPERATION_ID",
+  "status": "$status",
+  "started_at": "$STARTED_AT",
+  "completed_at": "$(date -u +%Y-%m-%dT%H:%M:%SZ)",
+  "files_transferred": $TOTAL_FILES,
+  "bytes_transferred": $TOTAL_BYTES,
+  "error_message": $error_message,
+  "scheduled": true
+}
+EOF
+}"#;
+
+/// PowerShell equivalent of BASH_RESULT_HELPERS for the Windows runner
+const POWERSHELL_RESULT_HELPERS: &str = r#"$OperationId = "scheduled-" + [guid]::NewGuid().ToString()
+$RunLog = Join-Path $RESULTS_DIR "$OperationId.log"
+$StartedAt = (Get-Date).ToUniversalTime().ToString("yyyy-MM-ddTHH:mm:ssZ")
+$TotalFiles = 0
+$TotalBytes = 0
+
+# Append a source's rclone log to the main and per-run logs and add its final stats to the totals
+function Record-SourceLog {
+    param($SourceLog)
+    if (!(Test-Path $SourceLog)) { return }
+    $Content = Get-Content -Path $SourceLog
+    $Content | Out-File -FilePath $LOG_FILE -Append -Encoding UTF8
+    $Content | Out-File -FilePath $RunLog -Append -Encoding UTF8
+    $FilesMatch = $Content | Select-String -Pattern 'Transferred:\s*(\d+) / \d+, ' | Select-Object -Last 1
+    if ($FilesMatch) {
+        $script:TotalFiles += [int64]$FilesMatch.Matches[0].Groups[1].Value
+    }
+    $BytesMatch = $Content | Select-String -Pattern 'Transferred:\s*([0-9.]+)\s*([KMGTP]?)i?B /' | Select-Object -Last 1
+    if ($BytesMatch) {
+        $Value = [double]::Parse($BytesMatch.Matches[0].Groups[1].Value, [Globalization.CultureInfo]::InvariantCulture)
+        $Multiplier = switch ($BytesMatch.Matches[0].Groups[2].Value) {
+            "K" { 1KB }
+            "M" { 1MB }
+            "G" { 1GB }
+            "T" { 1TB }
+            "P" { 1PB }
+            default { 1 }
+        }
+        $script:TotalBytes += [int64]($Value * $Multiplier)
+    }
+    Remove-Item -Path $SourceLog -Force
+}
 
-Set objShell = CreateObject("WScript.Shell")
-command = "powershell.exe -ExecutionPolicy Bypass -NoProfile -WindowStyle Hidden -File ""{}"
-objShell.Run command, 0, False
-"#,
-            ps_script_path
-        );
+# Write the machine-readable result; rename last so the app never reads a partial file
+function Write-Result {
+    $Status = if ($BackupSuccess) { "Completed" } else { "Failed" }
+    $Result = [ordered]@{
+        operation_id = $OperationId
+        profile_id = $PROFILE_ID
+        status = $Status
+        started_at = $StartedAt
+        completed_at = (Get-Date).ToUniversalTime().ToString("yyyy-MM-ddTHH:mm:ssZ")
+        files_transferred = $script:TotalFiles
+        bytes_transferred = $script:TotalBytes
+        error_message = if ($BackupSuccess) { $null } else { "One or more sources failed" }
+        log_file = $RunLog
+    }
+    $TempPath = Join-Path $RESULTS_DIR "$OperationId.json.tmp"
+    $Result | ConvertTo-Json | Out-File -FilePath $TempPath -Encoding UTF8
+    Move-Item -Path $TempPath -Destination (Join-Path $RESULTS_DIR "$OperationId.json") -Force
+}
 
-        fs::write(&vbs_path, vbs_content).map_err(|e| e.to_string())?;
-        return Ok(vbs_path);
+# POST the run summary to $WEBHOOK_URL, in the same shape the app sends. Never fails the run.
+function Send-WebhookNotification {
+    if (-not $WEBHOOK_URL) { return }
+    if ($BackupSuccess -and -not $NOTIFY_ON_SUCCESS) { return }
+    $Text = if ($BackupSuccess) {
+        "Scheduled backup of '$PROFILE_LABEL' completed: $($script:TotalFiles) files, $($script:TotalBytes) bytes"
+    } else {
+        "Scheduled backup of '$PROFILE_LABEL' failed: one or more sources failed"
     }
+    $Payload = [ordered]@{
+        text = $Text
+        content = $Text
+        profile_id = $PROFILE_ID
+        profile_name = $PROFILE_LABEL
+        operation_id = $OperationId
+        status = if ($BackupSuccess) { "Completed" } else { "Failed" }
+        started_at = $StartedAt
+        completed_at = (Get-Date).ToUniversalTime().ToString("yyyy-MM-ddTHH:mm:ssZ")
+        files_transferred = $script:TotalFiles
+        bytes_transferred = $script:TotalBytes
+        error_message = if ($BackupSuccess) { $null } else { "One or more sources failed" }
+        scheduled = $true
+    }
+    try {
+        Invoke-RestMethod -Uri $WEBHOOK_URL -Method Post -ContentType "application/json" -Body ($Payload | ConvertTo-Json) -TimeoutSec 15 | Out-Null
+    } catch {
+        Write-Log "Webhook notification failed: $_"
+    }
+}"#;
 
-    Ok(script_path)
+/// Profile name reduced to characters that are safe inside the runner's JSON and shell strings
+fn notification_label(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric() || " -_.()".contains(*c))
+        .collect()
 }
 
-fn generate_backup_commands(sources: &[String], destination: &str, operation: &str, flags: &str) -> String {
-    sources.iter()
+/// Single-quote a value for bash
+fn bash_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Single-quote a value for PowerShell, where nothing inside '...' is expanded
+fn powershell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// The app's proxy and bind settings as script lines built by `line`, so scheduled rclone
+/// runs use the same network path. Blank when none are set.
+fn network_env_lines(line: impl Fn(&str, &str) -> String) -> String {
+    let env = crate::network::network_env();
+    if env.is_empty() {
+        return String::new();
+    }
+    let lines: Vec<String> = env.iter().map(|(name, value)| line(name, value)).collect();
+    format!("\n# Proxy and bind settings from the app\n{}\n", lines.join("\n"))
+}
+
+/// With `backup_dir`, every source's replaced and deleted files go into one snapshot folder
+/// under it named by the time the script runs
+fn generate_backup_commands(sources: &[String], destination: &str, operation: &str, flags: &str, backup_dir: Option<&str>) -> String {
+    let stamp = backup_dir.map(|_| "BACKUP_STAMP=\"$(date -u +%Y-%m-%d_%H-%M-%S)\"\n\n".to_string());
+
+    let commands = sources.iter()
         .map(|source| {
             // Extract folder name from source path to preserve folder structure
             // E.g., /Users/john/Documents -> Documents
@@ -332,21 +894,36 @@ fn generate_backup_commands(sources: &[String], destination: &str, operation: &s
 
             // Append source folder name to destination
             // E.g., aws:bucket/users/john-id/Documents
-            let destination_with_folder = format!("{}/{}", destination, source_folder_name);
+            let destination_with_folder = remote_join(destination, source_folder_name);
+            let flags = match backup_dir {
+                Some(backup_dir) => format!("{} --backup-dir \"{}\"", flags, remote_join(&remote_join(backup_dir, "$BACKUP_STAMP"), source_folder_name)),
+                None => flags.to_string(),
+            };
 
             format!(
                 r#"echo "$(date): Backing up {} to {}" >> "$LOG_FILE"
-"$RCLONE_BIN" {} "{}" "{}" --config "$RCLONE_CONFIG" {} --log-file "$LOG_FILE" --log-level INFO"#,
-                source, destination_with_folder, operation, source, destination_with_folder, flags
+SOURCE_LOG="$(mktemp)"
+if "$RCLONE_BIN" {} "{}" "{}" --config "$RCLONE_CONFIG" {} --log-file "$SOURCE_LOG"; then
+    :
+else
+    echo "$(date): ERROR: Backup failed for {} with exit code $?" >> "$LOG_FILE"
+    FAILED_SOURCES=$((FAILED_SOURCES + 1))
+fi
+record_source_log "$SOURCE_LOG""#,
+                source, destination_with_folder, operation, source, destination_with_folder, flags, source
             )
         })
         .collect::<Vec<_>>()
-        .join("\n\n")
+        .join("\n\n");
+
+    format!("{}{}", stamp.unwrap_or_default(), commands)
 }
 
 #[cfg(target_os = "windows")]
-fn generate_backup_commands_windows(sources: &[String], destination: &str, operation: &str, flags: &str) -> String {
-    sources.iter()
+fn generate_backup_commands_windows(sources: &[String], destination: &str, operation: &str, flags: &str, backup_dir: Option<&str>) -> String {
+    let stamp = backup_dir.map(|_| "$BackupStamp = (Get-Date).ToUniversalTime().ToString(\"yyyy-MM-dd_HH-mm-ss\")\n\n".to_string());
+
+    let commands = sources.iter()
         .map(|source| {
             // Extract folder name from source path to preserve folder structure
             let source_folder_name = std::path::Path::new(source)
@@ -355,40 +932,49 @@ fn generate_backup_commands_windows(sources: &[String], destination: &str, opera
                 .unwrap_or("unknown");
 
             // Append source folder name to destination
-            let destination_with_folder = format!("{}/{}", destination, source_folder_name);
+            let destination_with_folder = remote_join(destination, source_folder_name);
+            let flags = match backup_dir {
+                Some(backup_dir) => format!("{} --backup-dir \"{}\"", flags, remote_join(&remote_join(backup_dir, "$BackupStamp"), source_folder_name)),
+                None => flags.to_string(),
+            };
 
             format!(
                 r#"Write-Log "Backing up {} to {}"
-& $RCLONE_BIN {} "{}" "{}" --config $RCLONE_CONFIG {} --log-file $LOG_FILE --log-level INFO
+$SourceLog = Join-Path $RESULTS_DIR "$OperationId-source.log"
+& $RCLONE_BIN {} "{}" "{}" --config $RCLONE_CONFIG {} --log-file $SourceLog
 if ($LASTEXITCODE -ne 0) {{
     Write-Log "ERROR: Backup failed for {} with exit code $LASTEXITCODE"
     $BackupSuccess = $false
-}}"#,
+}}
+Record-SourceLog $SourceLog"#,
                 source, destination_with_folder,
                 operation, source, destination_with_folder, flags,
                 source
             )
         })
         .collect::<Vec<_>>()
-        .join("\n\n")
+        .join("\n\n");
+
+    format!("{}{}", stamp.unwrap_or_default(), commands)
 }
 
 // Stub for non-Windows platforms to avoid compilation errors
 #[cfg(not(target_os = "windows"))]
-fn generate_backup_commands_windows(_sources: &[String], _destination: &str, _operation: &str, _flags: &str) -> String {
+fn generate_backup_commands_windows(_sources: &[String], _destination: &str, _operation: &str, _flags: &str, _backup_dir: Option<&str>) -> String {
     String::new()
 }
 
 #[cfg(target_os = "macos")]
-async fn create_simple_launchd_schedule(profile: &Profile, schedule: &Schedule, runner_script: &PathBuf) -> Result<(), String> {
-    let plist_name = format!("com.cloudbackup.backup-{}.plist", profile.id);
-    let plist_path = dirs::home_dir()
+fn launchd_plist_path(profile: &Profile) -> Result<PathBuf, String> {
+    Ok(dirs::home_dir()
         .ok_or("Could not determine home directory")?
         .join("Library/LaunchAgents")
-        .join(&plist_name);
-
-    fs::create_dir_all(plist_path.parent().unwrap()).map_err(|e| e.to_string())?;
+        .join(format!("com.cloudbackup.backup-{}.plist", profile.id)))
+}
 
+/// The launch agent that runs `runner_script` on the schedule
+#[cfg(target_os = "macos")]
+fn launchd_plist(profile: &Profile, schedule: &Schedule, runner_script: &PathBuf) -> Result<String, String> {
     let time = NaiveTime::parse_from_str(&schedule.time, "%H:%M")
         .map_err(|_| "Invalid time format")?;
 
@@ -436,6 +1022,15 @@ async fn create_simple_launchd_schedule(profile: &Profile, schedule: &Schedule,
         profile.id
     );
 
+    Ok(plist_content)
+}
+
+#[cfg(target_os = "macos")]
+async fn create_simple_launchd_schedule(profile: &Profile, schedule: &Schedule, runner_script: &PathBuf) -> Result<(), String> {
+    let plist_path = launchd_plist_path(profile)?;
+    fs::create_dir_all(plist_path.parent().unwrap()).map_err(|e| e.to_string())?;
+
+    let plist_content = launchd_plist(profile, schedule, runner_script)?;
     fs::write(&plist_path, plist_content).map_err(|e| e.to_string())?;
 
     // Unload any existing job first
@@ -461,61 +1056,10 @@ async fn create_simple_launchd_schedule(profile: &Profile, schedule: &Schedule,
 #[cfg(target_os = "macos")]
 #[allow(dead_code)]
 async fn create_launchd_schedule(profile: &Profile, schedule: &Schedule, runner_script: &PathBuf) -> Result<(), String> {
-    let plist_name = format!("com.cloudbackup.backup-{}.plist", profile.id);
-    let plist_path = dirs::home_dir()
-        .ok_or("Could not determine home directory")?
-        .join("Library/LaunchAgents")
-        .join(&plist_name);
-
+    let plist_path = launchd_plist_path(profile)?;
     fs::create_dir_all(plist_path.parent().unwrap()).map_err(|e| e.to_string())?;
 
-    let time = NaiveTime::parse_from_str(&schedule.time, "%H:%M")
-        .map_err(|_| "Invalid time format")?;
-
-    let calendar_interval = match schedule.frequency {
-        ScheduleFrequency::Daily => format!(
-            "<dict><key>Hour</key><integer>{}</integer><key>Minute</key><integer>{}</integer></dict>",
-            time.hour(), time.minute()
-        ),
-        ScheduleFrequency::Weekly(day) => format!(
-            "<dict><key>Weekday</key><integer>{}</integer><key>Hour</key><integer>{}</integer><key>Minute</key><integer>{}</integer></dict>",
-            day, time.hour(), time.minute()
-        ),
-        ScheduleFrequency::Monthly(day) => format!(
-            "<dict><key>Day</key><integer>{}</integer><key>Hour</key><integer>{}</integer><key>Minute</key><integer>{}</integer></dict>",
-            day, time.hour(), time.minute()
-        ),
-    };
-
-    let plist_content = format!(
-        r#"<?xml version="1.0" encoding="UTF-8"?>
-<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
-<plist version="1.0">
-<dict>
-    <key>Label</key>
-    <string>com.cloudbackup.backup-{}</string>
-    <key>ProgramArguments</key>
-    <array>
-        <string>{}</string>
-    </array>
-    <key>StartCalendarInterval</key>
-    {}
-    <key>WorkingDirectory</key>
-    <string>{}</string>
-    <key>StandardOutPath</key>
-    <string>/tmp/backup-{}.out</string>
-    <key>StandardErrorPath</key>
-    <string>/tmp/backup-{}.err</string>
-</dict>
-</plist>"#,
-        profile.id,
-        runner_script.display(),
-        calendar_interval,
-        get_config_dir()?.display(),
-        profile.id,
-        profile.id
-    );
-
+    let plist_content = launchd_plist(profile, schedule, runner_script)?;
     fs::write(&plist_path, plist_content).map_err(|e| e.to_string())?;
 
     // Load the launch agent
@@ -534,7 +1078,43 @@ async fn create_launchd_schedule(profile: &Profile, schedule: &Schedule, runner_
 
 #[cfg(target_os = "windows")]
 async fn create_windows_schedule(profile: &Profile, schedule: &Schedule, runner_script: &PathBuf) -> Result<(), String> {
-    let task_name = format!("CloudBackup\\backup-{}", profile.id);
+    let task_name = windows_task_name(profile);
+    let args = schtasks_create_args(profile, schedule, runner_script)?;
+
+    // Delete existing task first (ignore errors if it doesn't exist)
+    let _ = tokio::process::Command::new("schtasks")
+        .args(&["/Delete", "/TN", &task_name, "/F"])
+        .output()
+        .await;
+
+    debug!("Creating Windows scheduled task: {}", task_name);
+    debug!("Task arguments: {:?}", args);
+
+    // Create the scheduled task
+    let output = tokio::process::Command::new("schtasks")
+        .args(&args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute schtasks: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to create scheduled task: {}", stderr));
+    }
+
+    debug!("Windows scheduled task created successfully");
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn windows_task_name(profile: &Profile) -> String {
+    format!("CloudBackup\\backup-{}", profile.id)
+}
+
+/// Arguments to `schtasks` that create the task running `runner_script` on the schedule
+#[cfg(target_os = "windows")]
+fn schtasks_create_args(profile: &Profile, schedule: &Schedule, runner_script: &PathBuf) -> Result<Vec<String>, String> {
+    let task_name = windows_task_name(profile);
 
     // Use wscript.exe to run VBScript invisibly (VBScript launches PowerShell hidden)
     let task_run = format!(
@@ -550,9 +1130,9 @@ async fn create_windows_schedule(profile: &Profile, schedule: &Schedule, runner_
     let now = Local::now();
     let today = now.date_naive();
     let today_at_scheduled_time = today.and_time(time);
-    let scheduled_datetime = Local.from_local_datetime(&today_at_scheduled_time)
-        .single()
-        .ok_or("Invalid local datetime")?;
+    let scheduled_datetime = resolve_local_time(&Local, today_at_scheduled_time)
+        .ok_or("Invalid local datetime")?
+        .with_timezone(&Local);
 
     // If the scheduled time is in the future today, start today; otherwise start tomorrow
     let start_date = if scheduled_datetime > now {
@@ -602,32 +1182,11 @@ async fn create_windows_schedule(profile: &Profile, schedule: &Schedule, runner_
         },
     };
 
-    // Delete existing task first (ignore errors if it doesn't exist)
-    let _ = tokio::process::Command::new("schtasks")
-        .args(&["/Delete", "/TN", &task_name, "/F"])
-        .output()
-        .await;
+    debug!("Current time: {}", now.format("%Y-%m-%d %H:%M:%S"));
+    debug!("Scheduled time: {}", scheduled_datetime.format("%Y-%m-%d %H:%M:%S"));
+    debug!("Start date: {}", start_date_str);
 
-    println!("[DEBUG] Creating Windows scheduled task: {}", task_name);
-    println!("[DEBUG] Current time: {}", now.format("%Y-%m-%d %H:%M:%S"));
-    println!("[DEBUG] Scheduled time: {}", scheduled_datetime.format("%Y-%m-%d %H:%M:%S"));
-    println!("[DEBUG] Start date: {}", start_date_str);
-    println!("[DEBUG] Task arguments: {:?}", args);
-
-    // Create the scheduled task
-    let output = tokio::process::Command::new("schtasks")
-        .args(&args)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to execute schtasks: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to create scheduled task: {}", stderr));
-    }
-
-    println!("[DEBUG] Windows scheduled task created successfully");
-    Ok(())
+    Ok(args.into_iter().map(str::to_string).collect())
 }
 
 #[cfg(target_os = "linux")]
@@ -641,95 +1200,141 @@ pub fn calculate_next_run(schedule: &Schedule) -> Option<DateTime<Utc>> {
         return None;
     }
 
+    next_run_after(schedule, &Local::now())
+}
+
+/// First scheduled time strictly after `now`, using `now`'s timezone for wall-clock times
+fn next_run_after<Tz: TimeZone>(schedule: &Schedule, now: &DateTime<Tz>) -> Option<DateTime<Utc>> {
     let time = NaiveTime::parse_from_str(&schedule.time, "%H:%M").ok()?;
-    let now_local = Local::now();
-    let now_utc = now_local.with_timezone(&Utc);
-    let today_local = now_local.date_naive();
-    
-    match schedule.frequency {
-        ScheduleFrequency::Daily => {
-            // Try today first - create local datetime then convert to UTC
-            let today_at_time_local = today_local.and_time(time);
-            let today_local_dt = Local.from_local_datetime(&today_at_time_local).single()?;
-            let today_utc = today_local_dt.with_timezone(&Utc);
-            
-            if today_utc > now_utc {
-                // Today's time hasn't passed yet
-                Some(today_utc)
-            } else {
-                // Tomorrow at the scheduled time
-                let tomorrow_local = today_local + Duration::days(1);
-                let tomorrow_at_time_local = tomorrow_local.and_time(time);
-                let tomorrow_local_dt = Local.from_local_datetime(&tomorrow_at_time_local).single()?;
-                let tomorrow_utc = tomorrow_local_dt.with_timezone(&Utc);
-                Some(tomorrow_utc)
-            }
-        },
-        ScheduleFrequency::Weekly(target_weekday) => {
-            let current_weekday = today_local.weekday().num_days_from_sunday() as u8;
-            let days_until_target = if target_weekday >= current_weekday {
-                target_weekday - current_weekday
-            } else {
-                7 - current_weekday + target_weekday
-            };
-            
-            let target_date = if days_until_target == 0 {
-                // It's the target weekday today
-                let today_at_time_local = today_local.and_time(time);
-                let today_local_dt = Local.from_local_datetime(&today_at_time_local).single()?;
-                let today_utc = today_local_dt.with_timezone(&Utc);
-                
-                if today_utc > now_utc {
-                    today_local // Today's time hasn't passed yet
-                } else {
-                    today_local + Duration::days(7) // Next week
-                }
-            } else {
-                today_local + Duration::days(days_until_target as i64)
-            };
-            
-            let target_datetime_local = target_date.and_time(time);
-            let target_local_dt = Local.from_local_datetime(&target_datetime_local).single()?;
-            Some(target_local_dt.with_timezone(&Utc))
-        },
-        ScheduleFrequency::Monthly(target_day) => {
-            let current_day = today_local.day() as u8;
-            
-            // Try this month first
-            if target_day >= current_day {
-                if let Some(target_date) = NaiveDate::from_ymd_opt(today_local.year(), today_local.month(), target_day as u32) {
-                    let target_datetime_local = target_date.and_time(time);
-                    let target_local_dt = Local.from_local_datetime(&target_datetime_local).single()?;
-                    let target_utc = target_local_dt.with_timezone(&Utc);
-                    
-                    if target_utc > now_utc {
-                        return Some(target_utc);
-                    }
-                }
+    let timezone = now.timezone();
+    let now_utc = now.with_timezone(&Utc);
+    let today = now.naive_local().date();
+
+    // 62 days covers a monthly schedule on the 31st skipping a short month
+    (0..=62)
+        .map(|offset| today + Duration::days(offset))
+        .filter(|date| runs_on(&schedule.frequency, *date))
+        .filter_map(|date| resolve_local_time(&timezone, date.and_time(time)))
+        .find(|candidate| *candidate > now_utc)
+}
+
+fn runs_on(frequency: &ScheduleFrequency, date: NaiveDate) -> bool {
+    match frequency {
+        ScheduleFrequency::Daily => true,
+        ScheduleFrequency::Weekly(weekday) => date.weekday().num_days_from_sunday() as u8 == *weekday,
+        ScheduleFrequency::Monthly(day) => date.day() == *day as u32,
+    }
+}
+
+/// Convert a wall-clock time to UTC across DST transitions. An ambiguous time (clocks going
+/// back) takes the later offset; a time inside a spring-forward gap moves to the first valid
+/// minute after the gap.
+fn resolve_local_time<Tz: TimeZone>(timezone: &Tz, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    match timezone.from_local_datetime(&local) {
+        LocalResult::Single(dt) => Some(dt.with_timezone(&Utc)),
+        LocalResult::Ambiguous(_, later) => Some(later.with_timezone(&Utc)),
+        LocalResult::None => (1..=180)
+            .find_map(|minutes| timezone.from_local_datetime(&(local + Duration::minutes(minutes))).earliest())
+            .map(|dt| dt.with_timezone(&Utc)),
+    }
+}
+
+/// Stop the OS job from firing while keeping its plist/task and runner script in place
+async fn pause_os_schedule(profile_id: &str) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        let plist_path = dirs::home_dir()
+            .ok_or("Could not determine home directory")?
+            .join("Library/LaunchAgents")
+            .join(format!("com.cloudbackup.backup-{}.plist", profile_id));
+
+        if plist_path.exists() {
+            let output = tokio::process::Command::new("launchctl")
+                .args(&["unload", "-w", &plist_path.to_string_lossy()])
+                .output()
+                .await
+                .map_err(|e| e.to_string())?;
+
+            if !output.status.success() {
+                debug!("launchctl unload failed (may not be loaded): {}",
+                    String::from_utf8_lossy(&output.stderr));
             }
-            
-            // Next month
-            let next_month = if today_local.month() == 12 {
-                NaiveDate::from_ymd_opt(today_local.year() + 1, 1, target_day as u32)
-            } else {
-                NaiveDate::from_ymd_opt(today_local.year(), today_local.month() + 1, target_day as u32)
-            };
-            
-            if let Some(target_date) = next_month {
-                let target_datetime_local = target_date.and_time(time);
-                let target_local_dt = Local.from_local_datetime(&target_datetime_local).single()?;
-                Some(target_local_dt.with_timezone(&Utc))
-            } else {
-                None
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let task_name = format!("CloudBackup\\backup-{}", profile_id);
+        let output = tokio::process::Command::new("schtasks")
+            .args(&["/Change", "/TN", &task_name, "/DISABLE"])
+            .output()
+            .await
+            .map_err(|e| format!("Failed to execute schtasks: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!("Failed to disable scheduled task: {}", String::from_utf8_lossy(&output.stderr)));
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let _ = profile_id; // No OS job to pause until systemd scheduling exists
+    }
+
+    Ok(())
+}
+
+/// Re-activate a paused OS job, recreating it if the plist/task is missing
+async fn resume_os_schedule(profile: &Profile, schedule: &Schedule) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        let plist_path = dirs::home_dir()
+            .ok_or("Could not determine home directory")?
+            .join("Library/LaunchAgents")
+            .join(format!("com.cloudbackup.backup-{}.plist", profile.id));
+
+        if plist_path.exists() {
+            let output = tokio::process::Command::new("launchctl")
+                .args(&["load", "-w", &plist_path.to_string_lossy()])
+                .output()
+                .await
+                .map_err(|e| e.to_string())?;
+
+            if !output.status.success() {
+                return Err(format!("Failed to load launch agent: {}", String::from_utf8_lossy(&output.stderr)));
             }
+            return Ok(());
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let task_name = format!("CloudBackup\\backup-{}", profile.id);
+        let output = tokio::process::Command::new("schtasks")
+            .args(&["/Change", "/TN", &task_name, "/ENABLE"])
+            .output()
+            .await
+            .map_err(|e| format!("Failed to execute schtasks: {}", e))?;
+
+        if output.status.success() {
+            return Ok(());
         }
+        debug!("Failed to enable task (may not exist), recreating: {}",
+            String::from_utf8_lossy(&output.stderr));
     }
+
+    create_simple_os_schedule(profile, schedule).await
 }
 
 async fn remove_os_schedule(profile: &Profile) -> Result<(), String> {
+    remove_os_schedule_by_id(&profile.id).await
+}
+
+/// Remove the OS job and runner scripts for a profile id; also used for orphans whose profile is gone
+async fn remove_os_schedule_by_id(profile_id: &str) -> Result<(), String> {
     #[cfg(target_os = "macos")]
     {
-        let plist_name = format!("com.cloudbackup.backup-{}.plist", profile.id);
+        let plist_name = format!("com.cloudbackup.backup-{}.plist", profile_id);
         let plist_path = dirs::home_dir()
             .ok_or("Could not determine home directory")?
             .join("Library/LaunchAgents")
@@ -749,7 +1354,7 @@ async fn remove_os_schedule(profile: &Profile) -> Result<(), String> {
 
     #[cfg(target_os = "windows")]
     {
-        let task_name = format!("CloudBackup\\backup-{}", profile.id);
+        let task_name = format!("CloudBackup\\backup-{}", profile_id);
 
         // Delete the scheduled task
         let output = tokio::process::Command::new("schtasks")
@@ -759,7 +1364,7 @@ async fn remove_os_schedule(profile: &Profile) -> Result<(), String> {
 
         if let Ok(output) = output {
             if !output.status.success() {
-                println!("[DEBUG] Failed to delete task (may not exist): {}",
+                debug!("Failed to delete task (may not exist): {}",
                     String::from_utf8_lossy(&output.stderr));
             }
         }
@@ -772,11 +1377,11 @@ async fn remove_os_schedule(profile: &Profile) -> Result<(), String> {
     #[cfg(windows)]
     {
         // Remove both VBScript wrapper and PowerShell script
-        let vbs_path = scripts_dir.join(format!("backup-{}.vbs", profile.id));
+        let vbs_path = scripts_dir.join(format!("backup-{}.vbs", profile_id));
         if vbs_path.exists() {
             let _ = fs::remove_file(vbs_path);
         }
-        let ps_path = scripts_dir.join(format!("backup-{}.ps1", profile.id));
+        let ps_path = scripts_dir.join(format!("backup-{}.ps1", profile_id));
         if ps_path.exists() {
             let _ = fs::remove_file(ps_path);
         }
@@ -785,11 +1390,236 @@ async fn remove_os_schedule(profile: &Profile) -> Result<(), String> {
     #[cfg(not(windows))]
     {
         let script_ext = "sh";
-        let script_path = scripts_dir.join(format!("backup-{}.{}", profile.id, script_ext));
+        let script_path = scripts_dir.join(format!("backup-{}.{}", profile_id, script_ext));
         if script_path.exists() {
             fs::remove_file(script_path).map_err(|e| e.to_string())?;
         }
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[command]
+pub async fn list_os_schedules() -> Result<Vec<OsScheduleEntry>, String> {
+    let config = load_config().await?;
+
+    let mut entries = find_os_schedule_jobs().await?;
+    entries.extend(find_runner_scripts()?);
+
+    for entry in entries.iter_mut() {
+        entry.orphaned = !config.profiles.iter().any(|p| p.id == entry.profile_id);
+    }
+
+    debug!("Found {} scheduled jobs/scripts ({} orphaned)",
+        entries.len(), entries.iter().filter(|e| e.orphaned).count());
+    Ok(entries)
+}
+
+#[command]
+pub async fn cleanup_orphaned_schedules() -> Result<ScheduleCleanupResult, String> {
+    let found = list_os_schedules().await?;
+
+    let mut orphaned_ids: Vec<String> = found.iter()
+        .filter(|entry| entry.orphaned)
+        .map(|entry| entry.profile_id.clone())
+        .collect();
+    orphaned_ids.sort();
+    orphaned_ids.dedup();
+
+    let mut removed = Vec::new();
+    for profile_id in orphaned_ids {
+        match remove_os_schedule_by_id(&profile_id).await {
+            Ok(()) => {
+                debug!("Removed orphaned schedule for deleted profile {}", profile_id);
+                removed.extend(found.iter().filter(|entry| entry.profile_id == profile_id).cloned());
+            }
+            Err(e) => error!("Failed to remove orphaned schedule for {}: {}", profile_id, e),
+        }
+    }
+
+    Ok(ScheduleCleanupResult { found, removed })
+}
+
+/// Runner scripts named backup-{profile_id}.{sh,ps1,vbs} in the scripts directory
+fn find_runner_scripts() -> Result<Vec<OsScheduleEntry>, String> {
+    let scripts_dir = get_config_dir()?.join("scripts");
+    if !scripts_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(&scripts_dir)
+        .map_err(|e| format!("Failed to read scripts directory: {}", e))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let profile_id = ["sh", "ps1", "vbs"].iter()
+                .find_map(|ext| file_name.strip_suffix(&format!(".{}", ext)))?
+                .strip_prefix("backup-")?
+                .to_string();
+            Some(OsScheduleEntry {
+                profile_id,
+                kind: "script".to_string(),
+                location: entry.path().to_string_lossy().to_string(),
+                orphaned: false,
+            })
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+#[cfg(target_os = "macos")]
+async fn find_os_schedule_jobs() -> Result<Vec<OsScheduleEntry>, String> {
+    let launch_agents = dirs::home_dir()
+        .ok_or("Could not determine home directory")?
+        .join("Library/LaunchAgents");
+    if !launch_agents.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(&launch_agents)
+        .map_err(|e| format!("Failed to read LaunchAgents: {}", e))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let profile_id = file_name
+                .strip_prefix("com.cloudbackup.backup-")?
+                .strip_suffix(".plist")?
+                .to_string();
+            Some(OsScheduleEntry {
+                profile_id,
+                kind: "launchd".to_string(),
+                location: entry.path().to_string_lossy().to_string(),
+                orphaned: false,
+            })
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+#[cfg(target_os = "windows")]
+async fn find_os_schedule_jobs() -> Result<Vec<OsScheduleEntry>, String> {
+    let output = tokio::process::Command::new("schtasks")
+        .args(&["/Query", "/FO", "CSV", "/NH"])
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute schtasks: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("Failed to list scheduled tasks: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    // Each line looks like: "\CloudBackup\backup-<id>","<next run>","<status>"
+    let mut entries: Vec<OsScheduleEntry> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let task_name = line.split(',').next()?.trim_matches('"');
+            let profile_id = task_name.strip_prefix("\\CloudBackup\\backup-")?.to_string();
+            Some(OsScheduleEntry {
+                profile_id,
+                kind: "task_scheduler".to_string(),
+                location: task_name.trim_start_matches('\\').to_string(),
+                orphaned: false,
+            })
+        })
+        .collect();
+
+    // schtasks repeats a task once per trigger
+    entries.dedup_by(|a, b| a.profile_id == b.profile_id);
+    Ok(entries)
+}
+
+#[cfg(target_os = "linux")]
+async fn find_os_schedule_jobs() -> Result<Vec<OsScheduleEntry>, String> {
+    // No systemd integration yet (see create_systemd_schedule), so only runner scripts exist
+    Ok(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::America::New_York;
+
+    fn schedule(frequency: ScheduleFrequency, time: &str) -> Schedule {
+        Schedule {
+            enabled: true,
+            frequency,
+            time: time.to_string(),
+            last_run: None,
+            next_run: None,
+        }
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn weekly_time_in_spring_forward_gap_moves_past_gap() {
+        // 2025-03-09 02:00 EST jumps to 03:00 EDT, so 02:30 doesn't exist that Sunday
+        let now = New_York.with_ymd_and_hms(2025, 3, 8, 12, 0, 0).unwrap();
+        let next = next_run_after(&schedule(ScheduleFrequency::Weekly(0), "02:30"), &now);
+        assert_eq!(next, Some(utc("2025-03-09T07:00:00Z"))); // 03:00 EDT
+    }
+
+    #[test]
+    fn daily_time_in_fall_back_overlap_uses_later_offset() {
+        // 2025-11-02 01:30 happens twice: 01:30 EDT (05:30Z) and 01:30 EST (06:30Z)
+        let now = New_York.with_ymd_and_hms(2025, 11, 1, 23, 0, 0).unwrap();
+        let next = next_run_after(&schedule(ScheduleFrequency::Daily, "01:30"), &now);
+        assert_eq!(next, Some(utc("2025-11-02T06:30:00Z")));
+    }
+
+    #[test]
+    fn weekly_keeps_wall_clock_time_across_dst() {
+        // Saturday in EST, next Sunday run is in EDT: 09:00 EDT is 13:00Z, not 14:00Z
+        let now = New_York.with_ymd_and_hms(2025, 3, 8, 12, 0, 0).unwrap();
+        let next = next_run_after(&schedule(ScheduleFrequency::Weekly(0), "09:00"), &now);
+        assert_eq!(next, Some(utc("2025-03-09T13:00:00Z")));
+    }
+
+    #[test]
+    fn weekly_on_target_day_after_time_rolls_to_next_week() {
+        // Sunday 2025-03-16 10:00 EDT, schedule at 09:00 on Sundays
+        let now = New_York.with_ymd_and_hms(2025, 3, 16, 10, 0, 0).unwrap();
+        let next = next_run_after(&schedule(ScheduleFrequency::Weekly(0), "09:00"), &now);
+        assert_eq!(next, Some(utc("2025-03-23T13:00:00Z")));
+    }
+
+    #[test]
+    fn monthly_skips_months_without_the_day() {
+        let now = New_York.with_ymd_and_hms(2025, 1, 31, 12, 0, 0).unwrap();
+        let next = next_run_after(&schedule(ScheduleFrequency::Monthly(31), "09:00"), &now);
+        assert_eq!(next, Some(utc("2025-03-31T13:00:00Z")));
+    }
+
+    #[test]
+    fn disabled_schedule_has_no_next_run() {
+        let mut paused = schedule(ScheduleFrequency::Daily, "09:00");
+        paused.enabled = false;
+        assert_eq!(calculate_next_run(&paused), None);
+    }
+
+    #[test]
+    fn destinations_overlap_only_on_folder_boundaries() {
+        assert!(destinations_overlap("aws:bucket/users/a", "aws:bucket/users/a/"));
+        assert!(destinations_overlap("aws:bucket", "aws:bucket/users/a"));
+        assert!(destinations_overlap("/Volumes/Backup/lavoz/Docs", "/Volumes/Backup/lavoz"));
+        assert!(!destinations_overlap("aws:bucket/users/a", "aws:bucket/users/ab"));
+        assert!(!destinations_overlap("aws:bucket", "aws:bucket2"));
+    }
+
+    #[test]
+    fn daily_and_weekly_schedules_overlap_on_the_weekly_day() {
+        // Saturday; the weekly run is Sunday 09:30, 30 minutes after the daily 09:00 run
+        let now = New_York.with_ymd_and_hms(2025, 3, 15, 12, 0, 0).unwrap();
+        let daily = schedule(ScheduleFrequency::Daily, "09:00");
+        let weekly = schedule(ScheduleFrequency::Weekly(0), "09:30");
+
+        let (first, apart) = first_schedule_overlap(&daily, &weekly, &now, Duration::minutes(60)).unwrap();
+        assert_eq!(first, utc("2025-03-16T13:00:00Z"));
+        assert_eq!(apart, Duration::minutes(30));
+        assert_eq!(first_schedule_overlap(&daily, &weekly, &now, Duration::minutes(15)), None);
+    }
+}
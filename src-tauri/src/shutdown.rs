@@ -0,0 +1,78 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind, MessageDialogResult};
+use tracing::debug;
+
+use crate::process::request_shutdown;
+use crate::run_lock::active_run_count;
+
+/// Set once the user has picked how to quit, so the final exit goes through
+static QUITTING: AtomicBool = AtomicBool::new(false);
+/// Set while the quit prompt is open, so repeated close clicks don't stack dialogs
+static PROMPTING: AtomicBool = AtomicBool::new(false);
+
+/// How long cancelled runs get to stop rclone and save their final state
+const CANCEL_GRACE: Duration = Duration::from_secs(15);
+
+const CANCEL_AND_QUIT: &str = "Cancel and Quit";
+const QUIT_WHEN_FINISHED: &str = "Quit When Finished";
+const KEEP_OPEN: &str = "Keep Open";
+
+/// Called for window close and app exit requests. Returns true when the request must be
+/// held back because a backup or restore is running; the user is then asked whether to
+/// cancel it or wait for it, and the app exits once every run has stopped and saved.
+pub fn intercept_exit(app: &AppHandle) -> bool {
+    if QUITTING.load(Ordering::SeqCst) {
+        return false;
+    }
+    let running = active_run_count();
+    if running == 0 {
+        return false;
+    }
+    if PROMPTING.swap(true, Ordering::SeqCst) {
+        return true;
+    }
+
+    let app_handle = app.clone();
+    app.dialog()
+        .message(format!(
+            "{} backup or restore operation(s) still running. Cancelling stops rclone and marks the operation as cancelled.",
+            running
+        ))
+        .title("Operation in progress")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::YesNoCancelCustom(
+            CANCEL_AND_QUIT.to_string(),
+            QUIT_WHEN_FINISHED.to_string(),
+            KEEP_OPEN.to_string(),
+        ))
+        .show_with_result(move |result| {
+            PROMPTING.store(false, Ordering::SeqCst);
+            let cancel = match result {
+                MessageDialogResult::Yes => true,
+                MessageDialogResult::No => false,
+                MessageDialogResult::Custom(label) if label == CANCEL_AND_QUIT => true,
+                MessageDialogResult::Custom(label) if label == QUIT_WHEN_FINISHED => false,
+                _ => return,
+            };
+
+            QUITTING.store(true, Ordering::SeqCst);
+            if cancel {
+                debug!("Quit requested, cancelling {} running operation(s)", active_run_count());
+                request_shutdown();
+            } else {
+                debug!("Quit requested, waiting for {} running operation(s)", active_run_count());
+            }
+
+            tauri::async_runtime::spawn(async move {
+                let deadline = cancel.then(|| Instant::now() + CANCEL_GRACE);
+                while active_run_count() > 0 && deadline.is_none_or(|deadline| Instant::now() < deadline) {
+                    tokio::time::sleep(Duration::from_millis(250)).await;
+                }
+                app_handle.exit(0);
+            });
+        });
+
+    true
+}
@@ -0,0 +1,116 @@
+/// Parse a size like "1.5 MiB", "66 B", "5G" or "1,024 KB" into bytes.
+/// Single-letter suffixes follow rclone and are binary (k = 1024), as are KiB/MiB/...;
+/// KB/MB/... are decimal. A bare number is bytes. Commas are thousands separators.
+pub fn parse_byte_size(size_str: &str) -> Result<u64, String> {
+    let cleaned = size_str.replace([',', ' '], "");
+    let split = cleaned
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(cleaned.len());
+    let (number, suffix) = cleaned.split_at(split);
+
+    let multiplier: f64 = match suffix {
+        "" | "B" | "b" => 1.0,
+        "k" | "K" | "Ki" | "KiB" => 1024.0,
+        "KB" | "kB" => 1000.0,
+        "M" | "m" | "Mi" | "MiB" => 1024.0 * 1024.0,
+        "MB" => 1000.0 * 1000.0,
+        "G" | "g" | "Gi" | "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "GB" => 1000.0 * 1000.0 * 1000.0,
+        "T" | "t" | "Ti" | "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        "TB" => 1000.0 * 1000.0 * 1000.0 * 1000.0,
+        "P" | "p" | "Pi" | "PiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        "PB" => 1000.0 * 1000.0 * 1000.0 * 1000.0 * 1000.0,
+        _ => return Err(format!("Invalid size '{}': unknown unit '{}'", size_str.trim(), suffix)),
+    };
+
+    let value: f64 = number
+        .parse()
+        .map_err(|_| format!("Invalid size '{}': expected a number like 500K, 1.5M or 5G", size_str.trim()))?;
+
+    // Rounded, since the product of a decimal like 4.35 and 1000 lands just below 4350
+    Ok((value * multiplier).round() as u64)
+}
+
+/// Format bytes the way rclone's stats do, with binary units: "0 B", "512 B", "1.500 MiB"
+pub fn format_byte_size(bytes: f64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+
+    if bytes < 1024.0 {
+        return format!("{} B", bytes.max(0.0).round() as u64);
+    }
+    let mut value = bytes;
+    let mut unit = UNITS[0];
+    for candidate in UNITS {
+        value /= 1024.0;
+        unit = candidate;
+        if value < 1024.0 {
+            break;
+        }
+    }
+    format!("{:.3} {}", value, unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_like_rclone_stats() {
+        assert_eq!(format_byte_size(0.0), "0 B");
+        assert_eq!(format_byte_size(512.0), "512 B");
+        assert_eq!(format_byte_size(1536.0), "1.500 KiB");
+        assert_eq!(format_byte_size(1.5 * 1024.0 * 1024.0), "1.500 MiB");
+        assert_eq!(parse_byte_size(&format_byte_size(5.0 * 1024.0 * 1024.0 * 1024.0)), Ok(5 * 1024 * 1024 * 1024));
+    }
+
+    #[test]
+    fn bare_numbers_and_bytes() {
+        assert_eq!(parse_byte_size("0"), Ok(0));
+        assert_eq!(parse_byte_size("0 B"), Ok(0));
+        assert_eq!(parse_byte_size("66 B"), Ok(66));
+        assert_eq!(parse_byte_size("66b"), Ok(66));
+        assert_eq!(parse_byte_size("1234"), Ok(1234));
+        assert_eq!(parse_byte_size("  512  "), Ok(512));
+    }
+
+    #[test]
+    fn binary_and_decimal_units() {
+        const KI: u64 = 1024;
+        const K: u64 = 1000;
+        let cases = [
+            ("1k", KI), ("1K", KI), ("1Ki", KI), ("1KiB", KI), ("1KB", K), ("1kB", K),
+            ("1M", KI.pow(2)), ("1m", KI.pow(2)), ("1MiB", KI.pow(2)), ("1MB", K.pow(2)),
+            ("1G", KI.pow(3)), ("1g", KI.pow(3)), ("1GiB", KI.pow(3)), ("1GB", K.pow(3)),
+            ("1T", KI.pow(4)), ("1TiB", KI.pow(4)), ("1TB", K.pow(4)),
+            ("1P", KI.pow(5)), ("1PiB", KI.pow(5)), ("1PB", K.pow(5)),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_byte_size(input), Ok(expected), "{}", input);
+        }
+    }
+
+    #[test]
+    fn decimals_round_to_the_nearest_byte() {
+        assert_eq!(parse_byte_size("1.5 MiB"), Ok(1_572_864));
+        assert_eq!(parse_byte_size("4.35 KB"), Ok(4350));
+        assert_eq!(parse_byte_size("0.5"), Ok(1));
+        assert_eq!(parse_byte_size(".5K"), Ok(512));
+        assert_eq!(parse_byte_size("2. GB"), Ok(2_000_000_000));
+    }
+
+    #[test]
+    fn thousands_separators() {
+        assert_eq!(parse_byte_size("1,024 KB"), Ok(1_024_000));
+        assert_eq!(parse_byte_size("1,234.5 MiB"), Ok(1_294_467_072));
+        // 1234.56 * 2^30 = 1325598706237.44
+        assert_eq!(parse_byte_size("1,234.56 GiB"), Ok(1_325_598_706_237));
+        assert_eq!(parse_byte_size("12,345,678"), Ok(12_345_678));
+    }
+
+    #[test]
+    fn rejects_malformed_sizes() {
+        for input in ["", " ", "MB", "1.2.3 MB", "abc", "5 XB", "-5M", "1e3"] {
+            assert!(parse_byte_size(input).is_err(), "{} should be rejected", input);
+        }
+    }
+}
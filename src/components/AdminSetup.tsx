@@ -9,7 +9,7 @@ import {
   Plus,
   Trash2
 } from 'lucide-react';
-import { Profile, AwsConfig, LifecycleConfig } from '../types';
+import { Profile, AwsConfig, AwsSetupPlan, LifecycleConfig, AppSettings } from '../types';
 
 interface AdminSetupProps {
   onSetupComplete: (profile: Profile) => void;
@@ -38,18 +38,30 @@ export default function AdminSetup({ onSetupComplete, onCancel }: AdminSetupProp
     lifecycle_config: {
       enabled: true,
       days_to_ia: 30,
-      days_to_glacier: 90
+      days_to_glacier: 90,
+      days_to_deep_archive: 999999
     },
     employees: []
   });
   const [isConfiguring, setIsConfiguring] = useState(false);
   const [awsConfigured, setAwsConfigured] = useState(false);
   const [validationErrors, setValidationErrors] = useState<Record<string, string>>({});
+  const [setupPlan, setSetupPlan] = useState<AwsSetupPlan | null>(null);
 
   useEffect(() => {
     checkExistingAWS();
+    loadDefaultRegion();
   }, []);
 
+  const loadDefaultRegion = async () => {
+    try {
+      const settings = await invoke<AppSettings>('get_settings');
+      setSetupData(prev => ({ ...prev, aws_region: settings.default_region }));
+    } catch (error) {
+      console.error('Error loading app settings:', error);
+    }
+  };
+
   const checkExistingAWS = async () => {
     try {
       const isConfigured = await invoke<boolean>('check_aws_credentials');
@@ -164,7 +176,27 @@ export default function AdminSetup({ onSetupComplete, onCancel }: AdminSetupProp
     }));
   };
 
-  const setupInfrastructure = async () => {
+  const previewInfrastructure = async () => {
+    if (!validateStep(currentStep)) return;
+
+    try {
+      const plan = await invoke<AwsSetupPlan>('setup_aws_infrastructure', {
+        bucketName: setupData.bucket_name,
+        region: setupData.aws_region,
+        adminUsername: setupData.admin_username,
+        lifecycleConfig: setupData.lifecycle_config,
+        employees: setupData.employees.filter(emp => emp.trim() !== ''),
+        profileName: 'lavoz-cloud-app-test',
+        dryRun: true
+      });
+      setSetupPlan(plan);
+    } catch (error) {
+      console.error('Infrastructure preview failed:', error);
+      alert('Infrastructure preview failed: ' + error);
+    }
+  };
+
+  const setupInfrastructure = async (replaceExistingKeys = false) => {
     if (!validateStep(currentStep)) return;
 
     setIsConfiguring(true);
@@ -178,7 +210,8 @@ export default function AdminSetup({ onSetupComplete, onCancel }: AdminSetupProp
         adminUsername: setupData.admin_username,
         lifecycleConfig: setupData.lifecycle_config,
         employees,
-        profileName: 'lavoz-cloud-app-test'
+        profileName: 'lavoz-cloud-app-test',
+        replaceExistingKeys
       });
 
       // Create admin profile
@@ -201,6 +234,13 @@ export default function AdminSetup({ onSetupComplete, onCancel }: AdminSetupProp
       onSetupComplete(updatedProfile);
     } catch (error) {
       console.error('Infrastructure setup failed:', error);
+      // Existing keys can't be reused, only replaced; ask before deleting them
+      if (!replaceExistingKeys && String(error).includes('already have access keys')) {
+        if (confirm(`${error}\n\n${t('adminSetup.replaceKeysConfirm')}`)) {
+          return setupInfrastructure(true);
+        }
+        return;
+      }
       alert('Infrastructure setup failed: ' + error);
     } finally {
       setIsConfiguring(false);
@@ -395,6 +435,26 @@ export default function AdminSetup({ onSetupComplete, onCancel }: AdminSetupProp
                     <option value="never">Never (Keep in Standard-IA)</option>
                   </select>
                 </div>
+
+                <div className="form-group">
+                  <label htmlFor="days-to-deep-archive">{t('adminSetup.daysToDeepArchive')}</label>
+                  <select
+                    id="days-to-deep-archive"
+                    value={setupData.lifecycle_config.days_to_deep_archive === 999999 ? 'never' : setupData.lifecycle_config.days_to_deep_archive.toString()}
+                    onChange={(e) => setSetupData(prev => ({
+                      ...prev,
+                      lifecycle_config: {
+                        ...prev.lifecycle_config,
+                        days_to_deep_archive: e.target.value === 'never' ? 999999 : parseInt(e.target.value)
+                      }
+                    }))}
+                  >
+                    <option value="180">180 days</option>
+                    <option value="365">1 year</option>
+                    <option value="730">2 years</option>
+                    <option value="never">{t('adminSetup.neverDeepArchive')}</option>
+                  </select>
+                </div>
               </div>
             )}
           </div>
@@ -470,6 +530,7 @@ export default function AdminSetup({ onSetupComplete, onCancel }: AdminSetupProp
                 <div className="sub-items">
                   <div>{t('adminSetup.standardIAAfter')} {setupData.lifecycle_config.days_to_ia} {t('adminSetup.days')}</div>
                   <div>{t('adminSetup.glacier')}: {setupData.lifecycle_config.days_to_glacier === 999999 ? t('adminSetup.never') : `${t('adminSetup.after')} ${setupData.lifecycle_config.days_to_glacier} ${t('adminSetup.days')}`}</div>
+                  <div>{t('adminSetup.deepArchive')}: {setupData.lifecycle_config.days_to_deep_archive === 999999 ? t('adminSetup.neverDeepArchive') : `${t('adminSetup.after')} ${setupData.lifecycle_config.days_to_deep_archive} ${t('adminSetup.days')}`}</div>
                 </div>
               )}
             </div>
@@ -492,9 +553,47 @@ export default function AdminSetup({ onSetupComplete, onCancel }: AdminSetupProp
             </div>
           </div>
 
+          {setupPlan && (
+            <div className="review-section">
+              <div className="review-item">
+                <strong>{t('adminSetup.planBucket')}:</strong>
+                <div className="sub-items">
+                  {setupPlan.bucket_settings.map((setting, idx) => (
+                    <div key={idx}>{setting}</div>
+                  ))}
+                </div>
+              </div>
+
+              <div className="review-item">
+                <strong>{t('adminSetup.planUsers')}:</strong>
+                {setupPlan.users.map((user) => (
+                  <details key={user.username} className="sub-items">
+                    <summary>{user.username} ({user.policy_name})</summary>
+                    <pre>{JSON.stringify(JSON.parse(user.policy_document), null, 2)}</pre>
+                  </details>
+                ))}
+              </div>
+
+              {setupPlan.warnings.map((warning, idx) => (
+                <div key={idx} className="warning-box">
+                  <AlertCircle size={16} />
+                  <div>{warning}</div>
+                </div>
+              ))}
+            </div>
+          )}
+
+          <button
+            className="btn btn-secondary"
+            onClick={previewInfrastructure}
+            disabled={isConfiguring}
+          >
+            {t('adminSetup.previewChanges')}
+          </button>
+
           <button 
             className="btn btn-primary btn-large"
-            onClick={setupInfrastructure}
+            onClick={() => setupInfrastructure()}
             disabled={isConfiguring}
           >
             {isConfiguring ? t('adminSetup.settingUpInfrastructure') : t('adminSetup.createInfrastructure')}
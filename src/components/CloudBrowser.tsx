@@ -1,6 +1,7 @@
 import { useState, useEffect } from 'react';
 import { useTranslation } from 'react-i18next';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { open } from '@tauri-apps/plugin-dialog';
 import { 
   Folder, 
@@ -13,7 +14,14 @@ import {
   Calendar,
   HardDrive
 } from 'lucide-react';
-import { Profile, CloudFile, BackupOperation } from '../types';
+import { Profile, CloudFile, CloudFilesPage, CloudFilesBatch, CloudSearchBatch, BackupOperation, RestoreProgress, GlacierRestoreRequest, GlacierRestoreStatus } from '../types';
+import { errorKind, errorMessage } from '../services/backupErrors';
+
+// Entries per page of a folder listing
+const PAGE_SIZE = 500;
+
+// Days S3 keeps the thawed copy of archived files requested from here
+const ARCHIVE_RESTORE_DAYS = 7;
 
 interface CloudBrowserProps {
   profile: Profile | null;
@@ -24,9 +32,16 @@ export default function CloudBrowser({ profile }: CloudBrowserProps) {
   const [files, setFiles] = useState<CloudFile[]>([]);
   const [currentPath, setCurrentPath] = useState('');
   const [loading, setLoading] = useState(false);
+  const [nextPage, setNextPage] = useState<number | null>(null);
+  const [totalCount, setTotalCount] = useState(0);
   const [selectedFiles, setSelectedFiles] = useState<Set<string>>(new Set());
   const [searchTerm, setSearchTerm] = useState('');
+  // Matches from searching the current folder and everything below it; null when not searching
+  const [searchResults, setSearchResults] = useState<CloudFile[] | null>(null);
+  const [searching, setSearching] = useState(false);
   const [isRestoring, setIsRestoring] = useState(false);
+  const [preserveMetadata, setPreserveMetadata] = useState(false);
+  const [restoreProgress, setRestoreProgress] = useState<RestoreProgress | null>(null);
 
   useEffect(() => {
     if (profile) {
@@ -35,11 +50,40 @@ export default function CloudBrowser({ profile }: CloudBrowserProps) {
   }, [profile]);
 
   useEffect(() => {
+    setSearchResults(null);
     if (profile) {
       loadFiles(currentPath);
     }
   }, [currentPath]);
 
+  const searchFiles = async () => {
+    const pattern = searchTerm.trim();
+    if (!profile || !pattern) return;
+
+    setSearching(true);
+    setSearchResults([]);
+    const unlistenResults = await listen<CloudSearchBatch>('cloud-search-results', (event) => {
+      if (event.payload.pattern === pattern) {
+        setSearchResults(prev => [...(prev || []), ...event.payload.files]);
+      }
+    });
+    try {
+      const matches = await invoke<CloudFile[]>('search_cloud_files', {
+        profile,
+        pattern,
+        path: currentPath || null
+      });
+      setSearchResults(matches);
+    } catch (error) {
+      console.error('Failed to search cloud files:', error);
+      alert(t('cloudBrowser.searchFailed') + ': ' + errorMessage(error));
+      setSearchResults(null);
+    } finally {
+      unlistenResults();
+      setSearching(false);
+    }
+  };
+
   const loadFiles = async (path: string) => {
     if (!profile) return;
 
@@ -52,16 +96,29 @@ export default function CloudBrowser({ profile }: CloudBrowserProps) {
     }
 
     setLoading(true);
+    setFiles([]);
+    setNextPage(null);
+    // Show entries of big folders as they are listed; the first page replaces them when done
+    const unlistenBatch = await listen<CloudFilesBatch>('cloud-files-batch', (event) => {
+      if (event.payload.path === path) {
+        setFiles(prev => prev.length >= PAGE_SIZE ? prev : [...prev, ...event.payload.files].slice(0, PAGE_SIZE));
+        setTotalCount(event.payload.listed);
+      }
+    });
     try {
-      const cloudFiles = await invoke<CloudFile[]>('list_cloud_files', {
+      // Only the immediate children of the current directory; folders are listed when opened
+      const firstPage = await invoke<CloudFilesPage>('list_cloud_files_page', {
         profile,
         path: path || null,
-        maxDepth: 1 // Always show only immediate children of current directory
+        page: 0,
+        pageSize: PAGE_SIZE
       });
-      setFiles(cloudFiles);
+      setFiles(firstPage.files);
+      setTotalCount(firstPage.total_count);
+      setNextPage(firstPage.has_more ? 1 : null);
     } catch (error) {
       console.error('Failed to load cloud files:', error);
-      const errorStr = String(error).toLowerCase();
+      const errorStr = errorMessage(error).toLowerCase();
       
       // Handle common scenarios gracefully - don't show popup for these cases
       if (errorStr.includes('no such file or directory') || 
@@ -80,6 +137,28 @@ export default function CloudBrowser({ profile }: CloudBrowserProps) {
         // Replace alert with console.error to avoid popup
         // alert('Failed to load files: ' + error);
       }
+    } finally {
+      unlistenBatch();
+      setLoading(false);
+    }
+  };
+
+  const loadMore = async () => {
+    if (!profile || nextPage === null) return;
+
+    setLoading(true);
+    try {
+      const more = await invoke<CloudFilesPage>('list_cloud_files_page', {
+        profile,
+        path: currentPath || null,
+        page: nextPage,
+        pageSize: PAGE_SIZE
+      });
+      setFiles(prev => [...prev, ...more.files]);
+      setTotalCount(more.total_count);
+      setNextPage(more.has_more ? nextPage + 1 : null);
+    } catch (error) {
+      console.error('Failed to load more files:', error);
     } finally {
       setLoading(false);
     }
@@ -129,9 +208,56 @@ export default function CloudBrowser({ profile }: CloudBrowserProps) {
     setSelectedFiles(new Set());
   };
 
+  // Called when a restore hit Glacier/Deep Archive files: report a thaw that is already
+  // under way, or offer to start one
+  const offerArchiveRestore = async (paths: string[], reason: string) => {
+    if (!profile) return;
+
+    try {
+      const statuses = await Promise.all(paths.map(path =>
+        invoke<GlacierRestoreStatus>('check_restore_status', { profile, path })
+      ));
+      const notRequested = statuses.reduce((sum, status) => sum + status.not_requested, 0);
+      const inProgress = statuses.reduce((sum, status) => sum + status.in_progress, 0);
+
+      if (notRequested === 0) {
+        alert(t('cloudBrowser.archiveRestoreInProgress', { count: inProgress }));
+        return;
+      }
+      if (!confirm(`${reason}\n\n${t('cloudBrowser.archiveRestorePrompt', { count: notRequested, days: ARCHIVE_RESTORE_DAYS })}`)) {
+        return;
+      }
+
+      const request = await invoke<GlacierRestoreRequest>('request_glacier_restore', {
+        profile,
+        paths,
+        tier: 'Standard',
+        days: ARCHIVE_RESTORE_DAYS
+      });
+      const failed = request.failed.length > 0 ? `\n\n${request.failed.join('\n')}` : '';
+      alert(t('cloudBrowser.archiveRestoreRequested', {
+        count: request.requested + request.already_in_progress,
+        thaw: request.estimated_thaw
+      }) + failed);
+    } catch (error) {
+      console.error('Archive restore request failed:', error);
+      alert(`${t('cloudBrowser.archiveRestoreFailed')}: ${errorMessage(error)}`);
+    }
+  };
+
   const restoreSelected = async () => {
     if (!profile || selectedFiles.size === 0) return;
 
+    let unlistenProgress: (() => void) | undefined;
+    // Build full paths from bucket root by combining currentPath with file paths
+    const filesToRestore = Array.from(selectedFiles).map(filePath => {
+      // If we're in a subdirectory, prepend currentPath to the file path
+      if (currentPath) {
+        return `${currentPath}/${filePath}`;
+      }
+      return filePath;
+    });
+
     try {
       // Show folder picker dialog
       const localTarget = await open({
@@ -145,14 +271,8 @@ export default function CloudBrowser({ profile }: CloudBrowserProps) {
       }
 
       setIsRestoring(true);
-
-      // Build full paths from bucket root by combining currentPath with file paths
-      const filesToRestore = Array.from(selectedFiles).map(filePath => {
-        // If we're in a subdirectory, prepend currentPath to the file path
-        if (currentPath) {
-          return `${currentPath}/${filePath}`;
-        }
-        return filePath;
+      unlistenProgress = await listen<RestoreProgress>('restore-progress', (event) => {
+        setRestoreProgress(event.payload);
       });
 
       console.log('Starting restore operation:', {
@@ -162,30 +282,60 @@ export default function CloudBrowser({ profile }: CloudBrowserProps) {
         files: filesToRestore
       });
 
-      const operation = await invoke<BackupOperation>('restore_files', {
+      const runRestore = (force: boolean, resume = false) => invoke<BackupOperation>('restore_files', {
         profile,
         remotePaths: filesToRestore,
-        localTarget
+        localTarget,
+        overwrite: true,
+        force,
+        resume,
+        preserveMetadata
       });
 
+      let operation: BackupOperation;
+      try {
+        operation = await runRestore(false);
+      } catch (error) {
+        // The disk space preflight can be overridden by the user
+        if (!errorMessage(error).includes('Not enough disk space') || !confirm(`${errorMessage(error)}\n\nRestore anyway?`)) {
+          throw error;
+        }
+        operation = await runRestore(true);
+      }
+
+      // Retrying with resume keeps the files the failed attempt already downloaded
+      while (operation.status !== 'Completed' &&
+             confirm(`${t('cloudBrowser.restoreResumePrompt')}\n\n${operation.error_message || ''}`)) {
+        operation = await runRestore(true, true);
+      }
+
       console.log('Restore operation completed:', operation);
 
       // Show detailed success message
       const message = operation.status === 'Completed' 
-        ? `✅ Restore completed successfully!\n\n• ${operation.files_transferred} files restored\n• ${formatFileSize(operation.bytes_transferred)} transferred\n• Target: ${localTarget}`
+        ? `✅ Restore completed successfully!\n\n• ${operation.files_transferred} files restored\n${operation.files_skipped ? `• ${operation.files_skipped} files already present, skipped\n` : ''}• ${formatFileSize(operation.bytes_transferred)} transferred\n• Target: ${localTarget}`
         : `⚠️ Restore completed with issues:\n\n• ${operation.files_transferred} files processed\n• Error: ${operation.error_message || 'Unknown error'}`;
 
       alert(message);
       clearSelection();
     } catch (error) {
       console.error('Restore failed:', error);
-      alert(t('cloudBrowser.restoreError') + `:\n\n${error}`);
+      const kind = errorKind(error);
+      if (kind === 'ArchivedObjects') {
+        await offerArchiveRestore(filesToRestore, errorMessage(error));
+        return;
+      }
+      const hint = kind ? t(`backupErrors.${kind}`, { defaultValue: '' }) : '';
+      alert(t('cloudBrowser.restoreError') + `:\n\n${errorMessage(error)}` + (hint ? `\n\n${hint}` : ''));
     } finally {
+      unlistenProgress?.();
+      setRestoreProgress(null);
       setIsRestoring(false);
     }
   };
 
   const getFilteredFiles = (): CloudFile[] => {
+    if (searchResults) return searchResults;
     if (!searchTerm) return files;
     
     return files.filter(file => 
@@ -272,9 +422,23 @@ export default function CloudBrowser({ profile }: CloudBrowserProps) {
               type="text"
               placeholder={t('cloudBrowser.searchFiles')}
               value={searchTerm}
-              onChange={(e) => setSearchTerm(e.target.value)}
+              onChange={(e) => {
+                setSearchTerm(e.target.value);
+                setSearchResults(null);
+              }}
+              onKeyDown={(e) => {
+                if (e.key === 'Enter') searchFiles();
+              }}
             />
           </div>
+          <button
+            className="btn btn-small btn-secondary"
+            onClick={searchFiles}
+            disabled={searching || !searchTerm.trim()}
+            title={t('cloudBrowser.searchSubfoldersHint')}
+          >
+            {searching ? t('cloudBrowser.searching') : t('cloudBrowser.searchSubfolders')}
+          </button>
           <button 
             className="btn-icon"
             onClick={() => loadFiles(currentPath)}
@@ -292,6 +456,15 @@ export default function CloudBrowser({ profile }: CloudBrowserProps) {
             {selectedFiles.size} {t('cloudBrowser.filesSelected', { defaultValue: 'file(s) selected' })}
           </div>
           <div className="selection-actions">
+            <label className="checkbox-label" title={t('cloudBrowser.preserveMetadataHint')}>
+              <input
+                type="checkbox"
+                checked={preserveMetadata}
+                onChange={(e) => setPreserveMetadata(e.target.checked)}
+                disabled={isRestoring}
+              />
+              <span>{t('cloudBrowser.preserveMetadata')}</span>
+            </label>
             <button 
               className="btn btn-secondary"
               onClick={clearSelection}
@@ -304,7 +477,11 @@ export default function CloudBrowser({ profile }: CloudBrowserProps) {
               disabled={isRestoring}
             >
               <Download size={16} />
-              {isRestoring ? t('cloudBrowser.restoring') : t('cloudBrowser.restoreSelected')}
+              {isRestoring
+                ? restoreProgress
+                  ? `${t('cloudBrowser.restoring')} ${restoreProgress.path_index + 1}/${restoreProgress.path_count} (${restoreProgress.percent}%${restoreProgress.eta ? `, ETA ${restoreProgress.eta}` : ''})`
+                  : t('cloudBrowser.restoring')
+                : t('cloudBrowser.restoreSelected')}
             </button>
           </div>
         </div>
@@ -312,7 +489,7 @@ export default function CloudBrowser({ profile }: CloudBrowserProps) {
 
       {/* File List */}
       <div className="file-list-container">
-        {loading ? (
+        {(loading && files.length === 0) || (searching && filteredFiles.length === 0) ? (
           <div className="loading-state">
             <div className="loading-spinner"></div>
             <p>{t('cloudBrowser.loadingFiles', { defaultValue: 'Loading files...' })}</p>
@@ -343,7 +520,7 @@ export default function CloudBrowser({ profile }: CloudBrowserProps) {
 
             <div className="file-items">
               {/* Parent directory navigation */}
-              {currentPath && (
+              {currentPath && !searchResults && (
                 <div 
                   className="file-item directory"
                   onClick={navigateUp}
@@ -381,7 +558,7 @@ export default function CloudBrowser({ profile }: CloudBrowserProps) {
                   </div>
                   
                   <div className="file-info">
-                    <div className="file-name">{file.name}</div>
+                    <div className="file-name">{searchResults ? file.path : file.name}</div>
                     <div className="file-meta">
                       {!file.is_dir && (
                         <>
@@ -414,6 +591,17 @@ export default function CloudBrowser({ profile }: CloudBrowserProps) {
                 </div>
               ))}
             </div>
+
+            {!searchResults && (nextPage !== null || loading) && (
+              <div className="file-list-footer">
+                <span>{t('cloudBrowser.showingCount', { shown: files.length, total: totalCount })}</span>
+                {nextPage !== null && (
+                  <button className="btn btn-small btn-secondary" onClick={loadMore} disabled={loading}>
+                    {t('cloudBrowser.loadMore')}
+                  </button>
+                )}
+              </div>
+            )}
           </div>
         )}
       </div>
@@ -2,6 +2,8 @@ import { useState, useEffect } from 'react';
 import { useNavigate } from 'react-router-dom';
 import { useTranslation } from 'react-i18next';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { open, save } from '@tauri-apps/plugin-dialog';
 import {
   Play,
   Eye,
@@ -12,24 +14,48 @@ import {
   Calendar,
   Activity,
   Settings as SettingsIcon,
-  Loader2
+  Loader2,
+  Copy,
+  Download,
+  DollarSign
 } from 'lucide-react';
-import { Profile, BackupOperation, BackupPreview, Schedule } from '../types';
+import { Profile, BackupOperation, BackupLogsPage, BackupPreview, Schedule, CostEstimate, BackupStarted, TransferStats, LiveOperation, ChangesSinceBackup } from '../types';
+import { errorKind, errorMessage, isBackupError } from '../services/backupErrors';
 
 interface DashboardProps {
   profile: Profile | null;
   onProfileUpdated: () => void;
 }
 
-export default function Dashboard({ profile }: DashboardProps) {
+export default function Dashboard({ profile, onProfileUpdated }: DashboardProps) {
   const { t } = useTranslation();
   const navigate = useNavigate();
   const [lastBackup, setLastBackup] = useState<BackupOperation | null>(null);
   const [isRunning, setIsRunning] = useState(false);
+  const [transferStats, setTransferStats] = useState<TransferStats | null>(null);
   const [preview, setPreview] = useState<BackupPreview | null>(null);
   const [showPreview, setShowPreview] = useState(false);
   const [schedule, setSchedule] = useState<Schedule | null>(null);
   const [logs, setLogs] = useState<string>('');
+  const [estimatingCost, setEstimatingCost] = useState(false);
+  const [activeOperations, setActiveOperations] = useState<LiveOperation[]>([]);
+  const [changesSinceBackup, setChangesSinceBackup] = useState<ChangesSinceBackup | null>(null);
+  const [checkingChanges, setCheckingChanges] = useState(false);
+
+  // A count taken before the latest backup is stale
+  useEffect(() => {
+    setChangesSinceBackup(null);
+  }, [profile?.id, lastBackup?.id]);
+
+  // Everything running in the app, including restores started from the cloud browser
+  useEffect(() => {
+    const refresh = () => invoke<LiveOperation[]>('get_active_operations')
+      .then(setActiveOperations)
+      .catch((error) => console.warn('Could not load running operations:', error));
+    refresh();
+    const timer = setInterval(refresh, 2000);
+    return () => clearInterval(timer);
+  }, []);
 
   useEffect(() => {
     if (profile) {
@@ -57,7 +83,9 @@ export default function Dashboard({ profile }: DashboardProps) {
     if (!profile) return;
 
     try {
-      // Sync any scheduled backup logs first
+      // Ingest result files from scheduled runs, then sync any older-style scheduled logs
+      const scannedCount = await invoke<number>('scan_scheduled_results');
+      console.log('Ingested', scannedCount, 'scheduled backup results');
       console.log('Syncing scheduled backup logs...');
       const syncedCount = await invoke<number>('sync_scheduled_backup_logs', {
         profileId: profile.id
@@ -66,17 +94,20 @@ export default function Dashboard({ profile }: DashboardProps) {
 
       // Load backup logs
       console.log('Loading backup logs...');
-      const operations = await invoke<BackupOperation[]>('get_backup_logs', {
+      const page = await invoke<BackupLogsPage>('get_backup_logs', {
         profileId: profile.id,
         limit: 1
       });
-      console.log('Retrieved operations:', operations);
+      const operations = page.operations;
+      console.log('Retrieved operations:', operations, 'of', page.total_count);
       if (operations.length > 0) {
         console.log('Setting last backup to:', operations[0]);
         console.log('Operation type:', operations[0].operation_type);
         console.log('Started at:', operations[0].started_at);
-        console.log('Log output preview:', operations[0].log_output.substring(0, 100));
-        setLastBackup(operations[0]);
+        const logOutput = await invoke<string>('get_operation_log', {
+          operationId: operations[0].id
+        });
+        setLastBackup({ ...operations[0], log_output: logOutput });
       } else {
         console.log('No operations found');
       }
@@ -92,18 +123,51 @@ export default function Dashboard({ profile }: DashboardProps) {
     }
   };
 
-  const runBackup = async () => {
+  // Lists every source and the whole backup, so only run when asked
+  const checkChangesSinceBackup = async () => {
+    if (!profile) return;
+
+    setCheckingChanges(true);
+    try {
+      setChangesSinceBackup(await invoke<ChangesSinceBackup | null>('changes_since_last_backup', {
+        profileId: profile.id
+      }));
+    } catch (error) {
+      console.warn('Could not check for changes since the last backup:', error);
+      setChangesSinceBackup(null);
+    } finally {
+      setCheckingChanges(false);
+    }
+  };
+
+  const runBackup = async (confirmDeletions = false) => {
     if (!profile || isRunning) return;
 
     console.log(`Starting backup for profile: ${profile.name}`);
     setIsRunning(true);
     setLogs('Starting backup...\n');
 
+    // Poll rclone's rc stats for the running operation until the backup returns
+    let statsTimer: ReturnType<typeof setInterval> | undefined;
+    const unlistenStarted = await listen<BackupStarted>('backup-started', (event) => {
+      if (event.payload.profile_id !== profile.id) return;
+      const operationId = event.payload.operation_id;
+      statsTimer = setInterval(async () => {
+        try {
+          const stats = await invoke<TransferStats | null>('get_transfer_stats', { operationId });
+          if (stats) setTransferStats(stats);
+        } catch (error) {
+          console.error('Failed to read transfer stats:', error);
+        }
+      }, 1000);
+    });
+
     try {
-      console.log('Invoking backup_run command');
-      const operation = await invoke<BackupOperation>('backup_run', {
+      console.log('Invoking start_backup_with_rc command');
+      const operation = await invoke<BackupOperation>('start_backup_with_rc', {
         profile,
-        dryRun: false
+        dryRun: false,
+        confirmDeletions
       });
 
       console.log('Backup operation result:', operation);
@@ -117,15 +181,48 @@ export default function Dashboard({ profile }: DashboardProps) {
         await loadDashboardData();
 
         alert('Backup completed successfully!');
+      } else if (operation.status === 'PartialSuccess') {
+        await loadDashboardData();
+        alert(`${t('dashboard.backupPartial')}\n\n${operation.error_message ?? ''}`);
       } else if (operation.status === 'Failed') {
         console.error('Backup failed:', operation.error_message);
         alert(`Backup failed: ${operation.error_message}`);
       }
     } catch (error) {
+      if (isBackupError(error) && error.kind === 'SyncDeletionsPending') {
+        // Sync wanted to delete more than the profile allows; ask, then retry confirmed
+        const files = error.files_to_delete ?? [];
+        const listed = files.slice(0, 20).map(f => `• ${f.path}`).join('\n');
+        const more = files.length > 20 ? `\n${t('dashboard.andMore', { count: files.length - 20 })}` : '';
+        setIsRunning(false);
+        setLogs(prev => prev + `\n${error.message}\n`);
+        if (confirm(`${t('dashboard.syncDeleteConfirmation', { count: files.length })}\n\n${listed}${more}`)) {
+          await runBackup(true);
+        }
+        return;
+      }
       console.error('Backup failed:', error);
-      setLogs(prev => prev + `\nError: ${error}\n`);
-      alert('Backup failed: ' + error);
+      const message = errorMessage(error);
+      const kind = errorKind(error);
+      const hint = kind ? t(`backupErrors.${kind}`, { defaultValue: '' }) : '';
+      setLogs(prev => prev + `\nError: ${message}\n`);
+      // A missing or broken rclone.conf can be rebuilt from the credentials the app stores
+      if (kind === 'ConfigInvalid' && profile.aws_config && confirm(`${message}\n\n${t('dashboard.repairConfigPrompt')}`)) {
+        try {
+          await invoke<Profile>('repair_rclone_config', { profileId: profile.id });
+          onProfileUpdated();
+          alert(t('dashboard.repairConfigDone'));
+        } catch (repairError) {
+          console.error('Failed to repair rclone.conf:', repairError);
+          alert(`${t('dashboard.repairConfigFailed')}: ${errorMessage(repairError)}`);
+        }
+        return;
+      }
+      alert('Backup failed: ' + message + (hint ? `\n\n${hint}` : ''));
     } finally {
+      unlistenStarted();
+      clearInterval(statsTimer);
+      setTransferStats(null);
       setIsRunning(false);
     }
   };
@@ -144,7 +241,96 @@ export default function Dashboard({ profile }: DashboardProps) {
       setShowPreview(true);
     } catch (error) {
       console.error('Preview failed:', error);
-      alert('Preview failed: ' + error);
+      alert('Preview failed: ' + errorMessage(error));
+    }
+  };
+
+  const estimateCost = async () => {
+    if (!profile) return;
+
+    setEstimatingCost(true);
+    try {
+      const estimate = await invoke<CostEstimate>('estimate_cost', { profile });
+      const usd = (amount: number) => `$${amount.toFixed(2)}`;
+      alert(t('dashboard.costEstimate', {
+        files: estimate.total_files,
+        size: formatBytes(estimate.total_bytes),
+        firstMonth: usd(estimate.first_month_storage_cost),
+        monthly: usd(estimate.monthly_storage_cost),
+        storageClass: estimate.storage_class,
+        oneTime: usd(estimate.upload_request_cost + estimate.transition_request_cost)
+      }));
+    } catch (error) {
+      console.error('Cost estimate failed:', error);
+      alert(`${t('dashboard.costEstimateFailed')}: ${errorMessage(error)}`);
+    } finally {
+      setEstimatingCost(false);
+    }
+  };
+
+  const copyLog = async () => {
+    if (!lastBackup) return;
+
+    try {
+      const text = await invoke<string>('copy_operation_log', { operationId: lastBackup.id });
+      await navigator.clipboard.writeText(text);
+      alert(t('dashboard.logCopied'));
+    } catch (error) {
+      console.error('Failed to copy log:', error);
+      alert(`${t('dashboard.logCopyFailed')}: ${errorMessage(error)}`);
+    }
+  };
+
+  // Point-in-time restore of a folder (or everything) as the last backup left it
+  const restoreFromLastBackup = async () => {
+    if (!lastBackup || isRunning) return;
+
+    const remotePath = prompt(t('dashboard.restoreAsOfPrompt', { time: formatDate(lastBackup.completed_at ?? lastBackup.started_at) }), '');
+    if (remotePath === null) return;
+
+    try {
+      const localTarget = await open({ directory: true, multiple: false });
+      if (!localTarget || typeof localTarget !== 'string') return;
+
+      setIsRunning(true);
+      const operation = await invoke<BackupOperation>('restore_from_operation', {
+        operationId: lastBackup.id,
+        remotePaths: [remotePath.trim()],
+        localTarget
+      });
+      if (operation.status === 'Completed') {
+        alert(t('dashboard.restoreAsOfDone', { count: operation.files_transferred, target: localTarget }));
+      } else {
+        alert(`${t('dashboard.restoreAsOfFailed')}: ${operation.error_message ?? ''}`);
+      }
+    } catch (error) {
+      console.error('Point-in-time restore failed:', error);
+      const kind = errorKind(error);
+      const hint = kind ? t(`backupErrors.${kind}`, { defaultValue: '' }) : '';
+      alert(`${t('dashboard.restoreAsOfFailed')}: ${errorMessage(error)}` + (hint ? `\n\n${hint}` : ''));
+    } finally {
+      setIsRunning(false);
+    }
+  };
+
+  const exportLog = async () => {
+    if (!lastBackup) return;
+
+    try {
+      const outputPath = await save({
+        defaultPath: `lavoz-${lastBackup.operation_type.toLowerCase()}-${lastBackup.started_at.slice(0, 10)}.log`,
+        filters: [{ name: 'Log Files', extensions: ['log', 'txt'] }]
+      });
+      if (!outputPath) return;
+
+      const written = await invoke<string>('export_operation_log', {
+        operationId: lastBackup.id,
+        outputPath
+      });
+      alert(t('dashboard.logExported', { path: written }));
+    } catch (error) {
+      console.error('Failed to export log:', error);
+      alert(`${t('dashboard.logExportFailed')}: ${errorMessage(error)}`);
     }
   };
 
@@ -164,11 +350,11 @@ export default function Dashboard({ profile }: DashboardProps) {
     }
 
     setShowPreview(false);
-    await runBackup();
+    await runBackup(true);
   };
 
   const formatBytes = (bytes: number): string => {
-    if (bytes === 0) return '0 B';
+    if (bytes < 1) return '0 B';
     const k = 1024;
     const sizes = ['B', 'KB', 'MB', 'GB', 'TB'];
     const i = Math.floor(Math.log(bytes) / Math.log(k));
@@ -221,7 +407,7 @@ export default function Dashboard({ profile }: DashboardProps) {
             <div className="action-buttons">
               <button
                 className="btn btn-primary btn-large"
-                onClick={runBackup}
+                onClick={() => runBackup()}
                 disabled={isRunning}
               >
                 {isRunning ? (
@@ -242,8 +428,42 @@ export default function Dashboard({ profile }: DashboardProps) {
                   {t('dashboard.previewChanges')}
                 </button>
               )}
+              <button
+                className="btn btn-secondary"
+                onClick={estimateCost}
+                disabled={estimatingCost}
+              >
+                {estimatingCost ? <Loader2 size={16} className="spinning" /> : <DollarSign size={16} />}
+                {t('dashboard.estimateCost')}
+              </button>
             </div>
 
+            {isRunning && transferStats && (
+              <div className="transfer-stats">
+                <div className="progress-bar">
+                  <div
+                    className="progress-fill"
+                    style={{ width: `${transferStats.total_bytes ? Math.min(100, transferStats.bytes / transferStats.total_bytes * 100) : 0}%` }}
+                  />
+                </div>
+                <div className="transfer-stats-summary">
+                  {t('dashboard.transferProgress', {
+                    done: formatBytes(transferStats.bytes),
+                    total: formatBytes(transferStats.total_bytes),
+                    speed: formatBytes(transferStats.speed),
+                    files: transferStats.transfers,
+                    totalFiles: transferStats.total_transfers
+                  })}
+                  {transferStats.eta != null && ` · ${t('dashboard.transferEta', { seconds: transferStats.eta })}`}
+                </div>
+                {transferStats.transferring.slice(0, 5).map(file => (
+                  <div key={file.name} className="transfer-stats-file">
+                    {file.name} ({file.percentage}%)
+                  </div>
+                ))}
+              </div>
+            )}
+
             <div className="backup-info">
               <div className="info-item">
                 <span className="label">{t('dashboard.mode')}:</span>
@@ -255,6 +475,28 @@ export default function Dashboard({ profile }: DashboardProps) {
                 <span className="label">{t('dashboard.sources')}:</span>
                 <span className="value">{profile.sources.length} {t('dashboard.folders')}</span>
               </div>
+              {lastBackup && (
+                <div className="info-item">
+                  <span className="label">{t('dashboard.changedSinceBackup')}:</span>
+                  {changesSinceBackup ? (
+                    <span className="value">
+                      {t('dashboard.changedFiles', {
+                        count: changesSinceBackup.total_files,
+                        size: formatBytes(changesSinceBackup.total_size)
+                      })}
+                    </span>
+                  ) : (
+                    <button
+                      type="button"
+                      className="btn btn-secondary btn-small"
+                      onClick={checkChangesSinceBackup}
+                      disabled={checkingChanges}
+                    >
+                      {checkingChanges ? t('dashboard.checkingChanges') : t('dashboard.checkChanges')}
+                    </button>
+                  )}
+                </div>
+              )}
             </div>
           </div>
         </div>
@@ -270,10 +512,11 @@ export default function Dashboard({ profile }: DashboardProps) {
                 <div className="status-header">
                   <div className={`status-indicator ${lastBackup.status.toLowerCase()}`}>
                     {lastBackup.status === 'Completed' && <CheckCircle size={20} />}
-                    {lastBackup.status === 'Failed' && <AlertTriangle size={20} />}
+                    {(lastBackup.status === 'Failed' || lastBackup.status === 'PartialSuccess') && <AlertTriangle size={20} />}
                     {lastBackup.status === 'Running' && <Clock size={20} />}
                     <span>
                       {lastBackup.status === 'Completed' && t('dashboard.statusCompleted')}
+                      {lastBackup.status === 'PartialSuccess' && t('dashboard.statusPartial')}
                       {lastBackup.status === 'Failed' && t('dashboard.statusFailed')}
                       {lastBackup.status === 'Running' && t('dashboard.statusRunning')}
                     </span>
@@ -299,6 +542,26 @@ export default function Dashboard({ profile }: DashboardProps) {
                     {lastBackup.error_message}
                   </div>
                 )}
+
+                {(lastBackup.repeat_count ?? 1) > 1 && lastBackup.first_started_at && (
+                  <div className="backup-date">
+                    {t('dashboard.repeatedFailure', {
+                      count: lastBackup.repeat_count,
+                      since: formatDate(lastBackup.first_started_at)
+                    })}
+                  </div>
+                )}
+
+                {lastBackup.operation_type === 'Backup' && lastBackup.completed_at && (
+                  <button
+                    className="btn btn-secondary btn-small"
+                    onClick={restoreFromLastBackup}
+                    disabled={isRunning}
+                  >
+                    <Download size={14} />
+                    {t('dashboard.restoreAsOf')}
+                  </button>
+                )}
               </div>
             ) : (
               <div className="empty-state-small">
@@ -309,6 +572,38 @@ export default function Dashboard({ profile }: DashboardProps) {
           </div>
         </div>
 
+        {activeOperations.length > 0 && (
+          <div className="card">
+            <div className="card-header">
+              <h3>{t('dashboard.runningNow')}</h3>
+            </div>
+            <div className="card-content">
+              {activeOperations.map((op) => (
+                <div key={op.operation_id} className="transfer-stats">
+                  <div className="transfer-stats-summary">
+                    {t('dashboard.runningOperation', {
+                      type: op.operation_type,
+                      done: op.items_done,
+                      total: op.items_total,
+                      elapsed: `${Math.floor(op.elapsed_seconds / 60)}m ${op.elapsed_seconds % 60}s`
+                    })}
+                    {' • '}
+                    {op.bytes_total > 0
+                      ? `${formatBytes(op.bytes_done)} / ${formatBytes(op.bytes_total)}`
+                      : formatBytes(op.bytes_done)}
+                  </div>
+                  {op.current_item && <div className="transfer-stats-file">{op.current_item}</div>}
+                  {op.items_total > 0 && (
+                    <div className="progress-bar">
+                      <div className="progress-fill" style={{ width: `${Math.round((op.items_done / op.items_total) * 100)}%` }} />
+                    </div>
+                  )}
+                </div>
+              ))}
+            </div>
+          </div>
+        )}
+
         {/* Schedule Status */}
         <div className="card">
           <div className="card-header">
@@ -366,9 +661,14 @@ export default function Dashboard({ profile }: DashboardProps) {
         <div className="card full-width">
           <div className="card-header">
             <h3>{t('dashboard.recentLogs')}</h3>
-            <button className="btn-icon">
-              <FileText size={16} />
-            </button>
+            <div>
+              <button className="btn-icon" onClick={copyLog} disabled={!lastBackup} title={t('dashboard.copyLog')}>
+                <Copy size={16} />
+              </button>
+              <button className="btn-icon" onClick={exportLog} disabled={!lastBackup} title={t('dashboard.exportLog')}>
+                <Download size={16} />
+              </button>
+            </div>
           </div>
           <div className="card-content">
             {logs || lastBackup?.log_output ? (
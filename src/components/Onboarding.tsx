@@ -11,7 +11,8 @@ import {
   AlertCircle,
   // Globe
 } from 'lucide-react';
-import { Profile, BackupMode } from '../types';
+import { Profile, BackupMode, RcloneBinary } from '../types';
+import { errorKind, errorMessage } from '../services/backupErrors';
 
 interface OnboardingProps {
   onProfileCreated: () => void;
@@ -44,10 +45,12 @@ export default function Onboarding({ onProfileCreated }: OnboardingProps) {
     sources: [],
     mode: 'Copy'
   });
-  const [rcloneCandidates, setRcloneCandidates] = useState<string[]>([]);
+  const [rcloneCandidates, setRcloneCandidates] = useState<RcloneBinary[]>([]);
   const [validationErrors, setValidationErrors] = useState<Record<string, string>>({});
   const [isValidating, setIsValidating] = useState(false);
   const [creating, setCreating] = useState(false);
+  // Goes to the keychain, not the profile, so it isn't part of formData
+  const [configPassword, setConfigPassword] = useState('');
 
   useEffect(() => {
     detectRclone();
@@ -55,10 +58,10 @@ export default function Onboarding({ onProfileCreated }: OnboardingProps) {
 
   const detectRclone = async () => {
     try {
-      const candidates = await invoke<string[]>('detect_rclone');
+      const candidates = await invoke<RcloneBinary[]>('detect_rclone');
       setRcloneCandidates(candidates);
-      if (candidates.length > 0 && !candidates[0].includes('not found')) {
-        setFormData(prev => ({ ...prev, rclone_bin: candidates[0] }));
+      if (candidates.length > 0) {
+        setFormData(prev => ({ ...prev, rclone_bin: candidates[0].path }));
       }
     } catch (error) {
       console.error('Failed to detect rclone:', error);
@@ -90,13 +93,21 @@ export default function Onboarding({ onProfileCreated }: OnboardingProps) {
             try {
               const isValid = await invoke<boolean>('validate_rclone_config', {
                 rcloneBin: formData.rclone_bin,
-                configPath: formData.rclone_conf
+                configPath: formData.rclone_conf,
+                configPassword: configPassword || null
               });
               if (!isValid) {
-                errors.rclone_conf = t('onboarding.invalidRcloneConfig');
+                errors.rclone_conf = configPassword
+                  ? t('onboarding.invalidRcloneConfigOrPassword')
+                  : t('onboarding.invalidRcloneConfig');
               }
             } catch (error) {
-              errors.rclone_conf = t('onboarding.failedToValidateConfig');
+              const kind = errorKind(error);
+              errors.rclone_conf = kind === 'RcloneNotFound'
+                ? t('backupErrors.RcloneNotFound')
+                : kind === 'ConfigInvalid'
+                  ? errorMessage(error)
+                  : t('onboarding.failedToValidateConfig');
             }
           }
           break;
@@ -145,14 +156,14 @@ export default function Onboarding({ onProfileCreated }: OnboardingProps) {
         ...profile,
         ...formData,
         rclone_flags: [
-          '--checksum',
-          '--fast-list',
-          '--transfers=8',
-          '--checkers=32'
+          '--fast-list'
         ]
       };
       
       await invoke('update_profile', { profile: updatedProfile });
+      if (configPassword) {
+        await invoke('set_rclone_config_password', { profileId: profile.id, password: configPassword });
+      }
       await invoke('set_active_profile', { profileId: profile.id });
       
       onProfileCreated();
@@ -269,16 +280,19 @@ export default function Onboarding({ onProfileCreated }: OnboardingProps) {
         <div className="form-group">
           <label htmlFor="rclone-bin">{t('onboarding.rcloneBinaryPath')}</label>
           <div className="rclone-candidates">
-            {rcloneCandidates.map((candidate, index) => (
-              <label key={index} className="radio-option">
+            {rcloneCandidates.map((candidate) => (
+              <label key={candidate.path} className="radio-option">
                 <input
                   type="radio"
                   name="rclone-bin"
-                  value={candidate}
-                  checked={formData.rclone_bin === candidate}
+                  value={candidate.path}
+                  checked={formData.rclone_bin === candidate.path}
                   onChange={(e) => setFormData(prev => ({ ...prev, rclone_bin: e.target.value }))}
                 />
-                <span>{candidate}</span>
+                <span>
+                  {candidate.path} ({candidate.source}{candidate.version ? `, v${candidate.version}` : ''})
+                </span>
+                {candidate.warning && <div className="error-message">{candidate.warning}</div>}
               </label>
             ))}
           </div>
@@ -348,6 +362,17 @@ export default function Onboarding({ onProfileCreated }: OnboardingProps) {
           <div className="help-text">
             {t('onboarding.rcloneConfigHelp')}
           </div>
+          <label htmlFor="rclone-conf-password">{t('onboarding.configPassword')}</label>
+          <input
+            id="rclone-conf-password"
+            type="password"
+            value={configPassword}
+            onChange={(e) => setConfigPassword(e.target.value)}
+            autoComplete="off"
+          />
+          <div className="help-text">
+            {t('onboarding.configPasswordHelp')}
+          </div>
         </div>
       )
     },
@@ -14,8 +14,9 @@ import {
   Zap,
   CheckCircle
 } from 'lucide-react';
-import { Profile, Schedule, ScheduleFrequency, BackupMode } from '../types';
+import { Profile, Schedule, ScheduleConflict, ScheduleFrequency, BackupMode, HashMode, LogLevel, SymlinkMode, ScheduledTestRun, SchedulePreview, InvalidRcloneFlag, PrefixRename, PurgeResult, RcloneRemote, AppSettings } from '../types';
 import LanguageSwitcher from './LanguageSwitcher';
+import { errorMessage } from '../services/backupErrors';
 
 interface SettingsProps {
   profile: Profile | null;
@@ -32,6 +33,22 @@ export default function Settings({ profile, onProfileUpdated }: SettingsProps) {
   const [hasChanges, setHasChanges] = useState(false);
   const [hasShownScheduleAlert, setHasShownScheduleAlert] = useState(false);
   const [showScheduleNotification, setShowScheduleNotification] = useState(false);
+  const [testingSchedule, setTestingSchedule] = useState(false);
+  const [schedulePreview, setSchedulePreview] = useState<SchedulePreview | null>(null);
+  const [testingWebhook, setTestingWebhook] = useState(false);
+  const [invalidFlags, setInvalidFlags] = useState<InvalidRcloneFlag[] | null>(null);
+  const [movingPrefix, setMovingPrefix] = useState(false);
+  const [purging, setPurging] = useState(false);
+  const [configPassword, setConfigPassword] = useState('');
+  const [rcloneRemotes, setRcloneRemotes] = useState<RcloneRemote[]>([]);
+  const [networkSettings, setNetworkSettings] = useState<AppSettings | null>(null);
+  const [savingNetwork, setSavingNetwork] = useState(false);
+
+  useEffect(() => {
+    invoke<AppSettings>('get_settings')
+      .then(setNetworkSettings)
+      .catch((error) => console.error('Error loading app settings:', error));
+  }, []);
 
   useEffect(() => {
     if (profile) {
@@ -41,6 +58,20 @@ export default function Settings({ profile, onProfileUpdated }: SettingsProps) {
     }
   }, [profile]);
 
+  useEffect(() => {
+    if (!editedProfile) return;
+    invoke<RcloneRemote[]>('list_rclone_remotes', {
+      rcloneBin: editedProfile.rclone_bin,
+      configPath: editedProfile.rclone_conf,
+      profileId: editedProfile.id
+    })
+      .then(setRcloneRemotes)
+      .catch((error) => {
+        console.warn('Could not list rclone remotes:', error);
+        setRcloneRemotes([]);
+      });
+  }, [editedProfile?.rclone_bin, editedProfile?.rclone_conf, editedProfile?.id]);
+
   useEffect(() => {
     // Check URL parameters to set the active tab
     const searchParams = new URLSearchParams(location.search);
@@ -111,6 +142,25 @@ export default function Settings({ profile, onProfileUpdated }: SettingsProps) {
     handleProfileChange('sources', newSources);
   };
 
+  const handleSecondaryDestinationChange = (index: number, value: string) => {
+    if (!editedProfile) return;
+
+    const newDestinations = [...(editedProfile.secondary_destinations || [])];
+    newDestinations[index] = value;
+    handleProfileChange('secondary_destinations', newDestinations);
+  };
+
+  const addSecondaryDestination = () => {
+    if (!editedProfile) return;
+    handleProfileChange('secondary_destinations', [...(editedProfile.secondary_destinations || []), '']);
+  };
+
+  const removeSecondaryDestination = (index: number) => {
+    if (!editedProfile) return;
+    const newDestinations = (editedProfile.secondary_destinations || []).filter((_, i) => i !== index);
+    handleProfileChange('secondary_destinations', newDestinations);
+  };
+
   const handleFlagChange = (index: number, value: string) => {
     if (!editedProfile) return;
     
@@ -130,11 +180,34 @@ export default function Settings({ profile, onProfileUpdated }: SettingsProps) {
     handleProfileChange('rclone_flags', newFlags);
   };
 
+  // Flags rclone rejects; an unavailable rclone shouldn't block saving
+  const checkFlags = async (): Promise<InvalidRcloneFlag[]> => {
+    if (!editedProfile) return [];
+    try {
+      const invalid = await invoke<InvalidRcloneFlag[]>('validate_rclone_flags', {
+        rcloneBin: editedProfile.rclone_bin,
+        flags: editedProfile.rclone_flags
+      });
+      setInvalidFlags(invalid);
+      return invalid;
+    } catch (error) {
+      console.error('Failed to validate rclone flags:', error);
+      return [];
+    }
+  };
+
   const saveProfile = async () => {
     if (!editedProfile) return;
 
     setSaving(true);
     try {
+      const problems = await invoke<string[]>('validate_profile', { profile: editedProfile });
+      problems.push(...(await checkFlags()).map(f => `${f.flag}: ${f.message}`));
+      if (problems.length > 0 &&
+          !confirm('This profile has problems:\n\n' + problems.join('\n') + '\n\nSave anyway?')) {
+        return;
+      }
+
       await invoke('update_profile', { profile: editedProfile });
       setHasChanges(false);
       onProfileUpdated();
@@ -147,6 +220,152 @@ export default function Settings({ profile, onProfileUpdated }: SettingsProps) {
     }
   };
 
+  const testScheduledRun = async () => {
+    if (!profile) return;
+
+    setTestingSchedule(true);
+    try {
+      const result = await invoke<ScheduledTestRun>('run_scheduled_now', { profileId: profile.id });
+      const details = (result.log_excerpt || result.output).trim();
+      alert((result.success ? 'Scheduled backup ran successfully.' : `Scheduled backup failed (exit code ${result.exit_code}).`)
+        + (details ? `\n\n${details}` : ''));
+    } catch (error) {
+      console.error('Failed to run scheduled backup:', error);
+      alert('Failed to run scheduled backup: ' + error);
+    } finally {
+      setTestingSchedule(false);
+    }
+  };
+
+  // Show what saving this schedule would write, without installing it
+  const previewSchedule = async () => {
+    if (!profile || !schedule) return;
+    try {
+      setSchedulePreview(await invoke<SchedulePreview>('preview_schedule', { profileId: profile.id, schedule }));
+    } catch (error) {
+      console.error('Failed to preview schedule:', error);
+      alert(t('settings.schedulePreviewFailed') + ': ' + errorMessage(error));
+    }
+  };
+
+  const sendTestNotification = async () => {
+    if (!profile) return;
+
+    setTestingWebhook(true);
+    try {
+      await invoke('send_test_notification', { profileId: profile.id });
+      alert('Test notification sent.');
+    } catch (error) {
+      console.error('Failed to send test notification:', error);
+      alert('Failed to send test notification: ' + error);
+    } finally {
+      setTestingWebhook(false);
+    }
+  };
+
+  // Move existing backups to the edited prefix instead of just pointing the profile at it
+  const movePrefix = async (merge = false) => {
+    if (!profile || !editedProfile) return;
+    const newPrefix = editedProfile.prefix.trim();
+    if (!merge && !confirm(t('settings.movePrefixConfirm', { from: profile.prefix, to: newPrefix }))) return;
+
+    setMovingPrefix(true);
+    try {
+      const result = await invoke<PrefixRename>('rename_prefix', {
+        profileId: profile.id,
+        newPrefix,
+        merge
+      });
+      alert(t('settings.movePrefixDone', {
+        files: result.files_moved,
+        size: (result.bytes_moved / 1024 / 1024).toFixed(1),
+        to: result.new_prefix
+      }) + (result.policy_updated_for ? '\n' + t('settings.movePrefixPolicy', { user: result.policy_updated_for }) : ''));
+      onProfileUpdated();
+    } catch (error) {
+      console.error('Failed to move backups:', error);
+      const message = errorMessage(error);
+      // The new prefix already has data: offer to merge into it
+      if (!merge && message.includes('already holds')) {
+        if (confirm(message)) {
+          await movePrefix(true);
+        }
+        return;
+      }
+      alert(t('settings.movePrefixFailed') + ': ' + message);
+    } finally {
+      setMovingPrefix(false);
+    }
+  };
+
+  // Wipe everything stored at the saved bucket/prefix; the user has to type it to confirm
+  const purgeRemote = async () => {
+    if (!profile) return;
+    const prefix = profile.prefix.trim().replace(/^\/+|\/+$/g, '');
+    const location = prefix ? `${profile.bucket}/${prefix}` : profile.bucket;
+    const confirmToken = prompt(t('settings.purgeConfirm', { location }));
+    if (confirmToken === null) return;
+    const allVersions = confirm(t('settings.purgeAllVersions'));
+
+    setPurging(true);
+    try {
+      const result = await invoke<PurgeResult>('purge_remote', { profile, confirmToken, allVersions });
+      alert(t('settings.purgeDone', {
+        files: result.files_deleted,
+        size: (result.bytes_freed / 1024 / 1024).toFixed(1),
+        location: result.location
+      }));
+    } catch (error) {
+      console.error('Failed to purge remote data:', error);
+      alert(t('settings.purgeFailed') + ': ' + errorMessage(error));
+    } finally {
+      setPurging(false);
+    }
+  };
+
+  // Kept in the keychain rather than the profile, so it's saved on its own
+  const saveConfigPassword = async (password: string | null) => {
+    if (!profile) return;
+    try {
+      const updated = await invoke<Profile>('set_rclone_config_password', { profileId: profile.id, password });
+      setEditedProfile(prev => prev ? { ...prev, rclone_config_encrypted: updated.rclone_config_encrypted } : prev);
+      setConfigPassword('');
+      onProfileUpdated();
+    } catch (error) {
+      console.error('Failed to save rclone config password:', error);
+      alert(t('settings.configPasswordFailed') + ': ' + error);
+    }
+  };
+
+  const showAppLog = async () => {
+    try {
+      const path = await invoke<string>('get_app_log_path');
+      await invoke('reveal_in_file_manager', { path });
+    } catch (error) {
+      console.error('Failed to show app log:', error);
+      alert(t('settings.appLogFailed') + ': ' + error);
+    }
+  };
+
+  const handleNetworkChange = (field: 'http_proxy' | 'https_proxy' | 'no_proxy' | 'bind_address', value: string) => {
+    if (!networkSettings) return;
+    setNetworkSettings({ ...networkSettings, [field]: value || undefined });
+  };
+
+  const saveNetworkSettings = async () => {
+    if (!networkSettings) return;
+    setSavingNetwork(true);
+    try {
+      setNetworkSettings(await invoke<AppSettings>('update_settings', { settings: networkSettings }));
+      alert(t('settings.networkSaved'));
+    } catch (error) {
+      console.error('Failed to save network settings:', error);
+      alert(t('settings.networkSaveFailed') + ': ' + error);
+    } finally {
+      setSavingNetwork(false);
+    }
+  };
+
   const saveSchedule = async (showAlert = true) => {
     if (!profile || !schedule) return;
 
@@ -161,18 +380,39 @@ export default function Settings({ profile, onProfileUpdated }: SettingsProps) {
     try {
       if (schedule.enabled) {
         console.log('Enabling schedule backup');
-        const response = await invoke('schedule_backup', {
+        const conflicts = await invoke<ScheduleConflict[]>('schedule_backup', {
           profileId: profile.id,
           schedule
         });
-        console.log('Schedule backup response:', response);
         console.log('Schedule backup enabled successfully');
+
+        // Scheduling still goes ahead; the user decides whether to move one of the runs
+        if (conflicts.length > 0) {
+          const lines = conflicts.map(conflict => {
+            const other = conflict.profile_id === profile.id ? conflict.other_profile_name : conflict.profile_name;
+            return `• ${t('settings.scheduleConflict', {
+              other,
+              destination: conflict.destination,
+              time: new Date(conflict.first_overlap).toLocaleString(),
+              minutes: conflict.minutes_apart
+            })}${conflict.sync_mode ? ` ${t('settings.scheduleConflictSync')}` : ''}`;
+          });
+          alert(`${t('settings.scheduleConflictWarning')}\n\n${lines.join('\n')}`);
+        }
       } else {
-        console.log('Disabling schedule backup');
-        await invoke('unschedule_backup', {
-          profileId: profile.id
-        });
-        console.log('Schedule backup disabled successfully');
+        console.log('Pausing schedule backup');
+        if (profile.schedule) {
+          // Keep the schedule and OS job around so it can be resumed later
+          await invoke('set_schedule_enabled', {
+            profileId: profile.id,
+            enabled: false
+          });
+        } else {
+          await invoke('unschedule_backup', {
+            profileId: profile.id
+          });
+        }
+        console.log('Schedule backup paused successfully');
       }
       
       // Show notification for user action
@@ -363,15 +603,56 @@ export default function Settings({ profile, onProfileUpdated }: SettingsProps) {
                 </div>
               </div>
 
+              <div className="form-group">
+                <label htmlFor="rclone-conf-password">{t('settings.configPassword')}</label>
+                <div className="file-input">
+                  <input
+                    id="rclone-conf-password"
+                    type="password"
+                    value={configPassword}
+                    onChange={(e) => setConfigPassword(e.target.value)}
+                    placeholder={editedProfile.rclone_config_encrypted ? t('settings.configPasswordStored') : ''}
+                    autoComplete="off"
+                  />
+                  <button
+                    type="button"
+                    className="btn btn-secondary btn-small"
+                    onClick={() => saveConfigPassword(configPassword)}
+                    disabled={!configPassword}
+                  >
+                    {t('common.save')}
+                  </button>
+                  {editedProfile.rclone_config_encrypted && (
+                    <button
+                      type="button"
+                      className="btn btn-secondary btn-small"
+                      onClick={() => saveConfigPassword(null)}
+                    >
+                      {t('settings.configPasswordRemove')}
+                    </button>
+                  )}
+                </div>
+                <div className="help-text">{t('settings.configPasswordHelp')}</div>
+              </div>
+
               <div className="form-row">
                 <div className="form-group">
                   <label htmlFor="remote">{t('settings.remote')}</label>
                   <input
                     id="remote"
                     type="text"
+                    list="rclone-remotes"
                     value={editedProfile.remote}
                     onChange={(e) => handleProfileChange('remote', e.target.value)}
                   />
+                  <datalist id="rclone-remotes">
+                    {rcloneRemotes.map((remote) => (
+                      <option key={remote.name} value={remote.name}>{remote.remote_type}</option>
+                    ))}
+                  </datalist>
+                  {rcloneRemotes.length > 0 && (
+                    <div className="help-text">{t('settings.remoteHelp')}</div>
+                  )}
                 </div>
 
                 <div className="form-group">
@@ -392,6 +673,19 @@ export default function Settings({ profile, onProfileUpdated }: SettingsProps) {
                     value={editedProfile.prefix}
                     onChange={(e) => handleProfileChange('prefix', e.target.value)}
                   />
+                  {profile?.prefix && !profile.user_id && editedProfile.prefix.trim() !== profile.prefix && (
+                    <>
+                      <button
+                        type="button"
+                        className="btn btn-secondary btn-small"
+                        onClick={() => movePrefix()}
+                        disabled={movingPrefix || !editedProfile.prefix.trim()}
+                      >
+                        {movingPrefix ? t('settings.movingPrefix') : t('settings.movePrefix')}
+                      </button>
+                      <div className="help-text">{t('settings.movePrefixHelp')}</div>
+                    </>
+                  )}
                 </div>
               </div>
 
@@ -425,6 +719,69 @@ export default function Settings({ profile, onProfileUpdated }: SettingsProps) {
                   </label>
                 </div>
               </div>
+
+              {editedProfile.mode === 'Sync' && (
+                <div className="form-group">
+                  <label className="checkbox-label">
+                    <input
+                      type="checkbox"
+                      checked={editedProfile.confirm_sync_deletions ?? true}
+                      onChange={(e) => handleProfileChange('confirm_sync_deletions', e.target.checked)}
+                    />
+                    <span>{t('settings.confirmSyncDeletions')}</span>
+                  </label>
+                  {(editedProfile.confirm_sync_deletions ?? true) && (
+                    <>
+                      <label htmlFor="sync-delete-threshold">{t('settings.syncDeleteThreshold')}</label>
+                      <input
+                        id="sync-delete-threshold"
+                        type="number"
+                        min={0}
+                        value={editedProfile.sync_delete_threshold ?? 0}
+                        onChange={(e) => handleProfileChange('sync_delete_threshold', Math.max(0, parseInt(e.target.value) || 0))}
+                      />
+                      <div className="help-text">{t('settings.syncDeleteThresholdHelp')}</div>
+                    </>
+                  )}
+                </div>
+              )}
+
+              <div className="form-group">
+                <label className="checkbox-label">
+                  <input
+                    type="checkbox"
+                    checked={editedProfile.use_backup_dir ?? false}
+                    onChange={(e) => handleProfileChange('use_backup_dir', e.target.checked)}
+                  />
+                  <span>{t('settings.useBackupDir')}</span>
+                </label>
+                <div className="help-text">{t('settings.useBackupDirHelp')}</div>
+              </div>
+
+              <div className="form-group">
+                <label className="checkbox-label">
+                  <input
+                    type="checkbox"
+                    checked={editedProfile.check_write_access ?? false}
+                    onChange={(e) => handleProfileChange('check_write_access', e.target.checked)}
+                  />
+                  <span>{t('settings.checkWriteAccess')}</span>
+                </label>
+                <div className="help-text">{t('settings.checkWriteAccessHelp')}</div>
+              </div>
+
+              <div className="form-group">
+                <label>{t('settings.purge')}</label>
+                <button
+                  type="button"
+                  className="btn btn-danger btn-small"
+                  onClick={purgeRemote}
+                  disabled={purging || !profile?.bucket}
+                >
+                  {purging ? t('settings.purging') : t('settings.purgeButton')}
+                </button>
+                <div className="help-text">{t('settings.purgeHelp')}</div>
+              </div>
             </div>
           )}
 
@@ -470,6 +827,58 @@ export default function Settings({ profile, onProfileUpdated }: SettingsProps) {
                 <Plus size={16} />
                 {t('settings.addFolder')}
               </button>
+
+              <h2>{t('settings.secondaryDestinations')}</h2>
+              <p>{t('settings.secondaryDestinationsDescription')}</p>
+
+              <div className="sources-list">
+                {(editedProfile.secondary_destinations || []).map((destination, index) => (
+                  <div key={index} className="source-item">
+                    <input
+                      type="text"
+                      value={destination}
+                      onChange={(e) => handleSecondaryDestinationChange(index, e.target.value)}
+                      placeholder={t('settings.secondaryDestinationPlaceholder')}
+                    />
+                    <button
+                      type="button"
+                      className="btn-icon"
+                      onClick={() => openFolderDialog((path) =>
+                        handleSecondaryDestinationChange(index, path)
+                      )}
+                    >
+                      <Folder size={16} />
+                    </button>
+                    <button
+                      type="button"
+                      className="btn-icon danger"
+                      onClick={() => removeSecondaryDestination(index)}
+                    >
+                      <Trash2 size={16} />
+                    </button>
+                  </div>
+                ))}
+              </div>
+
+              <button
+                type="button"
+                className="btn btn-secondary"
+                onClick={addSecondaryDestination}
+              >
+                <Plus size={16} />
+                {t('settings.addDestination')}
+              </button>
+
+              {(editedProfile.secondary_destinations || []).length > 0 && (
+                <label className="checkbox-label">
+                  <input
+                    type="checkbox"
+                    checked={editedProfile.fail_on_secondary_error ?? false}
+                    onChange={(e) => handleProfileChange('fail_on_secondary_error', e.target.checked)}
+                  />
+                  <span>{t('settings.failOnSecondaryError')}</span>
+                </label>
+              )}
             </div>
           )}
 
@@ -478,6 +887,124 @@ export default function Settings({ profile, onProfileUpdated }: SettingsProps) {
               <h2>{t('settings.advanced')}</h2>
               <p>{t('settings.advancedDescription', { defaultValue: 'Configure rclone flags and advanced options.' })}</p>
 
+              <div className="form-group">
+                <label htmlFor="transfers">
+                  {t('settings.transfers', { defaultValue: 'Parallel transfers' })}: {editedProfile.transfers}
+                </label>
+                <input
+                  id="transfers"
+                  type="range"
+                  min={1}
+                  max={64}
+                  value={editedProfile.transfers}
+                  onChange={(e) => handleProfileChange('transfers', Number(e.target.value))}
+                />
+              </div>
+
+              <div className="form-group">
+                <label htmlFor="checkers">
+                  {t('settings.checkers', { defaultValue: 'Parallel checkers' })}: {editedProfile.checkers}
+                </label>
+                <input
+                  id="checkers"
+                  type="range"
+                  min={1}
+                  max={128}
+                  value={editedProfile.checkers}
+                  onChange={(e) => handleProfileChange('checkers', Number(e.target.value))}
+                />
+              </div>
+
+              <div className="form-group">
+                <label htmlFor="retries">{t('settings.retries')}</label>
+                <input
+                  id="retries"
+                  type="number"
+                  min={1}
+                  value={editedProfile.retries ?? 3}
+                  onChange={(e) => handleProfileChange('retries', Math.max(1, Number(e.target.value)))}
+                />
+                <label htmlFor="low-level-retries">{t('settings.lowLevelRetries')}</label>
+                <input
+                  id="low-level-retries"
+                  type="number"
+                  min={0}
+                  value={editedProfile.low_level_retries ?? 10}
+                  onChange={(e) => handleProfileChange('low_level_retries', Math.max(0, Number(e.target.value)))}
+                />
+                <label htmlFor="retries-sleep">{t('settings.retriesSleep')}</label>
+                <input
+                  id="retries-sleep"
+                  type="number"
+                  min={0}
+                  value={editedProfile.retries_sleep_secs ?? 0}
+                  onChange={(e) => handleProfileChange('retries_sleep_secs', Math.max(0, Number(e.target.value)))}
+                />
+                <div className="help-text">{t('settings.retriesHelp')}</div>
+              </div>
+
+              <div className="form-group">
+                <label htmlFor="hash-check">{t('settings.hashCheck', { defaultValue: 'File comparison' })}</label>
+                <select
+                  id="hash-check"
+                  value={editedProfile.hash_check}
+                  onChange={(e) => handleProfileChange('hash_check', e.target.value as HashMode)}
+                >
+                  <option value="Checksum">{t('settings.hashChecksum', { defaultValue: 'Checksum (safest, slowest)' })}</option>
+                  <option value="SizeModTime">{t('settings.hashSizeModTime', { defaultValue: 'Size and modification time' })}</option>
+                  <option value="SizeOnly">{t('settings.hashSizeOnly', { defaultValue: 'Size only (fastest)' })}</option>
+                </select>
+              </div>
+
+              <div className="form-group">
+                <label htmlFor="symlink-mode">{t('settings.symlinkMode')}</label>
+                <select
+                  id="symlink-mode"
+                  value={editedProfile.symlink_mode}
+                  onChange={(e) => handleProfileChange('symlink_mode', e.target.value as SymlinkMode)}
+                >
+                  <option value="Skip">{t('settings.symlinkSkip')}</option>
+                  <option value="Copy">{t('settings.symlinkCopy')}</option>
+                  <option value="Follow">{t('settings.symlinkFollow')}</option>
+                </select>
+                <div className="help-text">{t('settings.symlinkModeHelp')}</div>
+              </div>
+
+              <div className="form-group">
+                <label htmlFor="log-level">{t('settings.logLevel')}</label>
+                <select
+                  id="log-level"
+                  value={editedProfile.log_level}
+                  onChange={(e) => handleProfileChange('log_level', e.target.value as LogLevel)}
+                >
+                  <option value="Quiet">{t('settings.logLevelQuiet')}</option>
+                  <option value="Normal">{t('settings.logLevelNormal')}</option>
+                  <option value="Verbose">{t('settings.logLevelVerbose')}</option>
+                  <option value="Debug">{t('settings.logLevelDebug')}</option>
+                </select>
+                <div className="help-text">{t('settings.logLevelHelp')}</div>
+              </div>
+
+              <div className="form-group">
+                <label htmlFor="stats-interval">{t('settings.statsInterval')}</label>
+                <input
+                  id="stats-interval"
+                  type="number"
+                  min={1}
+                  value={editedProfile.stats_interval_secs ?? 1}
+                  onChange={(e) => handleProfileChange('stats_interval_secs', Math.max(1, Number(e.target.value)))}
+                />
+                <label className="checkbox-label">
+                  <input
+                    type="checkbox"
+                    checked={editedProfile.detailed_stats ?? false}
+                    onChange={(e) => handleProfileChange('detailed_stats', e.target.checked)}
+                  />
+                  <span>{t('settings.detailedStats')}</span>
+                </label>
+                <div className="help-text">{t('settings.statsHelp')}</div>
+              </div>
+
               <div className="form-group">
                 <label>{t('settings.rcloneFlags')}</label>
                 <div className="flags-list">
@@ -508,11 +1035,125 @@ export default function Settings({ profile, onProfileUpdated }: SettingsProps) {
                   <Plus size={16} />
                   {t('settings.addFlag')}
                 </button>
+                <button
+                  type="button"
+                  className="btn btn-secondary"
+                  onClick={checkFlags}
+                >
+                  {t('settings.checkFlags')}
+                </button>
+
+                {invalidFlags && (
+                  invalidFlags.length === 0 ? (
+                    <div className="help-text">{t('settings.flagsValid')}</div>
+                  ) : (
+                    <ul className="error-message">
+                      {invalidFlags.map(f => (
+                        <li key={f.flag}><code>{f.flag}</code>: {f.message}</li>
+                      ))}
+                    </ul>
+                  )
+                )}
 
                 <div className="help-text">
-                  Common flags: --checksum, --fast-list, --transfers=8, --checkers=32
+                  Common flags: --fast-list, --bwlimit=10M
                 </div>
               </div>
+
+              <div className="form-group">
+                <label htmlFor="webhook-url">{t('settings.webhookUrl', { defaultValue: 'Notification webhook URL' })}</label>
+                <input
+                  id="webhook-url"
+                  type="url"
+                  value={editedProfile.notifications?.webhook_url || ''}
+                  onChange={(e) => handleProfileChange('notifications', {
+                    ...editedProfile.notifications,
+                    webhook_url: e.target.value || undefined
+                  })}
+                  placeholder="https://hooks.slack.com/services/..."
+                />
+                <label className="checkbox-label">
+                  <input
+                    type="checkbox"
+                    checked={editedProfile.notifications?.notify_on_success ?? true}
+                    onChange={(e) => handleProfileChange('notifications', {
+                      ...editedProfile.notifications,
+                      notify_on_success: e.target.checked
+                    })}
+                  />
+                  <span>{t('settings.notifyOnSuccess', { defaultValue: 'Also notify when a backup succeeds' })}</span>
+                </label>
+                <button
+                  type="button"
+                  className="btn btn-secondary"
+                  onClick={sendTestNotification}
+                  disabled={!profile?.notifications?.webhook_url || testingWebhook}
+                >
+                  {t('settings.sendTestNotification', { defaultValue: 'Send test notification' })}
+                </button>
+                <div className="help-text">
+                  {t('settings.webhookHelp', { defaultValue: 'Works with Slack and Discord incoming webhooks. Save before testing; scheduled runs pick up changes when the schedule is saved again.' })}
+                </div>
+              </div>
+
+              {networkSettings && (
+                <div className="form-group">
+                  <label>{t('settings.network')}</label>
+                  <label htmlFor="http-proxy">{t('settings.httpProxy')}</label>
+                  <input
+                    id="http-proxy"
+                    type="text"
+                    value={networkSettings.http_proxy || ''}
+                    onChange={(e) => handleNetworkChange('http_proxy', e.target.value)}
+                    placeholder="http://proxy.example.com:8080"
+                  />
+                  <label htmlFor="https-proxy">{t('settings.httpsProxy')}</label>
+                  <input
+                    id="https-proxy"
+                    type="text"
+                    value={networkSettings.https_proxy || ''}
+                    onChange={(e) => handleNetworkChange('https_proxy', e.target.value)}
+                    placeholder="http://proxy.example.com:8080"
+                  />
+                  <label htmlFor="no-proxy">{t('settings.noProxy')}</label>
+                  <input
+                    id="no-proxy"
+                    type="text"
+                    value={networkSettings.no_proxy || ''}
+                    onChange={(e) => handleNetworkChange('no_proxy', e.target.value)}
+                    placeholder="localhost,.internal.example.com"
+                  />
+                  <label htmlFor="bind-address">{t('settings.bindAddress')}</label>
+                  <input
+                    id="bind-address"
+                    type="text"
+                    value={networkSettings.bind_address || ''}
+                    onChange={(e) => handleNetworkChange('bind_address', e.target.value)}
+                    placeholder="0.0.0.0"
+                  />
+                  <button
+                    type="button"
+                    className="btn btn-secondary"
+                    onClick={saveNetworkSettings}
+                    disabled={savingNetwork}
+                  >
+                    {t('common.save')}
+                  </button>
+                  <div className="help-text">{t('settings.networkHelp')}</div>
+                </div>
+              )}
+
+              <div className="form-group">
+                <label>{t('settings.appLog')}</label>
+                <button
+                  type="button"
+                  className="btn btn-secondary"
+                  onClick={showAppLog}
+                >
+                  {t('settings.showAppLog')}
+                </button>
+                <div className="help-text">{t('settings.appLogHelp')}</div>
+              </div>
             </div>
           )}
 
@@ -662,6 +1303,42 @@ export default function Settings({ profile, onProfileUpdated }: SettingsProps) {
                   <Save size={16} />
                   {t('settings.saveSchedule')}
                 </button>
+
+                <button
+                  className="btn btn-secondary"
+                  onClick={testScheduledRun}
+                  disabled={!profile?.schedule || testingSchedule}
+                >
+                  {testingSchedule
+                    ? t('settings.testingSchedule', { defaultValue: 'Running...' })
+                    : t('settings.testSchedule', { defaultValue: 'Run scheduled backup now' })}
+                </button>
+
+                <button
+                  className="btn btn-secondary"
+                  onClick={() => schedulePreview ? setSchedulePreview(null) : previewSchedule()}
+                  disabled={!schedule}
+                >
+                  {schedulePreview ? t('settings.hideSchedulePreview') : t('settings.previewSchedule')}
+                </button>
+
+                {schedulePreview && (
+                  <div className="form-group">
+                    <label>{schedulePreview.script_path}</label>
+                    <pre className="logs-content">{schedulePreview.script}</pre>
+                    {schedulePreview.wrapper_script && (
+                      <pre className="logs-content">{schedulePreview.wrapper_script}</pre>
+                    )}
+                    {schedulePreview.job_definition ? (
+                      <>
+                        <label>{schedulePreview.job_path}</label>
+                        <pre className="logs-content">{schedulePreview.job_definition}</pre>
+                      </>
+                    ) : (
+                      <div className="help-text">{t('settings.schedulePreviewNoJob', { platform: schedulePreview.platform })}</div>
+                    )}
+                  </div>
+                )}
               </div>
             </div>
           )}
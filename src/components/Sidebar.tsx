@@ -1,4 +1,4 @@
-import { useState } from 'react';
+import { useState, useEffect } from 'react';
 import { Link, useLocation } from 'react-router-dom';
 import { useTranslation } from 'react-i18next';
 import {
@@ -11,10 +11,11 @@ import {
   Users,
   MoreHorizontal,
   Trash2,
-  LogOut
+  LogOut,
+  Clock
 } from 'lucide-react';
 import { invoke } from '@tauri-apps/api/core';
-import { Profile, UserSession } from '../types';
+import { Profile, ProfileSummary, UserSession } from '../types';
 import { clsx } from 'clsx';
 
 interface SidebarProps {
@@ -38,6 +39,13 @@ export default function Sidebar({
 }: SidebarProps) {
   const { t } = useTranslation();
   const location = useLocation();
+  const [summaries, setSummaries] = useState<Record<string, ProfileSummary>>({});
+
+  useEffect(() => {
+    invoke<ProfileSummary[]>('get_profiles_summary')
+      .then(list => setSummaries(Object.fromEntries(list.map(summary => [summary.id, summary]))))
+      .catch(error => console.error('Failed to load profile summaries:', error));
+  }, [profiles]);
   const [showProfileMenu, setShowProfileMenu] = useState<string | null>(null);
 
   const handleDeleteProfile = async (profileId: string) => {
@@ -128,6 +136,29 @@ export default function Sidebar({
                   <div className="profile-meta">
                     {profile.bucket}/{profile.prefix}
                   </div>
+                  {summaries[profile.id] && (summaries[profile.id].is_scheduled || summaries[profile.id].last_operation_status) && (
+                    <div className="profile-status">
+                      {summaries[profile.id].is_scheduled && (
+                        <span
+                          title={summaries[profile.id].next_run
+                            ? t('sidebar.nextRun', { time: new Date(summaries[profile.id].next_run!).toLocaleString() })
+                            : t('sidebar.scheduled')}
+                        >
+                          <Clock size={10} />
+                        </span>
+                      )}
+                      {summaries[profile.id].last_operation_status && (
+                        <span
+                          className={`status-${summaries[profile.id].last_operation_status!.toLowerCase()}`}
+                          title={summaries[profile.id].last_run_at ? new Date(summaries[profile.id].last_run_at!).toLocaleString() : undefined}
+                        >
+                          {summaries[profile.id].last_operation_status === 'PartialSuccess'
+                            ? t('dashboard.statusPartial')
+                            : t(`dashboard.status${summaries[profile.id].last_operation_status}`)}
+                        </span>
+                      )}
+                    </div>
+                  )}
                 </div>
               </div>
 
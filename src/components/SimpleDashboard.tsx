@@ -232,7 +232,7 @@ export default function SimpleDashboard({ profile, userSession }: SimpleDashboar
                     <span className="operation-type">{op.operation_type}</span>
                     <span className={`operation-status status-${op.status.toLowerCase()}`}>
                       {op.status === 'Completed' && <CheckCircle size={14} />}
-                      {op.status === 'Failed' && <AlertCircle size={14} />}
+                      {(op.status === 'Failed' || op.status === 'PartialSuccess') && <AlertCircle size={14} />}
                       {op.status}
                     </span>
                   </div>
@@ -7,9 +7,12 @@ import {
   Key, 
   Copy, 
   Download,
-  RefreshCw
+  RefreshCw,
+  UserPlus,
+  ShieldCheck
 } from 'lucide-react';
-import { Profile, Employee } from '../types';
+import { Profile, Employee, BucketInfo, ConfigConsistency, IsolationReport, LifecycleConfig } from '../types';
+import { errorMessage } from '../services/backupErrors';
 
 interface UserManagementProps {
   profile: Profile | null;
@@ -22,12 +25,128 @@ export default function UserManagement({ profile }: UserManagementProps) {
   const [rcloneConfig, setRcloneConfig] = useState<string>('');
   const [showCredentials, setShowCredentials] = useState<Record<string, boolean>>({});
 
+  const [newUsername, setNewUsername] = useState('');
+  const [isAdding, setIsAdding] = useState(false);
+  const [bucketInfo, setBucketInfo] = useState<BucketInfo | null>(null);
+  const [configConsistency, setConfigConsistency] = useState<ConfigConsistency | null>(null);
+  const [checkingConfig, setCheckingConfig] = useState(false);
+  const [checkingBucket, setCheckingBucket] = useState(false);
+  const [testingIsolation, setTestingIsolation] = useState<string | null>(null);
+  const [lifecycle, setLifecycle] = useState<LifecycleConfig | null>(null);
+  const [savingLifecycle, setSavingLifecycle] = useState(false);
+
   useEffect(() => {
     if (profile?.aws_config?.employees) {
       setEmployees(profile.aws_config.employees);
     }
+    setLifecycle(profile?.aws_config?.lifecycle_config ?? null);
   }, [profile]);
 
+  const saveLifecycle = async () => {
+    if (!profile || !lifecycle) return;
+
+    setSavingLifecycle(true);
+    try {
+      await invoke('update_lifecycle_policy', {
+        profileId: profile.id,
+        lifecycleConfig: lifecycle
+      });
+      alert(t('userManagement.lifecycleSaved'));
+    } catch (error) {
+      console.error('Failed to update lifecycle policy:', error);
+      alert(`${t('userManagement.lifecycleSaveFailed')}: ${error}`);
+    } finally {
+      setSavingLifecycle(false);
+    }
+  };
+
+  const loadEmployees = async () => {
+    if (!profile) return;
+
+    try {
+      // This page shows keys and generates configs, so it needs the secrets
+      const list = await invoke<Employee[]>('list_employees', {
+        profileId: profile.id,
+        includeSecrets: true
+      });
+      setEmployees(list);
+    } catch (error) {
+      console.error('Failed to load employees:', error);
+    }
+  };
+
+  const checkBucket = async () => {
+    if (!profile) return;
+
+    setCheckingBucket(true);
+    try {
+      setBucketInfo(await invoke<BucketInfo>('get_bucket_info', { profileId: profile.id }));
+    } catch (error) {
+      console.error('Failed to read bucket settings:', error);
+      alert(`${t('userManagement.bucketCheckFailed')}: ${error}`);
+    } finally {
+      setCheckingBucket(false);
+    }
+  };
+
+  const checkRcloneConfig = async () => {
+    if (!profile) return;
+
+    setCheckingConfig(true);
+    try {
+      setConfigConsistency(await invoke<ConfigConsistency>('check_config_consistency', { profileId: profile.id }));
+    } catch (error) {
+      console.error('Failed to check rclone config:', error);
+      alert(`${t('userManagement.configCheckFailed')}: ${errorMessage(error)}`);
+    } finally {
+      setCheckingConfig(false);
+    }
+  };
+
+  const testIsolation = async (employee: Employee) => {
+    if (!profile) return;
+
+    setTestingIsolation(employee.id);
+    try {
+      const report = await invoke<IsolationReport>('test_employee_isolation', {
+        profileId: profile.id,
+        employeeId: employee.id
+      });
+      const lines = report.checks.map(check => {
+        const outcome = check.error ?? (check.denied ? t('userManagement.isolationDenied') : t('userManagement.isolationAllowed'));
+        return `${check.passed ? '✅' : '❌'} ${check.target}: ${outcome}`;
+      });
+      const summary = report.isolated
+        ? t('userManagement.isolationPassed', { username: report.username })
+        : t('userManagement.isolationFailed', { username: report.username });
+      alert(`${summary}\n\n${lines.join('\n')}`);
+    } catch (error) {
+      console.error('Failed to test isolation:', error);
+      alert(`${t('userManagement.isolationTestFailed')}: ${errorMessage(error)}`);
+    } finally {
+      setTestingIsolation(null);
+    }
+  };
+
+  const addEmployee = async () => {
+    if (!profile || !newUsername.trim()) return;
+
+    setIsAdding(true);
+    try {
+      const employee = await invoke<Employee>('add_employee', {
+        profileId: profile.id,
+        username: newUsername.trim()
+      });
+      setEmployees(prev => [...prev, employee]);
+      setNewUsername('');
+    } catch (error) {
+      console.error('Failed to add employee:', error);
+      alert(`${t('userManagement.addEmployeeFailed')}: ${error}`);
+    } finally {
+      setIsAdding(false);
+    }
+  };
+
   const generateRcloneConfig = async (employee: Employee) => {
     if (!profile?.aws_config) return;
 
@@ -58,6 +177,31 @@ acl = private`;
     }
   };
 
+  const downloadBundle = async (employee: Employee) => {
+    if (!profile) return;
+
+    try {
+      const outputPath = await save({
+        defaultPath: `onboarding-${employee.username}.zip`,
+        filters: [{
+          name: 'Zip Archives',
+          extensions: ['zip']
+        }]
+      });
+      if (!outputPath) return;
+
+      const written = await invoke<string>('create_employee_bundle', {
+        profileId: profile.id,
+        employeeId: employee.id,
+        outputPath
+      });
+      alert(t('userManagement.bundleSaved', { path: written }));
+    } catch (error) {
+      console.error('Failed to create onboarding bundle:', error);
+      alert(`${t('userManagement.downloadFailed')}: ${error}`);
+    }
+  };
+
   const copyToClipboard = (text: string, label: string) => {
     navigator.clipboard.writeText(text).then(() => {
       alert(`${label} copied to clipboard!`);
@@ -169,14 +313,128 @@ acl = private`;
       </div>
 
       <div className="management-content">
+        <div className="bucket-security">
+          <div className="section-header">
+            <h2>{t('userManagement.bucketSecurity')}</h2>
+            <button className="btn btn-secondary btn-small" onClick={checkBucket} disabled={checkingBucket}>
+              <ShieldCheck size={14} />
+              {checkingBucket ? t('userManagement.checkingBucket') : t('userManagement.checkBucket')}
+            </button>
+            <button className="btn btn-secondary btn-small" onClick={checkRcloneConfig} disabled={checkingConfig}>
+              <ShieldCheck size={14} />
+              {checkingConfig ? t('userManagement.checkingBucket') : t('userManagement.checkConfig')}
+            </button>
+          </div>
+
+          {configConsistency && (
+            <div className="review-section">
+              <div className="review-item"><strong>rclone.conf:</strong> {configConsistency.config_path} [{configConsistency.remote}]</div>
+              {configConsistency.drift.length === 0 ? (
+                <div className="success-message">{t('userManagement.configMatches')}</div>
+              ) : configConsistency.drift.map((item, idx) => (
+                <div key={idx} className="warning-box">{item}</div>
+              ))}
+            </div>
+          )}
+
+          {bucketInfo && (
+            <div className="review-section">
+              <div className="review-item"><strong>Versioning:</strong> {bucketInfo.versioning || 'Off'}</div>
+              <div className="review-item">
+                <strong>Encryption:</strong> {bucketInfo.encryption || 'None'}
+                {bucketInfo.bucket_key_enabled ? ' (bucket key)' : ''}
+              </div>
+              <div className="review-item"><strong>Public access blocked:</strong> {bucketInfo.public_access_blocked ? 'Yes' : 'No'}</div>
+              <div className="review-item"><strong>TLS-only policy:</strong> {bucketInfo.tls_only_policy ? 'Yes' : 'No'}</div>
+              <div className="review-item">
+                <strong>Lifecycle:</strong>
+                <div className="sub-items">
+                  {bucketInfo.lifecycle_rules.length === 0 ? <div>None</div> : bucketInfo.lifecycle_rules.map((rule, idx) => (
+                    <div key={idx}>{rule}</div>
+                  ))}
+                </div>
+              </div>
+              {lifecycle && (
+                <div className="form-group">
+                  <label className="checkbox-label">
+                    <input
+                      type="checkbox"
+                      checked={lifecycle.enabled}
+                      onChange={(e) => setLifecycle({ ...lifecycle, enabled: e.target.checked })}
+                    />
+                    <span>{t('userManagement.lifecycleEnabled')}</span>
+                  </label>
+                  {lifecycle.enabled && (
+                    <div className="form-row">
+                      <label>
+                        {t('userManagement.daysToIa')}
+                        <input
+                          type="number"
+                          min={30}
+                          value={lifecycle.days_to_ia}
+                          onChange={(e) => setLifecycle({ ...lifecycle, days_to_ia: Number(e.target.value) })}
+                        />
+                      </label>
+                      <label>
+                        {t('userManagement.daysToGlacier')}
+                        <input
+                          type="number"
+                          min={lifecycle.days_to_ia + 1}
+                          value={lifecycle.days_to_glacier}
+                          onChange={(e) => setLifecycle({ ...lifecycle, days_to_glacier: Number(e.target.value) })}
+                        />
+                      </label>
+                      <label>
+                        {t('userManagement.daysToDeepArchive')}
+                        <input
+                          type="number"
+                          min={lifecycle.days_to_ia + 1}
+                          value={lifecycle.days_to_deep_archive}
+                          onChange={(e) => setLifecycle({ ...lifecycle, days_to_deep_archive: Number(e.target.value) })}
+                        />
+                      </label>
+                    </div>
+                  )}
+                  <button className="btn btn-primary btn-small" onClick={saveLifecycle} disabled={savingLifecycle}>
+                    {t('userManagement.saveLifecycle')}
+                  </button>
+                </div>
+              )}
+              {bucketInfo.drift.length === 0 ? (
+                <div className="success-message">{t('userManagement.bucketMatchesSetup')}</div>
+              ) : bucketInfo.drift.map((item, idx) => (
+                <div key={idx} className="warning-box">{item}</div>
+              ))}
+            </div>
+          )}
+        </div>
+
         <div className="employees-section">
           <div className="section-header">
             <h2>{t('userManagement.employees')} ({employees.length})</h2>
-            <button className="btn-icon" title={t('common.refresh')}>
+            <button className="btn-icon" title={t('common.refresh')} onClick={loadEmployees}>
               <RefreshCw size={16} />
             </button>
           </div>
 
+          <div className="add-employee">
+            <input
+              type="text"
+              value={newUsername}
+              onChange={(e) => setNewUsername(e.target.value)}
+              placeholder={t('userManagement.newEmployeeUsername')}
+              disabled={isAdding}
+            />
+            <button
+              className="btn btn-primary btn-small"
+              onClick={addEmployee}
+              disabled={isAdding || !newUsername.trim()}
+            >
+              <UserPlus size={14} />
+              {isAdding ? t('userManagement.addingEmployee') : t('userManagement.addEmployee')}
+            </button>
+          </div>
+
           {employees.length === 0 ? (
             <div className="empty-state-small">
               <Users size={24} />
@@ -209,6 +467,23 @@ acl = private`;
                     >
                       {t('userManagement.generateConfig')}
                     </button>
+
+                    <button
+                      className="btn btn-secondary btn-small"
+                      onClick={() => downloadBundle(employee)}
+                    >
+                      <Download size={14} />
+                      {t('userManagement.downloadBundle')}
+                    </button>
+
+                    <button
+                      className="btn btn-secondary btn-small"
+                      onClick={() => testIsolation(employee)}
+                      disabled={testingIsolation !== null}
+                    >
+                      <ShieldCheck size={14} />
+                      {testingIsolation === employee.id ? t('userManagement.testingIsolation') : t('userManagement.testIsolation')}
+                    </button>
                   </div>
 
                   {showCredentials[employee.id] && (
@@ -152,9 +152,11 @@ acl = private
         lifecycle_config: {
           enabled: false,
           days_to_ia: 30,
-          days_to_glacier: 365
+          days_to_glacier: 365,
+          days_to_deep_archive: 999999
         },
-        employees: [] // Empty for user profiles
+        employees: [], // Empty for user profiles
+        provider: 'AWS'
       };
 
       // Create user profile with proper AWS config and rclone configuration
@@ -168,10 +170,7 @@ acl = private
         rclone_bin: 'rclone', // Default binary
         rclone_conf: configPath, // Generated config file
         rclone_flags: [
-          '--checksum',
-          '--fast-list', 
-          '--transfers=8',
-          '--checkers=32'
+          '--fast-list'
         ],
         // Add AWS config so backend auto-setup works
         aws_config: awsConfig
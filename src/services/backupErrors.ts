@@ -0,0 +1,36 @@
+import { FileChange } from '../types';
+
+// Errors returned by the rclone commands (see src-tauri/src/error.rs)
+export type BackupErrorKind =
+  | 'RcloneNotFound'
+  | 'ConfigInvalid'
+  | 'RemoteUnreachable'
+  | 'AuthFailed'
+  | 'SourceMissing'
+  | 'Timeout'
+  | 'Io'
+  | 'Parse'
+  | 'ArchivedObjects'
+  | 'Other'
+  | 'SyncDeletionsPending';
+
+export interface BackupError {
+  kind: BackupErrorKind;
+  message: string;
+  files_to_delete?: FileChange[];
+}
+
+export function isBackupError(error: unknown): error is BackupError {
+  return typeof error === 'object' && error !== null && 'kind' in error && 'message' in error;
+}
+
+export function errorKind(error: unknown): BackupErrorKind | null {
+  return isBackupError(error) ? error.kind : null;
+}
+
+// Message text for commands that reject with either a BackupError or a plain string
+export function errorMessage(error: unknown): string {
+  if (isBackupError(error)) return error.message;
+  if (error instanceof Error) return error.message;
+  return String(error);
+}
@@ -46,6 +46,34 @@ export interface Profile {
   mode: BackupMode;
   schedule?: Schedule;
   rclone_flags: string[];
+  include_patterns: string[];
+  exclude_patterns: string[];
+  min_size?: string;
+  max_size?: string;
+  min_age?: string;
+  max_age?: string;
+  max_parallel_sources: number;
+  transfer_timeout_secs: number;
+  transfers: number;
+  checkers: number;
+  retries: number; // Attempts at the whole transfer when files fail; at least 1
+  low_level_retries: number; // Attempts at each failed request
+  retries_sleep_secs: number; // Pause between whole-transfer attempts
+  hash_check: HashMode;
+  log_level: LogLevel;
+  symlink_mode: SymlinkMode;
+  stats_interval_secs: number; // How often progress goes to the backup/restore log
+  detailed_stats: boolean; // Multi-line stats with per-file progress instead of one line
+  tags: string[];
+  encryption_enabled: boolean;
+  rclone_config_encrypted: boolean;
+  notifications: NotificationConfig;
+  confirm_sync_deletions: boolean;
+  sync_delete_threshold: number;
+  check_write_access: boolean; // Test-write to the destination before each backup
+  use_backup_dir: boolean; // Deleted and replaced files go to _deleted/<time> instead of being lost
+  secondary_destinations: string[];
+  fail_on_secondary_error: boolean;
   aws_config?: AwsConfig;
   created_at: string;
   updated_at: string;
@@ -53,6 +81,20 @@ export interface Profile {
 
 export type ProfileType = 'Admin' | 'User';
 
+export interface AppSettings {
+  default_region: string;
+  preferred_rclone_bin?: string;
+  aws_cli_path?: string;
+  log_retention_count: number;
+  log_retention_days?: number;
+  default_transfers: number;
+  default_checkers: number;
+  http_proxy?: string;
+  https_proxy?: string;
+  no_proxy?: string;
+  bind_address?: string; // 0.0.0.0 forces IPv4, ::0 IPv6
+}
+
 export interface AwsConfig {
   aws_access_key_id: string;
   aws_secret_access_key: string;
@@ -61,12 +103,17 @@ export interface AwsConfig {
   bucket_name: string;
   lifecycle_config: LifecycleConfig;
   employees: Employee[];
+  provider: string;
+  endpoint?: string;
+  session_token?: string;
+  needs_verification: boolean;
 }
 
 export interface LifecycleConfig {
   enabled: boolean;
   days_to_ia: number;
   days_to_glacier: number;
+  days_to_deep_archive: number;
 }
 
 export interface Employee {
@@ -77,10 +124,19 @@ export interface Employee {
   secret_access_key: string;
   rclone_config_generated: boolean;
   created_at: string;
+  prefix?: string;
 }
 
+export type HashMode = 'Checksum' | 'SizeModTime' | 'SizeOnly';
+
+export type LogLevel = 'Quiet' | 'Normal' | 'Verbose' | 'Debug';
+
+export type SymlinkMode = 'Skip' | 'Copy' | 'Follow';
+
 export type BackupMode = 'Copy' | 'Sync';
 
+export type RemoteKind = 'S3' | 'GoogleDrive' | 'Dropbox' | 'Backblaze';
+
 export interface Schedule {
   enabled: boolean;
   frequency: ScheduleFrequency;
@@ -89,6 +145,18 @@ export interface Schedule {
   next_run?: string;
 }
 
+export interface ScheduleConflict {
+  profile_id: string;
+  profile_name: string;
+  other_profile_id: string;
+  other_profile_name: string;
+  destination: string;
+  other_destination: string;
+  first_overlap: string;
+  minutes_apart: number;
+  sync_mode: boolean;
+}
+
 export type ScheduleFrequency =
   | 'Daily'  // Unit variant serializes as string
   | { Weekly: number }
@@ -103,6 +171,14 @@ export interface CloudFile {
   mime_type?: string;
 }
 
+export interface FileVersion {
+  version_id: string;
+  path: string;
+  size: number;
+  mod_time: string;
+  is_current: boolean;
+}
+
 export interface BackupOperation {
   id: string;
   profile_id: string;
@@ -112,12 +188,52 @@ export interface BackupOperation {
   completed_at?: string;
   files_transferred: number;
   bytes_transferred: number;
+  files_skipped?: number;
   error_message?: string;
   log_output: string;
+  repeat_count?: number; // Identical failures merged by compact_history, this one included
+  first_started_at?: string; // Start of the oldest merged failure
+}
+
+export interface BackupLogsPage {
+  operations: BackupOperation[];
+  total_count: number;
 }
 
 export type OperationType = 'Backup' | 'Restore' | 'Preview';
-export type OperationStatus = 'Running' | 'Completed' | 'Failed' | 'Cancelled';
+export type OperationStatus = 'Running' | 'Completed' | 'PartialSuccess' | 'Failed' | 'Cancelled';
+
+export interface S3Pricing {
+  standard_per_gb_month: number;
+  standard_ia_per_gb_month: number;
+  glacier_per_gb_month: number;
+  deep_archive_per_gb_month: number;
+  put_per_1000: number;
+  ia_transition_per_1000: number;
+  glacier_transition_per_1000: number;
+  deep_archive_transition_per_1000: number;
+}
+
+export interface CostEstimate {
+  total_bytes: number;
+  total_files: number;
+  storage_class: string;
+  first_month_storage_cost: number;
+  monthly_storage_cost: number;
+  upload_request_cost: number;
+  transition_request_cost: number;
+  pricing: S3Pricing;
+}
+
+export interface InvalidRcloneFlag {
+  flag: string;
+  message: string;
+}
+
+export interface RcloneRemote {
+  name: string;
+  remote_type: string;
+}
 
 export interface BackupPreview {
   files_to_copy: FileChange[];
@@ -127,6 +243,28 @@ export interface BackupPreview {
   total_size: number;
 }
 
+export interface SourceInfo {
+  path: string;
+  exists: boolean;
+  mounted: boolean; // false when the external drive it lives on isn't connected
+  mount_point?: string; // Drive it lives on, e.g. "/Volumes/USB"; absent on the system volume
+  total_bytes?: number; // Volume sizes; absent when the drive isn't mounted
+  used_bytes?: number;
+  free_bytes?: number; // Available to this user
+}
+
+export interface ProfileFieldError {
+  field: string; // e.g. "bucket", "sources[1]" or "schedule.time"; empty for the whole file
+  message: string;
+}
+
+export interface DeletedSnapshot {
+  name: string; // Folder under _deleted
+  taken_at?: string;
+  files: number;
+  bytes: number;
+}
+
 export interface FileChange {
   path: string;
   size: number;
@@ -135,9 +273,296 @@ export interface FileChange {
 
 export type ChangeAction = 'Copy' | 'Update' | 'Delete';
 
+export interface SourceChanges {
+  source: string;
+  added: FileChange[];
+  modified: FileChange[];
+  deleted: FileChange[]; // Sync profiles only
+}
+
+export interface ChangesSinceBackup {
+  since: string; // When the last successful backup finished
+  sources: SourceChanges[];
+  total_files: number;
+  total_size: number;
+}
+
+export interface CloudFilesPage {
+  files: CloudFile[];
+  page: number;
+  page_size: number;
+  total_count: number;
+  has_more: boolean;
+}
+
+export interface CloudFilesBatch {
+  path: string;
+  files: CloudFile[];
+  listed: number;
+}
+
+export interface CloudSearchBatch {
+  pattern: string;
+  files: CloudFile[];
+  found: number;
+}
+
+export interface RestoreProgress {
+  operation_id: string;
+  remote_path: string;
+  path_index: number;
+  path_count: number;
+  bytes_done: number;
+  bytes_total: number;
+  percent: number;
+  speed?: string;
+  eta?: string;
+}
+
+export interface BackupStarted {
+  profile_id: string;
+  operation_id: string;
+}
+
+export interface TransferringFile {
+  name: string;
+  size: number;
+  bytes: number;
+  percentage: number;
+  speed: number;
+  eta?: number;
+}
+
+export interface LiveOperation {
+  operation_id: string;
+  profile_id: string;
+  operation_type: OperationType;
+  status: OperationStatus;
+  started_at: string;
+  elapsed_seconds: number;
+  current_item?: string;
+  items_done: number;
+  items_total: number;
+  files_done: number;
+  bytes_done: number;
+  bytes_total: number;
+  percent: number;
+}
+
+export interface TransferStats {
+  operation_id: string;
+  bytes: number;
+  total_bytes: number;
+  speed: number;
+  eta?: number;
+  transfers: number;
+  total_transfers: number;
+  checks: number;
+  errors: number;
+  elapsed_seconds: number;
+  transferring: TransferringFile[];
+}
+
+export type GlacierRestoreTier = 'Expedited' | 'Standard' | 'Bulk';
+
+export interface GlacierRestoreRequest {
+  tier: GlacierRestoreTier;
+  days: number;
+  requested: number;
+  already_in_progress: number;
+  not_archived: number;
+  failed: string[];
+  estimated_thaw: string;
+}
+
+export interface GlacierRestoreStatus {
+  path: string;
+  archived_files: number;
+  not_requested: number;
+  in_progress: number;
+  restored: number;
+  restored_until?: string;
+  ready: boolean;
+}
+
 export interface RcloneOutput {
   stdout: string;
   stderr: string;
   success: boolean;
   exit_code: number;
-}
\ No newline at end of file
+}
+
+export type ConnectionStatus = 'Success' | 'AuthFailed' | 'BucketNotFound' | 'NetworkError' | 'Unknown';
+
+export interface ConnectionTestResult {
+  status: ConnectionStatus;
+  message: string;
+  duration_ms: number;
+  warning?: string;
+}
+
+export interface SchedulePreview {
+  platform: string;
+  script_path: string;
+  script: string;
+  wrapper_script?: string;
+  job_path?: string;
+  job_definition?: string;
+  next_run?: string;
+}
+
+export interface ScheduledTestRun {
+  success: boolean;
+  exit_code: number;
+  output: string;
+  log_excerpt: string;
+}
+
+export interface CryptSecrets {
+  password: string;
+  password2: string;
+}
+
+export interface DiagnosticCheck {
+  name: string;
+  passed: boolean;
+  detail: string;
+}
+
+export interface RcloneBinary {
+  path: string;
+  version?: string;
+  source: 'bundled' | 'system';
+  warning?: string;
+}
+
+export interface PlannedIamUser {
+  username: string;
+  is_admin: boolean;
+  policy_name: string;
+  policy_document: string;
+}
+
+export interface BucketInfo {
+  bucket_name: string;
+  region: string;
+  versioning?: string;
+  encryption?: string;
+  bucket_key_enabled: boolean;
+  public_access_blocked: boolean;
+  tls_only_policy: boolean;
+  lifecycle_rules: string[];
+  drift: string[];
+}
+
+export interface ProfileSummary {
+  id: string;
+  name: string;
+  is_scheduled: boolean;
+  next_run?: string;
+  last_operation_status?: OperationStatus;
+  last_run_at?: string;
+}
+
+export interface PurgeResult {
+  location: string;
+  files_deleted: number;
+  bytes_freed: number;
+  all_versions: boolean;
+}
+
+export interface PrefixRename {
+  old_prefix: string;
+  new_prefix: string;
+  files_moved: number;
+  bytes_moved: number;
+  merged: boolean;
+  policy_updated_for?: string;
+}
+
+export interface ConfigConsistency {
+  remote: string;
+  config_path: string;
+  drift: string[];
+}
+
+export interface IsolationCheck {
+  target: string;
+  expect_denied: boolean;
+  denied: boolean;
+  error?: string;
+  passed: boolean;
+}
+
+export interface KeyRotation {
+  employee: Employee;
+  rclone_config: string;
+  bundle_path?: string;
+  warnings: string[];
+}
+
+export interface IsolationReport {
+  employee_id: string;
+  username: string;
+  checks: IsolationCheck[];
+  isolated: boolean;
+}
+
+export interface NotificationConfig {
+  webhook_url?: string;
+  notify_on_success: boolean;
+}
+
+export interface AwsSetupPlan {
+  bucket_name: string;
+  region: string;
+  bucket_settings: string[];
+  bucket_policy: string;
+  users: PlannedIamUser[];
+  warnings: string[];
+}
+
+export interface AssumedRoleCredentials {
+  access_key_id: string;
+  secret_access_key: string;
+  session_token: string;
+  expiration: string;
+  profile_name: string;
+}
+
+export interface RcloneVersion {
+  major: number;
+  minor: number;
+  patch: number;
+  pre_release?: string;
+  raw: string;
+  supported: boolean;
+}
+
+export interface ProfileExport {
+  format_version: number;
+  app_version: string;
+  exported_at: string;
+  secrets_included: boolean;
+  profile: Profile;
+  crypt_secrets?: CryptSecrets;
+  rclone_config_password?: string;
+}
+
+export interface OsScheduleEntry {
+  profile_id: string;
+  kind: 'launchd' | 'task_scheduler' | 'script';
+  location: string;
+  orphaned: boolean;
+}
+
+export interface ScheduleCleanupResult {
+  found: OsScheduleEntry[];
+  removed: OsScheduleEntry[];
+}
+
+export interface PrefixUsage {
+  prefix: string;
+  total_bytes: number;
+  file_count: number;
+}